@@ -0,0 +1,106 @@
+//! Defines [Control], [Termination] and [Limits].
+
+use std::time::Duration;
+
+use crate::error::SolverError;
+
+/// Control flow request, which can be returned by the callbacks of [crate::Solver::on],
+/// [crate::Solver::on_mut] and [crate::Solver::on_step] (callbacks returning `()` continue the
+/// integration).
+#[derive(Clone, Debug, Default, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub enum Control {
+    /// Continue the integration.
+    #[default]
+    Continue,
+    /// Stop the integration at the current time with the given reason, see
+    /// [Termination::Stopped].
+    Stop(String),
+    /// Continue the integration, restarting the stepsize controller (see
+    /// [crate::stepsize::StepsizeController::init]) after the step, e.g. after a discontinuous
+    /// change of the state.
+    RestartStepsize,
+}
+
+impl From<()> for Control {
+    fn from(_: ()) -> Self {
+        Control::Continue
+    }
+}
+
+impl Control {
+    /// Combines the request with the `other` one, requested after it: [Control::Stop] takes
+    /// precedence over [Control::RestartStepsize], which takes precedence over
+    /// [Control::Continue].
+    pub fn request(&mut self, other: Control) {
+        if !matches!(self, Control::Stop(_)) && other != Control::Continue {
+            *self = other;
+        }
+    }
+}
+
+/// Reason of the end of the integration, available as [crate::state::State::termination].
+#[derive(Clone, Debug, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub enum Termination<T> {
+    /// The end of the integration interval is reached.
+    ReachedEnd,
+    /// A callback requested [Control::Stop] with the `reason`. The `event` is the index of the
+    /// located event, whose callback made the request, in the order of addition to the solver
+    /// (`None` for other callbacks).
+    Stopped {
+        event: Option<usize>,
+        reason: String,
+    },
+    /// The integration is ended before the step, which would exceed the limit, see [Limits].
+    LimitReached(Limit),
+    /// The integration is cancelled with [crate::Solver::cancel_token].
+    Cancelled,
+    /// The integration failed with the error, which is also returned by [crate::Solver::try_run]
+    /// or [crate::Stepper::step].
+    Failed(SolverError<T>),
+}
+
+/// Limits of a single run, see [crate::Solver::max_steps], [crate::Solver::max_rhs_evals] and
+/// [crate::Solver::time_limit]. The limits are checked before each step and after each rejected
+/// attempt of a step, and count from the start of the run (or from its resumption, see
+/// [crate::Solver::resume]).
+#[derive(Clone, Copy, Debug, Default, PartialEq)]
+pub struct Limits {
+    /// Maximum number of committed steps.
+    pub max_steps: Option<usize>,
+    /// Maximum number of evaluations of the right hand side of the equation. Since the limit is
+    /// checked between the attempts of the steps, it may be exceeded by the evaluations of the
+    /// last attempt.
+    pub max_rhs_evals: Option<usize>,
+    /// Maximum wall-clock time.
+    pub time_limit: Option<Duration>,
+}
+
+/// Limit of [Limits], which ended the integration, see [Termination::LimitReached].
+#[derive(Clone, Copy, Debug, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub enum Limit {
+    /// [Limits::max_steps]
+    MaxSteps,
+    /// [Limits::max_rhs_evals]
+    MaxRhsEvals,
+    /// [Limits::time_limit]
+    TimeLimit,
+}
+
+impl Limits {
+    /// Returns the limit reached after `steps` steps, `rhs_evals` evaluations and `elapsed` time,
+    /// if any.
+    pub fn reached(&self, steps: usize, rhs_evals: usize, elapsed: Duration) -> Option<Limit> {
+        if self.max_steps.is_some_and(|max| steps >= max) {
+            Some(Limit::MaxSteps)
+        } else if self.max_rhs_evals.is_some_and(|max| rhs_evals >= max) {
+            Some(Limit::MaxRhsEvals)
+        } else if self.time_limit.is_some_and(|max| elapsed >= max) {
+            Some(Limit::TimeLimit)
+        } else {
+            None
+        }
+    }
+}
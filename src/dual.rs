@@ -0,0 +1,769 @@
+//! Defines [Dual], the forward-mode dual number type for automatic differentiation, [jacobian],
+//! which evaluates the Jacobian of a function with it, and [DualStateRef], the state lifted into
+//! it.
+
+use std::{
+    fmt::{Debug, Display},
+    ops::{Add, AddAssign, Div, DivAssign, Mul, MulAssign, Neg, Rem, RemAssign, Sub, SubAssign},
+};
+
+use approx::{AbsDiffEq, RelativeEq, UlpsEq};
+use nalgebra::{ComplexField, Field, RealField, SMatrix, SVector, SimdValue};
+use num_traits::{FromPrimitive, Num, One, Signed, Zero};
+use simba::scalar::{SubsetOf, SupersetOf};
+
+use crate::{
+    StateRef,
+    error::HistoryError,
+    initial_condition::InitialCondition,
+    state::{LiftedHistory, State, StateHistory},
+    traits::RealVectorSpace,
+};
+
+/// Dual number `re + eps·ε` with `N` partial derivatives, where `ε² = 0`.
+///
+/// It implements [RealField], so it can be used as the scalar type of generic code (including
+/// the solver and equation closures), which then computes the derivatives of its results along
+/// with the values. The equality, ordering and rounding only involve the value `re`, so that the
+/// control flow of generic code follows the values, as it does for the real numbers, while
+/// [Zero::is_zero] involves the partial derivatives as well (so that, for instance, a jump of the
+/// derivatives alone is a change of the state).
+#[derive(Clone, Copy, Debug)]
+pub struct Dual<T: RealField + Copy, const N: usize> {
+    /// Value
+    pub re: T,
+    /// Partial derivatives
+    pub eps: SVector<T, N>,
+}
+
+impl<T: RealField + Copy, const N: usize> Dual<T, N> {
+    pub fn new(re: T, eps: SVector<T, N>) -> Self {
+        Self { re, eps }
+    }
+
+    /// Returns the dual number with zero partial derivatives.
+    pub fn constant(re: T) -> Self {
+        Self::new(re, SVector::zeros())
+    }
+
+    /// Returns the dual number of the `i`-th independent variable, with the unit `i`-th partial
+    /// derivative.
+    pub fn variable(re: T, i: usize) -> Self {
+        let mut eps = SVector::zeros();
+        eps[i] = T::one();
+        Self::new(re, eps)
+    }
+
+    /// Applies the function with the value `f` and derivative `df` at `re`.
+    fn chain(self, f: T, df: T) -> Self {
+        Self::new(f, self.eps * df)
+    }
+}
+
+/// Evaluates the function `f` at `x` and returns its value together with its Jacobian,
+/// computed by forward-mode automatic differentiation with [Dual].
+pub fn jacobian<T: RealField + Copy, const N: usize, const M: usize>(
+    f: impl FnOnce(SVector<Dual<T, N>, N>) -> SVector<Dual<T, N>, M>,
+    x: SVector<T, N>,
+) -> (SVector<T, M>, SMatrix<T, M, N>) {
+    let y = f(SVector::from_fn(|i, _| Dual::variable(x[i], i)));
+    (y.map(|y| y.re), SMatrix::from_fn(|i, j| y[i].eps[j]))
+}
+
+/// [StateRef] of a state with the scalar type `T` and the position of `N` components, lifted into
+/// the dual numbers with `K` partial derivatives. Its history lookups are evaluated through the
+/// history of the original state, so the type of the initial condition is only a placeholder.
+/// See [State::jacobian] and [crate::Solver::dual_sensitivity_equation].
+pub type DualStateRef<'s, T, const K: usize, const N: usize, const S: usize, const I: usize> =
+    StateRef<'s, Dual<T, K>, SVector<Dual<T, K>, N>, S, I, SVector<Dual<T, K>, N>>;
+
+/// Position, which is lifted into the dual numbers with the partial derivatives it carries, see
+/// [DualStateRef].
+pub(crate) trait Lift<T: RealField + Copy, const K: usize, const N: usize> {
+    fn lift(&self) -> SVector<Dual<T, K>, N>;
+}
+
+impl<T: RealField + Copy, const K: usize, const N: usize> Lift<T, K, N> for SVector<T, N> {
+    fn lift(&self) -> SVector<Dual<T, K>, N> {
+        self.map(Dual::constant)
+    }
+}
+
+/// The lookups at the dual times `t` are evaluated at `t.re`, and the positions gain the
+/// derivative of the history times `t.eps`, so that the derivatives of the state-dependent delays
+/// are taken into account (but not for the derivatives of the history).
+impl<
+    T: RealField + Copy,
+    P: RealVectorSpace<T> + Lift<T, K, N>,
+    const S: usize,
+    const I: usize,
+    IC: InitialCondition<T, P>,
+    const K: usize,
+    const N: usize,
+> LiftedHistory<Dual<T, K>, SVector<Dual<T, K>, N>> for StateHistory<T, P, S, I, IC>
+{
+    fn eval(&self, t: Dual<T, K>, derivative: bool) -> SVector<Dual<T, K>, N> {
+        if derivative {
+            return StateHistory::eval::<1>(self, t.re).lift();
+        }
+        let mut p = StateHistory::eval::<0>(self, t.re).lift();
+        if !t.eps.is_zero() {
+            let d = StateHistory::eval::<1>(self, t.re).lift();
+            p.zip_apply(&d, |p, d| p.eps += t.eps * d.re);
+        }
+        p
+    }
+
+    fn try_eval(
+        &self,
+        t: Dual<T, K>,
+        derivative: bool,
+    ) -> Result<SVector<Dual<T, K>, N>, HistoryError<Dual<T, K>>> {
+        let lift_error = |error: HistoryError<T>| error.map(Dual::constant);
+        if derivative {
+            return StateHistory::try_eval::<1>(self, t.re)
+                .map(|d| d.lift())
+                .map_err(lift_error);
+        }
+        let mut p = StateHistory::try_eval::<0>(self, t.re)
+            .map_err(lift_error)?
+            .lift();
+        if !t.eps.is_zero() {
+            let d = StateHistory::try_eval::<1>(self, t.re)
+                .map_err(lift_error)?
+                .lift();
+            p.zip_apply(&d, |p, d| p.eps += t.eps * d.re);
+        }
+        Ok(p)
+    }
+}
+
+impl<T: RealField + Copy, const N: usize, const S: usize, const I: usize, IC>
+    State<T, SVector<T, N>, S, I, IC>
+where
+    IC: InitialCondition<T, SVector<T, N>>,
+{
+    /// Returns the Jacobian of the right hand side of the `equation` with respect to the current
+    /// position, evaluated at the current state. The `equation` is a function generic over the
+    /// scalar type (the same as the one given to [crate::Solver::equation]), which is evaluated
+    /// with the state lifted into [Dual] numbers. The history does not depend on the current
+    /// position, but the delayed arguments at the times depending on it (state-dependent delays)
+    /// do.
+    pub fn jacobian(
+        &self,
+        equation: impl FnOnce(&DualStateRef<T, N, N, S, I>) -> SVector<Dual<T, N>, N>,
+    ) -> SMatrix<T, N, N> {
+        let p = self
+            .p_curr
+            .map_with_location(|i, _, p| Dual::variable(p, i));
+        let (d, p_prev) = (self.d_curr.lift(), self.p_prev.lift());
+        let d = equation(&StateRef::lifted(
+            Dual::constant(self.t_curr),
+            &p,
+            &d,
+            Dual::constant(self.t_prev),
+            &p_prev,
+            &self.history,
+        ));
+        SMatrix::from_fn(|i, j| d[i].eps[j])
+    }
+}
+
+impl<T: RealField + Copy, const N: usize> Display for Dual<T, N> {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "{} + [", self.re)?;
+        for (i, eps) in self.eps.iter().enumerate() {
+            if i > 0 {
+                write!(f, ", ")?;
+            }
+            write!(f, "{eps}")?;
+        }
+        write!(f, "]ε")
+    }
+}
+
+impl<T: RealField + Copy, const N: usize> PartialEq for Dual<T, N> {
+    fn eq(&self, other: &Self) -> bool {
+        self.re == other.re
+    }
+}
+
+impl<T: RealField + Copy, const N: usize> PartialOrd for Dual<T, N> {
+    fn partial_cmp(&self, other: &Self) -> Option<std::cmp::Ordering> {
+        self.re.partial_cmp(&other.re)
+    }
+}
+
+impl<T: RealField + Copy, const N: usize> Neg for Dual<T, N> {
+    type Output = Self;
+
+    fn neg(self) -> Self {
+        Self::new(-self.re, -self.eps)
+    }
+}
+
+impl<T: RealField + Copy, const N: usize> Add for Dual<T, N> {
+    type Output = Self;
+
+    fn add(self, rhs: Self) -> Self {
+        Self::new(self.re + rhs.re, self.eps + rhs.eps)
+    }
+}
+
+impl<T: RealField + Copy, const N: usize> Sub for Dual<T, N> {
+    type Output = Self;
+
+    fn sub(self, rhs: Self) -> Self {
+        Self::new(self.re - rhs.re, self.eps - rhs.eps)
+    }
+}
+
+impl<T: RealField + Copy, const N: usize> Mul for Dual<T, N> {
+    type Output = Self;
+
+    fn mul(self, rhs: Self) -> Self {
+        Self::new(self.re * rhs.re, self.eps * rhs.re + rhs.eps * self.re)
+    }
+}
+
+impl<T: RealField + Copy, const N: usize> Div for Dual<T, N> {
+    type Output = Self;
+
+    fn div(self, rhs: Self) -> Self {
+        let re = self.re / rhs.re;
+        Self::new(re, (self.eps - rhs.eps * re) / rhs.re)
+    }
+}
+
+impl<T: RealField + Copy, const N: usize> Rem for Dual<T, N> {
+    type Output = Self;
+
+    fn rem(self, rhs: Self) -> Self {
+        let quotient = (self.re / rhs.re).trunc();
+        Self::new(self.re % rhs.re, self.eps - rhs.eps * quotient)
+    }
+}
+
+macro_rules! impl_assign_op {
+    ($($Trait:ident, $method:ident, $op:tt);*) => {$(
+        impl<T: RealField + Copy, const N: usize> $Trait for Dual<T, N> {
+            fn $method(&mut self, rhs: Self) {
+                *self = *self $op rhs;
+            }
+        }
+    )*};
+}
+
+impl_assign_op!(
+    AddAssign, add_assign, +;
+    SubAssign, sub_assign, -;
+    MulAssign, mul_assign, *;
+    DivAssign, div_assign, /;
+    RemAssign, rem_assign, %
+);
+
+impl<T: RealField + Copy, const N: usize> Zero for Dual<T, N> {
+    fn zero() -> Self {
+        Self::constant(T::zero())
+    }
+
+    fn is_zero(&self) -> bool {
+        self.re.is_zero() && self.eps.iter().all(|eps| eps.is_zero())
+    }
+}
+
+impl<T: RealField + Copy, const N: usize> One for Dual<T, N> {
+    fn one() -> Self {
+        Self::constant(T::one())
+    }
+}
+
+impl<T: RealField + Copy, const N: usize> Num for Dual<T, N> {
+    type FromStrRadixErr = T::FromStrRadixErr;
+
+    fn from_str_radix(str: &str, radix: u32) -> Result<Self, Self::FromStrRadixErr> {
+        T::from_str_radix(str, radix).map(Self::constant)
+    }
+}
+
+impl<T: RealField + Copy, const N: usize> Signed for Dual<T, N> {
+    fn abs(&self) -> Self {
+        ComplexField::abs(*self)
+    }
+
+    fn abs_sub(&self, other: &Self) -> Self {
+        if self <= other {
+            Self::zero()
+        } else {
+            *self - *other
+        }
+    }
+
+    fn signum(&self) -> Self {
+        ComplexField::signum(*self)
+    }
+
+    fn is_positive(&self) -> bool {
+        self.re.is_positive()
+    }
+
+    fn is_negative(&self) -> bool {
+        self.re.is_negative()
+    }
+}
+
+impl<T: RealField + Copy, const N: usize> FromPrimitive for Dual<T, N> {
+    fn from_i64(n: i64) -> Option<Self> {
+        T::from_i64(n).map(Self::constant)
+    }
+
+    fn from_u64(n: u64) -> Option<Self> {
+        T::from_u64(n).map(Self::constant)
+    }
+
+    fn from_f32(n: f32) -> Option<Self> {
+        T::from_f32(n).map(Self::constant)
+    }
+
+    fn from_f64(n: f64) -> Option<Self> {
+        T::from_f64(n).map(Self::constant)
+    }
+}
+
+impl<T: RealField + Copy, const N: usize> AbsDiffEq for Dual<T, N> {
+    type Epsilon = Self;
+
+    fn default_epsilon() -> Self {
+        Self::constant(T::default_epsilon())
+    }
+
+    fn abs_diff_eq(&self, other: &Self, epsilon: Self) -> bool {
+        self.re.abs_diff_eq(&other.re, epsilon.re)
+    }
+}
+
+impl<T: RealField + Copy, const N: usize> RelativeEq for Dual<T, N> {
+    fn default_max_relative() -> Self {
+        Self::constant(T::default_max_relative())
+    }
+
+    fn relative_eq(&self, other: &Self, epsilon: Self, max_relative: Self) -> bool {
+        self.re.relative_eq(&other.re, epsilon.re, max_relative.re)
+    }
+}
+
+impl<T: RealField + Copy, const N: usize> UlpsEq for Dual<T, N> {
+    fn default_max_ulps() -> u32 {
+        T::default_max_ulps()
+    }
+
+    fn ulps_eq(&self, other: &Self, epsilon: Self, max_ulps: u32) -> bool {
+        self.re.ulps_eq(&other.re, epsilon.re, max_ulps)
+    }
+}
+
+impl<T: RealField + Copy, const N: usize> SimdValue for Dual<T, N> {
+    const LANES: usize = 1;
+    type Element = Self;
+    type SimdBool = bool;
+
+    fn splat(val: Self) -> Self {
+        val
+    }
+
+    fn extract(&self, _: usize) -> Self {
+        *self
+    }
+
+    unsafe fn extract_unchecked(&self, _: usize) -> Self {
+        *self
+    }
+
+    fn replace(&mut self, _: usize, val: Self) {
+        *self = val
+    }
+
+    unsafe fn replace_unchecked(&mut self, _: usize, val: Self) {
+        *self = val
+    }
+
+    fn select(self, cond: bool, other: Self) -> Self {
+        if cond { self } else { other }
+    }
+}
+
+impl<T: RealField + Copy, const N: usize> Field for Dual<T, N> {}
+
+impl<T: RealField + Copy, const N: usize> SubsetOf<Dual<T, N>> for Dual<T, N> {
+    fn to_superset(&self) -> Self {
+        *self
+    }
+
+    fn from_superset_unchecked(element: &Self) -> Self {
+        *element
+    }
+
+    fn is_in_subset(_: &Self) -> bool {
+        true
+    }
+}
+
+macro_rules! impl_subset_of_dual {
+    ($($t:ty),*) => {$(
+        impl<T: RealField + Copy, const N: usize> SubsetOf<Dual<T, N>> for $t {
+            fn to_superset(&self) -> Dual<T, N> {
+                Dual::constant(<T as SupersetOf<$t>>::from_subset(self))
+            }
+
+            fn from_superset_unchecked(element: &Dual<T, N>) -> Self {
+                <T as SupersetOf<$t>>::to_subset_unchecked(&element.re)
+            }
+
+            fn is_in_subset(element: &Dual<T, N>) -> bool {
+                element.eps.is_zero() && <T as SupersetOf<$t>>::is_in_subset(&element.re)
+            }
+        }
+    )*};
+}
+
+impl_subset_of_dual!(f32, f64);
+
+impl<T: RealField + Copy, const N: usize> ComplexField for Dual<T, N> {
+    type RealField = Self;
+
+    fn from_real(re: Self) -> Self {
+        re
+    }
+
+    fn real(self) -> Self {
+        self
+    }
+
+    fn imaginary(self) -> Self {
+        Self::zero()
+    }
+
+    fn modulus(self) -> Self {
+        ComplexField::abs(self)
+    }
+
+    fn modulus_squared(self) -> Self {
+        self * self
+    }
+
+    fn argument(self) -> Self {
+        if self.re >= T::zero() {
+            Self::zero()
+        } else {
+            Self::pi()
+        }
+    }
+
+    fn norm1(self) -> Self {
+        ComplexField::abs(self)
+    }
+
+    fn scale(self, factor: Self) -> Self {
+        self * factor
+    }
+
+    fn unscale(self, factor: Self) -> Self {
+        self / factor
+    }
+
+    fn signum(self) -> Self {
+        Self::constant(ComplexField::signum(self.re))
+    }
+
+    fn floor(self) -> Self {
+        Self::constant(self.re.floor())
+    }
+
+    fn ceil(self) -> Self {
+        Self::constant(self.re.ceil())
+    }
+
+    fn round(self) -> Self {
+        Self::constant(self.re.round())
+    }
+
+    fn trunc(self) -> Self {
+        Self::constant(self.re.trunc())
+    }
+
+    fn fract(self) -> Self {
+        Self::new(self.re.fract(), self.eps)
+    }
+
+    fn mul_add(self, a: Self, b: Self) -> Self {
+        self * a + b
+    }
+
+    fn abs(self) -> Self {
+        self.chain(self.re.abs(), ComplexField::signum(self.re))
+    }
+
+    fn hypot(self, other: Self) -> Self {
+        let re = self.re.hypot(other.re);
+        Self::new(re, (self.eps * self.re + other.eps * other.re) / re)
+    }
+
+    fn recip(self) -> Self {
+        let re = self.re.recip();
+        self.chain(re, -re * re)
+    }
+
+    fn conjugate(self) -> Self {
+        self
+    }
+
+    fn sin(self) -> Self {
+        let (sin, cos) = self.re.sin_cos();
+        self.chain(sin, cos)
+    }
+
+    fn cos(self) -> Self {
+        let (sin, cos) = self.re.sin_cos();
+        self.chain(cos, -sin)
+    }
+
+    fn sin_cos(self) -> (Self, Self) {
+        let (sin, cos) = self.re.sin_cos();
+        (self.chain(sin, cos), self.chain(cos, -sin))
+    }
+
+    fn tan(self) -> Self {
+        let tan = self.re.tan();
+        self.chain(tan, T::one() + tan * tan)
+    }
+
+    fn asin(self) -> Self {
+        self.chain(
+            self.re.asin(),
+            (T::one() - self.re * self.re).sqrt().recip(),
+        )
+    }
+
+    fn acos(self) -> Self {
+        self.chain(
+            self.re.acos(),
+            -(T::one() - self.re * self.re).sqrt().recip(),
+        )
+    }
+
+    fn atan(self) -> Self {
+        self.chain(self.re.atan(), (T::one() + self.re * self.re).recip())
+    }
+
+    fn sinh(self) -> Self {
+        self.chain(self.re.sinh(), self.re.cosh())
+    }
+
+    fn cosh(self) -> Self {
+        self.chain(self.re.cosh(), self.re.sinh())
+    }
+
+    fn tanh(self) -> Self {
+        let tanh = self.re.tanh();
+        self.chain(tanh, T::one() - tanh * tanh)
+    }
+
+    fn asinh(self) -> Self {
+        self.chain(
+            self.re.asinh(),
+            (self.re * self.re + T::one()).sqrt().recip(),
+        )
+    }
+
+    fn acosh(self) -> Self {
+        self.chain(
+            self.re.acosh(),
+            (self.re * self.re - T::one()).sqrt().recip(),
+        )
+    }
+
+    fn atanh(self) -> Self {
+        self.chain(self.re.atanh(), (T::one() - self.re * self.re).recip())
+    }
+
+    fn log(self, base: Self) -> Self {
+        self.ln() / base.ln()
+    }
+
+    fn log2(self) -> Self {
+        self.chain(self.re.log2(), (self.re * T::ln_2()).recip())
+    }
+
+    fn log10(self) -> Self {
+        self.chain(self.re.log10(), (self.re * T::ln_10()).recip())
+    }
+
+    fn ln(self) -> Self {
+        self.chain(self.re.ln(), self.re.recip())
+    }
+
+    fn ln_1p(self) -> Self {
+        self.chain(self.re.ln_1p(), (T::one() + self.re).recip())
+    }
+
+    fn sqrt(self) -> Self {
+        let sqrt = self.re.sqrt();
+        self.chain(sqrt, (sqrt + sqrt).recip())
+    }
+
+    fn exp(self) -> Self {
+        let exp = self.re.exp();
+        self.chain(exp, exp)
+    }
+
+    fn exp2(self) -> Self {
+        let exp2 = self.re.exp2();
+        self.chain(exp2, exp2 * T::ln_2())
+    }
+
+    fn exp_m1(self) -> Self {
+        self.chain(self.re.exp_m1(), self.re.exp())
+    }
+
+    fn powi(self, n: i32) -> Self {
+        if n == 0 {
+            return Self::one();
+        }
+        let n_t = T::from_i32(n).unwrap();
+        self.chain(self.re.powi(n), n_t * self.re.powi(n - 1))
+    }
+
+    fn powf(self, n: Self) -> Self {
+        let re = self.re.powf(n.re);
+        let mut eps = self.eps * (n.re * self.re.powf(n.re - T::one()));
+        // the derivative with respect to the exponent is skipped if it is zero, since the
+        // logarithm of the base may be undefined
+        if !n.eps.is_zero() {
+            eps += n.eps * (re * self.re.ln());
+        }
+        Self::new(re, eps)
+    }
+
+    fn powc(self, n: Self) -> Self {
+        self.powf(n)
+    }
+
+    fn cbrt(self) -> Self {
+        let cbrt = self.re.cbrt();
+        self.chain(cbrt, (cbrt * cbrt * T::from_u8(3).unwrap()).recip())
+    }
+
+    fn is_finite(&self) -> bool {
+        self.re.is_finite() && self.eps.iter().all(|eps| eps.is_finite())
+    }
+
+    fn try_sqrt(self) -> Option<Self> {
+        (self.re >= T::zero()).then(|| self.sqrt())
+    }
+}
+
+impl<T: RealField + Copy, const N: usize> RealField for Dual<T, N> {
+    fn is_sign_positive(&self) -> bool {
+        self.re.is_sign_positive()
+    }
+
+    fn is_sign_negative(&self) -> bool {
+        self.re.is_sign_negative()
+    }
+
+    fn copysign(self, sign: Self) -> Self {
+        if self.re.is_sign_positive() == sign.re.is_sign_positive() {
+            self
+        } else {
+            -self
+        }
+    }
+
+    fn max(self, other: Self) -> Self {
+        if other.re > self.re { other } else { self }
+    }
+
+    fn min(self, other: Self) -> Self {
+        if other.re < self.re { other } else { self }
+    }
+
+    fn clamp(self, min: Self, max: Self) -> Self {
+        RealField::min(RealField::max(self, min), max)
+    }
+
+    fn atan2(self, other: Self) -> Self {
+        let denominator = self.re * self.re + other.re * other.re;
+        Self::new(
+            self.re.atan2(other.re),
+            (self.eps * other.re - other.eps * self.re) / denominator,
+        )
+    }
+
+    fn min_value() -> Option<Self> {
+        T::min_value().map(Self::constant)
+    }
+
+    fn max_value() -> Option<Self> {
+        T::max_value().map(Self::constant)
+    }
+
+    fn pi() -> Self {
+        Self::constant(T::pi())
+    }
+
+    fn two_pi() -> Self {
+        Self::constant(T::two_pi())
+    }
+
+    fn frac_pi_2() -> Self {
+        Self::constant(T::frac_pi_2())
+    }
+
+    fn frac_pi_3() -> Self {
+        Self::constant(T::frac_pi_3())
+    }
+
+    fn frac_pi_4() -> Self {
+        Self::constant(T::frac_pi_4())
+    }
+
+    fn frac_pi_6() -> Self {
+        Self::constant(T::frac_pi_6())
+    }
+
+    fn frac_pi_8() -> Self {
+        Self::constant(T::frac_pi_8())
+    }
+
+    fn frac_1_pi() -> Self {
+        Self::constant(T::frac_1_pi())
+    }
+
+    fn frac_2_pi() -> Self {
+        Self::constant(T::frac_2_pi())
+    }
+
+    fn frac_2_sqrt_pi() -> Self {
+        Self::constant(T::frac_2_sqrt_pi())
+    }
+
+    fn e() -> Self {
+        Self::constant(T::e())
+    }
+
+    fn log2_e() -> Self {
+        Self::constant(T::log2_e())
+    }
+
+    fn log10_e() -> Self {
+        Self::constant(T::log10_e())
+    }
+
+    fn ln_2() -> Self {
+        Self::constant(T::ln_2())
+    }
+
+    fn ln_10() -> Self {
+        Self::constant(T::ln_10())
+    }
+}
@@ -0,0 +1,203 @@
+//! Defines [Ensemble], which runs many integrations of the same configuration, e.g. for
+//! parameter sweeps and Monte-Carlo simulations.
+
+use hlist2::{HList, ops::Append};
+use nalgebra::RealField;
+
+use crate::{
+    Solver,
+    error::SolverError,
+    initial_condition::InitialCondition,
+    interval::IntegrationInterval,
+    loc::loc_hlist::HListLocateEarliest,
+    state::{EvalMutStateFnHList, EvalState, State},
+    stepsize::StepsizeController,
+    traits::RealVectorSpace,
+};
+
+/// Member of an [Ensemble], passed to its factory and reduction closures.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+pub struct Member {
+    /// Index of the member, from `0` to [Ensemble::size].
+    pub index: usize,
+    /// Seed of the member, derived from [Ensemble::seed] and the index, e.g. to initialize a
+    /// random number generator for random initial conditions or parameters. It does not depend
+    /// on the order, in which the members are run.
+    pub seed: u64,
+}
+
+/// Ensemble of integrations, in which each member runs the solver produced by the factory
+/// closure, and the final state is reduced to the output of the member.
+///
+/// The members run in parallel on scoped threads, one per available core (see
+/// [std::thread::available_parallelism]), or on the global thread pool of `rayon` with the
+/// `rayon` feature. In both cases, the outputs are returned in the order of member indices.
+pub struct Ensemble<Factory> {
+    /// Number of members.
+    pub size: usize,
+    /// Seed, from which the seeds of the members are derived, see [Member::seed].
+    pub seed: u64,
+    /// Closure, which produces the configured solver of a member.
+    pub factory: Factory,
+}
+
+impl<Factory> Ensemble<Factory> {
+    /// Creates the ensemble of `size` members, which run solvers produced by `factory`.
+    pub fn new<Configured>(size: usize, factory: Factory) -> Self
+    where
+        Factory: Fn(Member) -> Configured,
+    {
+        Self {
+            size,
+            seed: 0,
+            factory,
+        }
+    }
+
+    /// [Ensemble::seed] setter. Returns self.
+    pub fn seed(self, seed: u64) -> Self {
+        Self { seed, ..self }
+    }
+
+    /// Returns the member with the given index.
+    pub fn member(&self, index: usize) -> Member {
+        Member {
+            index,
+            seed: split_mix64(split_mix64(self.seed) ^ index as u64),
+        }
+    }
+
+    /// Runs all the members, and returns the outputs of `reduce` on their final states, or the
+    /// errors of the failed members, in the order of member indices. A failure of a member,
+    /// including a panic in the factory, the integration or `reduce`, does not affect the
+    /// others.
+    pub fn run<
+        T: RealField + Copy,
+        P: RealVectorSpace<T>,
+        const S: usize,
+        const I: usize,
+        Equation: EvalState<T, P, S, I, Initial, P>,
+        Initial: InitialCondition<T, P>,
+        Interval: IntegrationInterval<T>,
+        Stepsize: StepsizeController<T, P>,
+        EventsOnStep: HList + Append + EvalMutStateFnHList<T, P, S, I, Initial, ()>,
+        EventsOnStart: HList + Append + EvalMutStateFnHList<T, P, S, I, Initial, ()>,
+        EventsOnStop: HList + Append + EvalMutStateFnHList<T, P, S, I, Initial, ()>,
+        EventsOnLoc: HList
+            + Append
+            + HListLocateEarliest<T, P, S, I, Initial>
+            + EvalMutStateFnHList<T, P, S, I, Initial, ()>,
+        Output: Send,
+    >(
+        &self,
+        reduce: impl Fn(Member, State<T, P, S, I, Initial>) -> Output + Sync,
+    ) -> Vec<Result<Output, MemberError<T>>>
+    where
+        Factory: Fn(
+                Member,
+            ) -> Solver<
+                T,
+                P,
+                S,
+                I,
+                Equation,
+                Initial,
+                Interval,
+                Stepsize,
+                EventsOnStep,
+                EventsOnStart,
+                EventsOnStop,
+                EventsOnLoc,
+            > + Sync,
+    {
+        let run_member = |index| {
+            let member = self.member(index);
+            std::panic::catch_unwind(std::panic::AssertUnwindSafe(|| {
+                (self.factory)(member)
+                    .try_run()
+                    .map(|state| reduce(member, state))
+            }))
+            .map_err(MemberError::from_panic)?
+            .map_err(MemberError::Solver)
+        };
+
+        #[cfg(feature = "rayon")]
+        {
+            use rayon::prelude::*;
+            (0..self.size).into_par_iter().map(run_member).collect()
+        }
+        #[cfg(not(feature = "rayon"))]
+        {
+            // the threads take the members one by one, so that the slow ones do not hold back
+            // the others
+            let next = std::sync::atomic::AtomicUsize::new(0);
+            let threads = std::thread::available_parallelism()
+                .map_or(1, usize::from)
+                .min(self.size);
+            let mut outputs = std::thread::scope(|scope| {
+                let workers = (0..threads)
+                    .map(|_| {
+                        scope.spawn(|| {
+                            let mut outputs = Vec::new();
+                            loop {
+                                let index = next.fetch_add(1, std::sync::atomic::Ordering::Relaxed);
+                                if index >= self.size {
+                                    break outputs;
+                                }
+                                outputs.push((index, run_member(index)));
+                            }
+                        })
+                    })
+                    .collect::<Vec<_>>();
+                workers
+                    .into_iter()
+                    .flat_map(|worker| worker.join().expect("member panics are caught"))
+                    .collect::<Vec<_>>()
+            });
+            outputs.sort_by_key(|&(index, _)| index);
+            outputs.into_iter().map(|(_, output)| output).collect()
+        }
+    }
+}
+
+/// Failure of an [Ensemble] member.
+#[derive(Clone, Debug, PartialEq)]
+pub enum MemberError<T> {
+    /// The integration failed, see [Solver::try_run].
+    Solver(SolverError<T>),
+    /// The factory, the integration or the reduction panicked with the message.
+    Panic(String),
+}
+
+impl<T> MemberError<T> {
+    /// Extracts the message from the payload of a panic.
+    fn from_panic(payload: Box<dyn std::any::Any + Send>) -> Self {
+        let message = match payload.downcast::<String>() {
+            Ok(message) => *message,
+            Err(payload) => match payload.downcast::<&str>() {
+                Ok(message) => message.to_string(),
+                Err(_) => String::from("panic with a non-string payload"),
+            },
+        };
+        MemberError::Panic(message)
+    }
+}
+
+impl<T: std::fmt::Debug> std::fmt::Display for MemberError<T> {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            MemberError::Solver(error) => write!(f, "{error}"),
+            MemberError::Panic(message) => write!(f, "Member panicked: {message}"),
+        }
+    }
+}
+
+impl<T: std::fmt::Debug> std::error::Error for MemberError<T> {}
+
+/// SplitMix64 mixing function, which maps consecutive integers to well distributed seeds.
+fn split_mix64(x: u64) -> u64 {
+    let mut z = x.wrapping_add(0x9E37_79B9_7F4A_7C15);
+    z = (z ^ (z >> 30)).wrapping_mul(0xBF58_476D_1CE4_E5B9);
+    z = (z ^ (z >> 27)).wrapping_mul(0x94D0_49BB_1331_11EB);
+    z ^ (z >> 31)
+}
@@ -0,0 +1,129 @@
+//! Defines [SolverError] and [HistoryError].
+
+/// Failure of evaluation of a state at a given time, see [crate::state::StateHistory::try_eval].
+#[derive(Clone, Copy, Debug, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub enum HistoryError<T> {
+    /// The requested time lies before the earliest time still stored in the history. Increase
+    /// [crate::Solver::max_delay] to keep more history.
+    Deleted { t: T, t_earliest: T },
+    /// The requested time lies after the most recent computed time.
+    NotComputed { t: T, t_latest: T },
+    /// Derivative of the requested order is not available (either from the initial condition or
+    /// from the dense output).
+    UnsupportedDerivative { t: T, order: usize },
+}
+
+impl<T: std::fmt::Debug> std::fmt::Display for HistoryError<T> {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            HistoryError::Deleted { t, t_earliest } => write!(
+                f,
+                "Evaluation of state at {t:?} in deleted time range (before {t_earliest:?})"
+            ),
+            HistoryError::NotComputed { t, t_latest } => write!(
+                f,
+                "Evaluation of state in a not yet computed time range at {t:?} while most recent time in history is {t_latest:?}."
+            ),
+            HistoryError::UnsupportedDerivative { t, order } => write!(
+                f,
+                "Evaluation of derivative of order {order} at {t:?} is not supported."
+            ),
+        }
+    }
+}
+
+impl<T: std::fmt::Debug> std::error::Error for HistoryError<T> {}
+
+impl<T> HistoryError<T> {
+    /// Converts the times of the error with `f`, e.g. into another scalar type.
+    pub fn map<U>(self, f: impl Fn(T) -> U) -> HistoryError<U> {
+        match self {
+            HistoryError::Deleted { t, t_earliest } => HistoryError::Deleted {
+                t: f(t),
+                t_earliest: f(t_earliest),
+            },
+            HistoryError::NotComputed { t, t_latest } => HistoryError::NotComputed {
+                t: f(t),
+                t_latest: f(t_latest),
+            },
+            HistoryError::UnsupportedDerivative { t, order } => {
+                HistoryError::UnsupportedDerivative { t: f(t), order }
+            }
+        }
+    }
+}
+
+/// Error returned by [crate::Solver::try_run].
+///
+/// Each variant records the time `t` of the state and the number `step` of the step at the moment
+/// of failure (with `0` corresponding to the start of the integration).
+#[derive(Clone, Copy, Debug, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub enum SolverError<T> {
+    /// Evaluation of the state history failed (in the equation, an event or a callback).
+    History {
+        error: HistoryError<T>,
+        t: T,
+        step: usize,
+    },
+    /// The step was rejected by the stepsize controller, but the stepsize cannot be decreased
+    /// any further (e.g. it hit [crate::AutomaticStepsize::stepsize_range] start).
+    StepsizeUnderflow { stepsize: T, t: T, step: usize },
+    /// The state contains infinite or NaN values.
+    NonFinite { t: T, step: usize },
+    /// An event (with the index `event` in the order of addition of the located events) detected
+    /// accumulation of its firings, see [crate::Solver::on_mut_zeno].
+    Zeno {
+        t: T,
+        event: Option<usize>,
+        step: usize,
+    },
+    /// The Runge-Kutta method is implicit, but there are no Newton iterations to solve its
+    /// stage equations, since it is set with [crate::Solver::rk] instead of
+    /// [crate::Solver::implicit_rk].
+    ImplicitMethod { t: T, step: usize },
+    /// One of the stop times set with [crate::Solver::tstops] is not finite.
+    NonFiniteTstop { tstop: T, t: T, step: usize },
+    /// The end `t_end` of the interval of a resumed integration is before the current time of
+    /// the state in the direction of its history, see [crate::Solver::try_resume].
+    ReversedInterval { t_end: T, t: T, step: usize },
+}
+
+impl<T: std::fmt::Debug> std::fmt::Display for SolverError<T> {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            SolverError::History { error, t, step } => {
+                write!(f, "{error} (at t = {t:?}, step {step})")
+            }
+            SolverError::StepsizeUnderflow { stepsize, t, step } => write!(
+                f,
+                "Step of size {stepsize:?} is rejected and the stepsize cannot be decreased (at t = {t:?}, step {step})"
+            ),
+            SolverError::NonFinite { t, step } => {
+                write!(f, "State is not finite (at t = {t:?}, step {step})")
+            }
+            SolverError::Zeno { t, event, step } => match event {
+                Some(event) => write!(
+                    f,
+                    "Zeno behavior of the event {event} (at t = {t:?}, step {step})"
+                ),
+                None => write!(f, "Zeno behavior of an event (at t = {t:?}, step {step})"),
+            },
+            SolverError::ImplicitMethod { t, step } => write!(
+                f,
+                "Implicit Runge-Kutta method is to be set with Solver::implicit_rk (at t = {t:?}, step {step})"
+            ),
+            SolverError::NonFiniteTstop { tstop, t, step } => write!(
+                f,
+                "Stop time {tstop:?} is not finite (at t = {t:?}, step {step})"
+            ),
+            SolverError::ReversedInterval { t_end, t, step } => write!(
+                f,
+                "End of the interval {t_end:?} is before the current time in the direction of the history (at t = {t:?}, step {step})"
+            ),
+        }
+    }
+}
+
+impl<T: std::fmt::Debug> std::error::Error for SolverError<T> {}
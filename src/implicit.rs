@@ -0,0 +1,926 @@
+//! Defines [Newton], which solves the stage equations of implicit Runge-Kutta methods and
+//! Rosenbrock methods, see [crate::Solver::implicit_rk], and the corrector equations of the BDF
+//! method, see [crate::Solver::bdf].
+
+use std::{fmt, ops::Range, sync::Arc};
+
+use nalgebra::{Complex, ComplexField, DMatrix, DVector, Dyn, LU, RealField};
+
+use crate::{
+    initial_condition::InitialCondition,
+    rk::Rosenbrock,
+    state::{EvalState, State},
+    traits::{Components, RealVectorSpace},
+};
+
+/// Simplified Newton iterations for the stage equations of implicit Runge-Kutta methods, stored
+/// in [State::newton].
+///
+/// The iterations use the Jacobian of the right hand side with respect to the position,
+/// computed by finite differences at the start of a step (delayed arguments are treated as
+/// constant), or by [Newton::jacobian_fn] if it is set. The Jacobian and the LU factorizations
+/// of the iteration matrices are reused across steps, and the Jacobian is recomputed only when
+/// the iterations fail to converge with it. If they fail with the fresh Jacobian, the step is
+/// rejected, see [State::newton_failed].
+///
+/// The iteration matrix `I - h A ⊗ J` of a block of coupled stages with the coefficients `A`
+/// is split as in RADAU5 (E. Hairer, G. Wanner - Solving Ordinary Differential Equations II,
+/// Section IV.8): with the eigendecomposition `A = V Λ V⁻¹`, it is transformed into the systems
+/// `I - h λ J` of the size of the position for each eigenvalue `λ`, of which only one of a
+/// complex conjugate pair is solved (in complex numbers). The whole system is factorized only
+/// if the eigenvalues are not distinct. The eigendecomposition is computed once for the
+/// coefficients of each block, and the systems are factorized again only when the Jacobian is
+/// recomputed, or the stepsize changes by a ratio outside of `[1, 1.2]` (the iterations
+/// converge with the factorization for a slightly smaller stepsize, as in RADAU5). The error
+/// estimate is filtered with the factorization of `I - h γ J` for the largest real eigenvalue
+/// `γ` of the coefficients of the method, which is reused in the same way.
+///
+/// Rosenbrock methods (see [Rosenbrock]) need no iterations, but recompute the Jacobian in every
+/// step, and solve one linear system with `I - h γ J` for each stage.
+///
+/// The BDF method (see [crate::multistep::Multistep]) iterates with `I - γ J`, where `γ`
+/// depends on the stepsizes and the order, and reuses its factorization while `γ` changes by
+/// less than 30 %, scaling the corrections by `2 / (1 + γ / γ_saved)` as in CVODE.
+///
+/// Each of the factorizations of `I - c J` is stored with the coefficient `c`, for which it is
+/// computed.
+#[derive(Clone)]
+pub struct Newton<T: RealField, Y> {
+    /// The iterations stop when the estimated error of the stages is below `kappa` times the
+    /// tolerances of the stepsize controller, measured as `|Δy| / (atol + rtol |y|)` in each
+    /// component (see [crate::stepsize::StepsizeController::tolerances]), as in E. Hairer,
+    /// G. Wanner - Solving Ordinary Differential Equations II.
+    pub kappa: T,
+    /// Tolerance of the iterations, if the stepsize controller has no tolerances (e.g. for a
+    /// fixed stepsize), measured as `|Δy| / (1 + |y|)` in each component.
+    pub tolerance: T,
+    /// Largest number of iterations in a step.
+    pub max_iterations: usize,
+    /// Jacobian of the right hand side, reused across steps.
+    pub jacobian: Option<DMatrix<T>>,
+    /// Function of time and position, which computes the Jacobian instead of finite
+    /// differences, see [crate::Solver::jacobian].
+    pub jacobian_fn: Option<JacobianFn<T, Y>>,
+    /// Whether the iterations converged in the last step.
+    pub converged: bool,
+    /// Whether the Jacobian is computed in the current step.
+    fresh: bool,
+    /// Factorizations of the iteration matrices of the blocks of stages, by the first stage of
+    /// the block.
+    blocks: Vec<(usize, BlockFactorization<T>)>,
+    /// Coefficients `a` of the method with their largest real eigenvalue `γ`, see
+    /// [State::filter_error].
+    filter_gamma: Option<(DMatrix<T>, T)>,
+    /// LU factorization of `I - h γ J`, which filters the error estimate, with `h γ`.
+    lu_filter: Option<(T, LU<T, Dyn, Dyn>)>,
+    /// LU factorization of `I - h γ J`, which solves the stages of Rosenbrock methods, with
+    /// `h γ`.
+    lu_rosenbrock: Option<(T, LU<T, Dyn, Dyn>)>,
+    /// LU factorization of `I - γ J`, which solves the corrector equations of the BDF method,
+    /// with `γ`.
+    lu_bdf: Option<(T, LU<T, Dyn, Dyn>)>,
+    /// Absolute and relative tolerances of the stepsize controller in components, see
+    /// [Newton::kappa].
+    tolerances: Option<(DVector<T>, DVector<T>)>,
+    to_components: fn(&Y) -> DVector<T>,
+    from_components: fn(&DVector<T>) -> Y,
+}
+
+/// User-supplied Jacobian of the right hand side, see [Newton::jacobian_fn].
+pub type JacobianFn<T, Y> = Arc<dyn Fn(T, &Y) -> DMatrix<T> + Send + Sync>;
+
+impl<T: RealField, Y> fmt::Debug for Newton<T, Y> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("Newton")
+            .field("kappa", &self.kappa)
+            .field("tolerance", &self.tolerance)
+            .field("max_iterations", &self.max_iterations)
+            .field("jacobian", &self.jacobian)
+            .field("converged", &self.converged)
+            .finish_non_exhaustive()
+    }
+}
+
+impl<T: RealField + Copy, Y: Components<T>> Default for Newton<T, Y> {
+    fn default() -> Self {
+        Self {
+            kappa: T::from_f64(0.03).unwrap(),
+            tolerance: T::from_f64(1e-10).unwrap(),
+            max_iterations: 10,
+            jacobian: None,
+            jacobian_fn: None,
+            converged: true,
+            fresh: false,
+            blocks: vec![],
+            filter_gamma: None,
+            lu_filter: None,
+            lu_rosenbrock: None,
+            lu_bdf: None,
+            tolerances: None,
+            to_components: Y::to_components,
+            from_components: Y::from_components,
+        }
+    }
+}
+
+impl<T: RealField, Y> Newton<T, Y> {
+    /// Discards the Jacobian, so that it is recomputed in the next step, e.g. after the
+    /// equation is changed.
+    pub fn reset(&mut self) {
+        self.jacobian = None;
+        self.discard_factorizations();
+    }
+
+    /// Discards the factorizations, which depend on the Jacobian.
+    fn discard_factorizations(&mut self) {
+        for (_, block) in &mut self.blocks {
+            block.systems = None;
+        }
+        self.lu_filter = None;
+        self.lu_rosenbrock = None;
+        self.lu_bdf = None;
+    }
+}
+
+impl<T: RealField + Copy, Y> Newton<T, Y> {
+    /// Sets the absolute and relative tolerances of the stepsize controller, see
+    /// [Newton::kappa].
+    pub(crate) fn set_tolerances(&mut self, tolerances: Option<(Y, Y)>) {
+        self.tolerances = tolerances
+            .map(|(atol, rtol)| ((self.to_components)(&atol), (self.to_components)(&rtol)));
+    }
+
+    /// Returns the norm of the increment `delta` of the iterations at `y` (consisting of the
+    /// positions of one or more stages) relative to the tolerance, so that the iterations stop
+    /// when the estimated error is below one, see [Newton::kappa] and [Newton::tolerance].
+    fn increment_norm(&self) -> impl Fn(&DVector<T>, &DVector<T>) -> T + use<T, Y> {
+        let (kappa, tolerance, tolerances) = (self.kappa, self.tolerance, self.tolerances.clone());
+        move |delta, y| {
+            let scale = |i: usize, y: T| match &tolerances {
+                Some((atol, rtol)) => {
+                    let i = i % atol.len();
+                    kappa * (atol[i] + rtol[i] * y.abs())
+                }
+                None => tolerance * (T::one() + y.abs()),
+            };
+            delta
+                .iter()
+                .zip(y.iter())
+                .enumerate()
+                .map(|(i, (&d, &y))| d.abs() / scale(i, y))
+                .fold(T::zero(), T::max)
+        }
+    }
+
+    /// Returns `true` if the factorization for the stepsize `h_saved` is reused for the stepsize
+    /// `h`, which is when `1 <= h / h_saved <= 1.2`, as in RADAU5.
+    fn reuse_stepsize(h: T, h_saved: T) -> bool {
+        let ratio = h / h_saved;
+        ratio >= T::one() && ratio <= T::from_f64(1.2).unwrap()
+    }
+
+    /// Returns the factorization of the iteration matrix of the block of stages from `start`
+    /// with the coefficients `a` and the stepsize `h`, or `None` if the Jacobian is not
+    /// computed. The eigendecomposition of `a` is computed only for the new coefficients of the
+    /// block, and the systems are factorized unless the ones for the previous stepsize are
+    /// reused, see [Newton::reuse_stepsize].
+    fn block(&mut self, start: usize, a: DMatrix<T>, h: T) -> Option<&BlockFactorization<T>> {
+        let jacobian = self.jacobian.as_ref()?;
+        let index = match self.blocks.iter().position(|(s, _)| *s == start) {
+            Some(index) => {
+                if self.blocks[index].1.a != a {
+                    self.blocks[index].1 = BlockFactorization::new(a);
+                }
+                index
+            }
+            None => {
+                self.blocks.push((start, BlockFactorization::new(a)));
+                self.blocks.len() - 1
+            }
+        };
+        let block = &mut self.blocks[index].1;
+        if block
+            .systems
+            .as_ref()
+            .is_none_or(|(h_saved, _)| !Self::reuse_stepsize(h, *h_saved))
+        {
+            block.factorize(jacobian, h);
+        }
+        Some(&self.blocks[index].1)
+    }
+
+    /// Returns the largest real eigenvalue of the coefficients `a` of the method, computing it
+    /// only for the new coefficients.
+    fn filter_gamma(&mut self, a: DMatrix<T>) -> T {
+        match &self.filter_gamma {
+            Some((a_saved, gamma)) if *a_saved == a => *gamma,
+            _ => {
+                let tolerance = T::default_epsilon().sqrt();
+                let gamma = a
+                    .complex_eigenvalues()
+                    .iter()
+                    .filter(|z| z.im.abs() <= tolerance)
+                    .map(|z| z.re)
+                    .fold(T::zero(), T::max);
+                self.filter_gamma = Some((a, gamma));
+                self.lu_filter = None;
+                gamma
+            }
+        }
+    }
+
+    /// Returns the LU factorization of `I - h γ J` for the error filter, reusing the one for the
+    /// previous `h γ` as [Newton::reuse_stepsize] does, or `None` if the Jacobian is not
+    /// computed.
+    fn lu_filter(&mut self, h_gamma: T) -> Option<&LU<T, Dyn, Dyn>> {
+        shifted_lu(
+            &mut self.lu_filter,
+            self.jacobian.as_ref()?,
+            h_gamma,
+            Self::reuse_stepsize,
+        )
+        .map(|(_, lu)| lu)
+    }
+
+    /// Returns the LU factorization of `I - h γ J` for the stages of Rosenbrock methods,
+    /// computing it if `h γ` is changed, or `None` if the Jacobian is not computed.
+    fn lu_rosenbrock(&mut self, h_gamma: T) -> Option<&LU<T, Dyn, Dyn>> {
+        shifted_lu(
+            &mut self.lu_rosenbrock,
+            self.jacobian.as_ref()?,
+            h_gamma,
+            |c, c_saved| c == c_saved,
+        )
+        .map(|(_, lu)| lu)
+    }
+
+    /// Returns `γ_saved` and the LU factorization of `I - γ_saved J`, computing it with
+    /// `γ_saved = γ` if `γ` differs from the previous `γ_saved` by more than 30 %, or `None` if
+    /// the Jacobian is not computed.
+    fn lu_near(&mut self, gamma: T) -> Option<(T, &LU<T, Dyn, Dyn>)> {
+        let change = T::from_f64(0.3).unwrap();
+        shifted_lu(
+            &mut self.lu_bdf,
+            self.jacobian.as_ref()?,
+            gamma,
+            |gamma, gamma_saved| (gamma / gamma_saved - T::one()).abs() <= change,
+        )
+    }
+}
+
+/// Returns the coefficient `c_saved` and the LU factorization of `I - c_saved J` from the
+/// `cache`, computing it with `c_saved = c` unless `reuse(c, c_saved)`. Returns `None` if the
+/// factorization is not computed.
+fn shifted_lu<'a, T: RealField + Copy>(
+    cache: &'a mut Option<(T, LU<T, Dyn, Dyn>)>,
+    jacobian: &DMatrix<T>,
+    c: T,
+    reuse: impl Fn(T, T) -> bool,
+) -> Option<(T, &'a LU<T, Dyn, Dyn>)> {
+    if cache
+        .as_ref()
+        .is_none_or(|(c_saved, _)| !reuse(c, *c_saved))
+    {
+        let n = jacobian.nrows();
+        let matrix = DMatrix::identity(n, n) - jacobian * c;
+        *cache = Some((c, matrix.lu()));
+    }
+    cache.as_ref().map(|(c_saved, lu)| (*c_saved, lu))
+}
+
+/// Factorization of the iteration matrix `I - h A ⊗ J` of a block of stages with the
+/// coefficients `A`, see [Newton].
+#[derive(Clone)]
+struct BlockFactorization<T: RealField> {
+    /// The coefficients `A` of the block.
+    a: DMatrix<T>,
+    /// The eigendecomposition of `A`, or `None` if its eigenvalues are not distinct.
+    eigen: Option<Eigen<T>>,
+    /// The stepsize `h`, and the systems factorized for it with the current Jacobian.
+    systems: Option<(T, BlockSystems<T>)>,
+}
+
+type ComplexMatrix<T> = DMatrix<Complex<T>>;
+
+/// The eigenvalues `λ` of the coefficients of a block, and their eigenvectors `V` (as columns)
+/// with `V⁻¹`.
+#[derive(Clone)]
+struct Eigen<T: RealField> {
+    values: Vec<Complex<T>>,
+    vectors: ComplexMatrix<T>,
+    vectors_inverse: ComplexMatrix<T>,
+}
+
+#[derive(Clone)]
+enum BlockSystems<T: RealField> {
+    /// The LU factorizations of `I - h λ J` for the real eigenvalues and for the eigenvalues
+    /// with positive imaginary part, by the index of the eigenvector.
+    Eigen(Vec<(usize, EigenSystem<T>)>),
+    /// The LU factorization of the whole iteration matrix.
+    Dense(LU<T, Dyn, Dyn>),
+}
+
+#[derive(Clone)]
+enum EigenSystem<T: RealField> {
+    Real(LU<T, Dyn, Dyn>),
+    Complex(LU<Complex<T>, Dyn, Dyn>),
+}
+
+impl<T: RealField + Copy> BlockFactorization<T> {
+    fn new(a: DMatrix<T>) -> Self {
+        let eigen = eigendecomposition(&a).and_then(|(values, vectors)| {
+            let vectors_inverse = vectors.clone().try_inverse()?;
+            Some(Eigen {
+                values,
+                vectors,
+                vectors_inverse,
+            })
+        });
+        Self {
+            a,
+            eigen,
+            systems: None,
+        }
+    }
+
+    /// Factorizes the systems for the stepsize `h`, with the eigenvalues of `h A` given by the
+    /// eigenvalues of `A` scaled by `h`.
+    fn factorize(&mut self, jacobian: &DMatrix<T>, h: T) {
+        let n = jacobian.nrows();
+        let m = self.a.nrows();
+        let systems = if let Some(eigen) = &self.eigen {
+            let systems = (0..m)
+                .filter_map(|k| {
+                    let value = eigen.values[k] * h;
+                    let system = if value.im == T::zero() {
+                        EigenSystem::Real((DMatrix::identity(n, n) - jacobian * value.re).lu())
+                    } else if value.im * h.signum() > T::zero() {
+                        let jacobian = jacobian.map(Complex::from);
+                        EigenSystem::Complex((DMatrix::identity(n, n) - jacobian * value).lu())
+                    } else {
+                        return None;
+                    };
+                    Some((k, system))
+                })
+                .collect();
+            BlockSystems::Eigen(systems)
+        } else {
+            let mut matrix = DMatrix::identity(m * n, m * n);
+            for r in 0..m {
+                for l in 0..m {
+                    let mut view = matrix.view_mut((r * n, l * n), (n, n));
+                    view -= jacobian * (self.a[(r, l)] * h);
+                }
+            }
+            BlockSystems::Dense(matrix.lu())
+        };
+        self.systems = Some((h, systems));
+    }
+
+    /// Solves the iteration matrix of the block for the `residual` of the stage positions,
+    /// each of `n` components. Returns `None` if the matrix is singular.
+    fn solve(&self, residual: &DVector<T>, n: usize) -> Option<DVector<T>> {
+        let (eigen, systems) = match (&self.eigen, &self.systems.as_ref()?.1) {
+            (Some(eigen), BlockSystems::Eigen(systems)) => (eigen, systems),
+            (_, BlockSystems::Dense(lu)) => return lu.solve(residual),
+            (None, BlockSystems::Eigen(_)) => return None,
+        };
+        let (vectors, vectors_inverse) = (&eigen.vectors, &eigen.vectors_inverse);
+        let m = self.a.nrows();
+        let mut delta = DVector::zeros(m * n);
+        for (k, system) in systems {
+            // the residual transformed by `V⁻¹` for the eigenvalue
+            let mut transformed = DVector::<Complex<T>>::zeros(n);
+            for l in 0..m {
+                let residual = residual.rows(l * n, n).map(Complex::from);
+                transformed.axpy(vectors_inverse[(*k, l)], &residual, Complex::from(T::one()));
+            }
+            match system {
+                EigenSystem::Real(lu) => {
+                    let solution = lu.solve(&transformed.map(|z| z.re))?;
+                    for r in 0..m {
+                        delta
+                            .rows_mut(r * n, n)
+                            .axpy(vectors[(r, *k)].re, &solution, T::one());
+                    }
+                }
+                EigenSystem::Complex(lu) => {
+                    // the conjugate eigenvalue contributes the conjugate of the solution
+                    let solution = lu.solve(&transformed)?;
+                    let two = T::one() + T::one();
+                    for r in 0..m {
+                        let contribution = (solution.clone() * vectors[(r, *k)]).map(|z| z.re);
+                        delta.rows_mut(r * n, n).axpy(two, &contribution, T::one());
+                    }
+                }
+            }
+        }
+        Some(delta)
+    }
+}
+
+/// Returns the eigenvalues of `matrix` and its eigenvectors (as columns), which are real for the
+/// real eigenvalues, and the conjugates of each other, in adjacent columns, for the complex
+/// conjugate pairs of eigenvalues. Returns `None` if the eigenvalues are not distinct.
+fn eigendecomposition<T: RealField + Copy>(
+    matrix: &DMatrix<T>,
+) -> Option<(Vec<Complex<T>>, ComplexMatrix<T>)> {
+    let m = matrix.nrows();
+    let eigenvalues = matrix.complex_eigenvalues();
+    let scale = eigenvalues
+        .iter()
+        .map(|z| z.modulus())
+        .fold(T::zero(), T::max);
+    let tolerance = T::default_epsilon().sqrt() * scale;
+    for i in 0..m {
+        for j in 0..i {
+            if (eigenvalues[i] - eigenvalues[j]).modulus() <= tolerance {
+                return None;
+            }
+        }
+    }
+
+    let mut values = Vec::with_capacity(m);
+    let mut columns = Vec::with_capacity(m);
+    for &value in eigenvalues.iter() {
+        let real = value.im.abs() <= tolerance;
+        if !real && value.im < T::zero() {
+            continue;
+        }
+        let value = if real { Complex::from(value.re) } else { value };
+        // the eigenvector spans the null space of `A - λ I`, which is the right singular vector
+        // of the smallest singular value
+        let shifted = matrix.map(Complex::from) - DMatrix::identity(m, m) * value;
+        let v_t = shifted.svd(false, true).v_t?;
+        let mut vector = v_t.row(m - 1).adjoint();
+        if real {
+            // the eigenvector is real up to a factor
+            let largest = vector[vector.icamax()];
+            vector *= largest.conj() / Complex::from(largest.modulus());
+            vector.apply(|z| z.im = T::zero());
+            values.push(value);
+            columns.push(vector);
+        } else {
+            values.push(value);
+            columns.push(vector.clone());
+            values.push(value.conj());
+            columns.push(vector.conjugate());
+        }
+    }
+    Some((values, DMatrix::from_columns(&columns)))
+}
+
+impl<
+    T: RealField + Copy,
+    Y: RealVectorSpace<T>,
+    const S: usize,
+    const I: usize,
+    IC: InitialCondition<T, Y>,
+> State<T, Y, S, I, IC>
+{
+    /// Returns `true` if the Newton iterations of the last step of an implicit method failed
+    /// to converge, in which case the step is rejected by the solver and retried with the
+    /// halved stepsize.
+    pub fn newton_failed(&self) -> bool {
+        self.newton.as_ref().is_some_and(|newton| !newton.converged)
+    }
+
+    /// Makes the step of an implicit Runge-Kutta or Rosenbrock method, see [State::make_step].
+    pub(crate) fn make_implicit_step(
+        &mut self,
+        rhs: &mut impl EvalState<T, Y, S, I, IC, Y>,
+        t_step: T,
+    ) {
+        let mut newton = self
+            .newton
+            .take()
+            .expect("the solver checks that implicit methods have Newton iterations");
+
+        let d_start = if self.t_prev != self.t_curr {
+            self.d_curr
+        } else {
+            self.stats.rhs_evals += 1;
+            rhs.eval_curr(self)
+        };
+
+        self.t_prev = self.t_curr;
+        self.p_prev = self.p_curr;
+        self.d_prev = self.d_curr;
+
+        newton.fresh = false;
+        let converged = if t_step == T::zero() {
+            self.k_curr = [d_start; S];
+            true
+        } else if let Some(rosenbrock) = self.rk.rosenbrock {
+            self.update_jacobian(&mut newton, rhs, d_start);
+            self.solve_rosenbrock_stages(&mut newton, rhs, t_step, d_start, rosenbrock)
+        } else {
+            if newton.jacobian.is_none() {
+                self.update_jacobian(&mut newton, rhs, d_start);
+            }
+            let mut converged = self.solve_stages(&mut newton, rhs, t_step, d_start);
+            if !converged && !newton.fresh && !self.history.failed() {
+                self.update_jacobian(&mut newton, rhs, d_start);
+                converged = self.solve_stages(&mut newton, rhs, t_step, d_start);
+            }
+            converged
+        };
+        newton.converged = converged;
+
+        self.t_curr = self.t_prev + t_step;
+        if converged {
+            self.p_curr = self.p_prev
+                + (0..S).fold(Y::zero(), |acc, j| acc + self.k_curr[j] * self.rk.b[j]) * t_step;
+            self.d_curr = rhs.eval_curr(self);
+            self.stats.rhs_evals += 1;
+
+            let error = (0..S).fold(Y::zero(), |acc, j| {
+                acc + self.k_curr[j] * (self.rk.b2[j] - self.rk.b[j])
+            }) * t_step;
+            self.e_curr = if self.rk.rosenbrock.is_some() {
+                error
+            } else {
+                self.filter_error(&mut newton, error, t_step)
+            };
+        } else {
+            self.p_curr = self.p_prev;
+            self.d_curr = d_start;
+            self.e_curr = Y::zero();
+        }
+
+        self.newton = Some(newton);
+    }
+
+    /// Computes the Jacobian at the start of the step by [Newton::jacobian_fn] or by finite
+    /// differences.
+    fn update_jacobian(
+        &mut self,
+        newton: &mut Newton<T, Y>,
+        rhs: &mut impl EvalState<T, Y, S, I, IC, Y>,
+        d_start: Y,
+    ) {
+        let jacobian = if let Some(jacobian_fn) = &newton.jacobian_fn {
+            jacobian_fn(self.t_prev, &self.p_prev)
+        } else {
+            let y = (newton.to_components)(&self.p_prev);
+            let f = (newton.to_components)(&d_start);
+            let n = y.len();
+            let sqrt_epsilon = T::default_epsilon().sqrt();
+
+            let mut jacobian = DMatrix::zeros(n, n);
+            self.t_curr = self.t_prev;
+            for j in 0..n {
+                let delta = sqrt_epsilon * y[j].abs().max(T::one());
+                let mut y_delta = y.clone();
+                y_delta[j] += delta;
+                self.p_curr = (newton.from_components)(&y_delta);
+                let f_delta = (newton.to_components)(&rhs.eval_curr(self));
+                jacobian.set_column(j, &((f_delta - &f) / delta));
+                if self.history.failed() {
+                    break;
+                }
+            }
+            self.p_curr = self.p_prev;
+            self.stats.rhs_evals += n;
+            jacobian
+        };
+        self.stats.jacobian_evals += 1;
+
+        newton.jacobian = Some(jacobian);
+        newton.fresh = true;
+        newton.discard_factorizations();
+    }
+
+    /// Computes the stages into [State::k_curr], evaluating the explicit ones directly and
+    /// solving the blocks of implicit ones with Newton iterations. Returns `false` if the
+    /// iterations fail.
+    fn solve_stages(
+        &mut self,
+        newton: &mut Newton<T, Y>,
+        rhs: &mut impl EvalState<T, Y, S, I, IC, Y>,
+        t_step: T,
+        d_start: Y,
+    ) -> bool {
+        let stages = self.rk.stages();
+        self.k_curr[stages..].fill(Y::zero());
+        let mut i = 0;
+        while i < stages {
+            let end = self.implicit_block_end(i);
+            if end > i {
+                if !self.solve_block(newton, rhs, t_step, i..end, d_start) {
+                    return false;
+                }
+                i = end;
+            } else {
+                self.k_curr[i] = if i == 0 && self.rk.c[0] == T::zero() {
+                    d_start
+                } else {
+                    self.t_curr = self.t_prev + self.rk.c[i] * t_step;
+                    self.p_curr = self.stage_base(i, i, t_step);
+                    self.stats.rhs_evals += 1;
+                    rhs.eval_curr(self)
+                };
+                if self.history.failed() {
+                    return false;
+                }
+                i += 1;
+            }
+        }
+        true
+    }
+
+    /// Returns the end of the smallest block of stages starting at `start`, which do not depend
+    /// on the later stages, or `start` if the stage is explicit.
+    fn implicit_block_end(&self, start: usize) -> usize {
+        let mut end = start;
+        let mut row = start;
+        while row == start || row < end {
+            if let Some(j) = (row..S).rev().find(|&j| self.rk.a[row][j] != T::zero()) {
+                end = end.max(j + 1);
+            }
+            row += 1;
+        }
+        end
+    }
+
+    /// Returns the position, to which the stages before `before` contribute in the stage `i`.
+    fn stage_base(&self, i: usize, before: usize, t_step: T) -> Y {
+        self.p_prev
+            + (0..before).fold(Y::zero(), |acc, j| acc + self.k_curr[j] * self.rk.a[i][j]) * t_step
+    }
+
+    /// Solves the stage equations of the `block` of stages by simplified Newton iterations.
+    /// Returns `false` if they fail to converge.
+    fn solve_block(
+        &mut self,
+        newton: &mut Newton<T, Y>,
+        rhs: &mut impl EvalState<T, Y, S, I, IC, Y>,
+        t_step: T,
+        block: Range<usize>,
+        d_start: Y,
+    ) -> bool {
+        let (to_components, from_components) = (newton.to_components, newton.from_components);
+        let (max_iterations, increment_norm) = (newton.max_iterations, newton.increment_norm());
+        let Some(n) = newton.jacobian.as_ref().map(|jacobian| jacobian.nrows()) else {
+            return false;
+        };
+        let m = block.len();
+        let a = DMatrix::from_fn(m, m, |r, l| self.rk.a[block.start + r][block.start + l]);
+        let ha = &a * t_step;
+        let Some(factorization) = newton.block(block.start, a, t_step) else {
+            return false;
+        };
+
+        let mut base = DVector::zeros(m * n);
+        for r in 0..m {
+            let stage_base = self.stage_base(block.start + r, block.start, t_step);
+            base.rows_mut(r * n, n)
+                .copy_from(&to_components(&stage_base));
+        }
+        let d_start = to_components(&d_start);
+        let mut y = base.clone();
+        for r in 0..m {
+            for l in 0..m {
+                y.rows_mut(r * n, n).axpy(ha[(r, l)], &d_start, T::one());
+            }
+        }
+
+        let mut converged = false;
+        let mut norm_prev: Option<T> = None;
+        for _ in 0..max_iterations {
+            let mut f = DVector::zeros(m * n);
+            for r in 0..m {
+                self.t_curr = self.t_prev + self.rk.c[block.start + r] * t_step;
+                self.p_curr = from_components(&y.rows(r * n, n).into_owned());
+                f.rows_mut(r * n, n)
+                    .copy_from(&to_components(&rhs.eval_curr(self)));
+            }
+            self.stats.rhs_evals += m;
+            self.stats.newton_iterations += 1;
+            if self.history.failed() {
+                return false;
+            }
+
+            let mut residual = &y - &base;
+            for r in 0..m {
+                for l in 0..m {
+                    let f_l = f.rows(l * n, n).into_owned();
+                    residual
+                        .rows_mut(r * n, n)
+                        .axpy(-ha[(r, l)], &f_l, T::one());
+                }
+            }
+            let Some(delta) = factorization.solve(&residual, n) else {
+                return false;
+            };
+            y -= &delta;
+
+            let norm = increment_norm(&delta, &y);
+            if !norm.is_finite() {
+                return false;
+            }
+            if let Some(norm_prev) = norm_prev {
+                let rate = norm / norm_prev;
+                if rate >= T::one() {
+                    return false;
+                }
+                if norm * rate / (T::one() - rate) <= T::one() {
+                    converged = true;
+                    break;
+                }
+            } else if norm <= T::one() {
+                converged = true;
+                break;
+            }
+            norm_prev = Some(norm);
+        }
+        if !converged {
+            return false;
+        }
+
+        // the stages are recovered from the stage positions, as (h A) K = Y - base
+        let z = y - base;
+        let z = DMatrix::from_fn(m, n, |r, i| z[r * n + i]);
+        let Some(k) = ha.lu().solve(&z) else {
+            return false;
+        };
+        for r in 0..m {
+            self.k_curr[block.start + r] = from_components(&k.row(r).transpose());
+        }
+        true
+    }
+
+    /// Computes the stages of a Rosenbrock method into [State::k_curr], solving the linear
+    /// system with `I - h γ J` for each of them. The derivative of the right hand side with
+    /// respect to time is computed by finite differences. Returns `false` if the matrix is
+    /// singular.
+    fn solve_rosenbrock_stages(
+        &mut self,
+        newton: &mut Newton<T, Y>,
+        rhs: &mut impl EvalState<T, Y, S, I, IC, Y>,
+        t_step: T,
+        d_start: Y,
+        rosenbrock: Rosenbrock<T, S>,
+    ) -> bool {
+        let (to_components, from_components) = (newton.to_components, newton.from_components);
+        let d_time = if rosenbrock.gamma_t.iter().any(|&gamma| gamma != T::zero()) {
+            let delta =
+                T::default_epsilon().sqrt() * self.t_prev.abs().max(T::one()) * t_step.signum();
+            self.t_curr = self.t_prev + delta;
+            self.p_curr = self.p_prev;
+            self.stats.rhs_evals += 1;
+            (rhs.eval_curr(self) - d_start) / delta
+        } else {
+            Y::zero()
+        };
+        let Some(lu) = newton.lu_rosenbrock(rosenbrock.gamma * t_step) else {
+            return false;
+        };
+
+        let stages = self.rk.stages();
+        self.k_curr[stages..].fill(Y::zero());
+        for i in 0..stages {
+            let f = if i == 0 && self.rk.c[0] == T::zero() {
+                d_start
+            } else {
+                self.t_curr = self.t_prev + self.rk.c[i] * t_step;
+                self.p_curr = self.stage_base(i, i, t_step);
+                self.stats.rhs_evals += 1;
+                rhs.eval_curr(self)
+            };
+            if self.history.failed() {
+                return false;
+            }
+            let stage_rhs = (0..i)
+                .fold(f, |acc, j| acc + self.k_curr[j] * rosenbrock.coupling[i][j])
+                + d_time * (rosenbrock.gamma_t[i] * t_step);
+            let Some(k) = lu.solve(&to_components(&stage_rhs)) else {
+                return false;
+            };
+            self.k_curr[i] = from_components(&k) * rosenbrock.gamma;
+        }
+        true
+    }
+
+    /// Solves the corrector equation `y = prediction + γ (f(t_prev + t_step, y) - derivative)`
+    /// of the BDF method into [State::p_curr] by simplified Newton iterations, starting from
+    /// `prediction`. The Jacobian is recomputed, and the iterations repeated, if they fail with
+    /// the reused one. Returns `false` if they fail with the fresh Jacobian.
+    pub(crate) fn solve_bdf(
+        &mut self,
+        newton: &mut Newton<T, Y>,
+        rhs: &mut impl EvalState<T, Y, S, I, IC, Y>,
+        t_step: T,
+        gamma: T,
+        prediction: Y,
+        derivative: Y,
+    ) -> bool {
+        newton.fresh = false;
+        if newton.jacobian.is_none() {
+            self.update_bdf_jacobian(newton, rhs);
+        }
+        let mut converged = self.bdf_iterations(newton, rhs, t_step, gamma, prediction, derivative);
+        if !converged && !newton.fresh && !self.history.failed() {
+            self.update_bdf_jacobian(newton, rhs);
+            converged = self.bdf_iterations(newton, rhs, t_step, gamma, prediction, derivative);
+        }
+        newton.converged = converged;
+        converged
+    }
+
+    /// Computes the Jacobian at the start of the BDF step. The derivative at the start of the
+    /// step is evaluated again for the finite differences, since the one of the BDF method
+    /// satisfies the equation only up to the tolerance of the iterations.
+    fn update_bdf_jacobian(
+        &mut self,
+        newton: &mut Newton<T, Y>,
+        rhs: &mut impl EvalState<T, Y, S, I, IC, Y>,
+    ) {
+        let d_start = if newton.jacobian_fn.is_some() {
+            self.d_prev
+        } else {
+            self.t_curr = self.t_prev;
+            self.p_curr = self.p_prev;
+            self.stats.rhs_evals += 1;
+            rhs.eval_curr(self)
+        };
+        self.update_jacobian(newton, rhs, d_start);
+    }
+
+    /// Iterates the corrector equation of the BDF method, see [State::solve_bdf]. Returns
+    /// `false` if the iterations fail to converge.
+    fn bdf_iterations(
+        &mut self,
+        newton: &mut Newton<T, Y>,
+        rhs: &mut impl EvalState<T, Y, S, I, IC, Y>,
+        t_step: T,
+        gamma: T,
+        prediction: Y,
+        derivative: Y,
+    ) -> bool {
+        let (to_components, from_components) = (newton.to_components, newton.from_components);
+        let (max_iterations, increment_norm) = (newton.max_iterations, newton.increment_norm());
+        let Some((gamma_saved, lu)) = newton.lu_near(gamma) else {
+            return false;
+        };
+        let scale = (T::one() + T::one()) / (T::one() + gamma / gamma_saved);
+
+        self.t_curr = self.t_prev + t_step;
+        let base = to_components(&(prediction - derivative * gamma));
+        let mut y = to_components(&prediction);
+        let mut norm_prev: Option<T> = None;
+        for _ in 0..max_iterations {
+            self.p_curr = from_components(&y);
+            let f = to_components(&rhs.eval_curr(self));
+            self.stats.rhs_evals += 1;
+            self.stats.newton_iterations += 1;
+            if self.history.failed() {
+                return false;
+            }
+
+            let residual = &y - &base - f * gamma;
+            let Some(delta) = lu.solve(&residual) else {
+                return false;
+            };
+            let delta = delta * scale;
+            y -= &delta;
+
+            let norm = increment_norm(&delta, &y);
+            if !norm.is_finite() {
+                return false;
+            }
+            let converged = if let Some(norm_prev) = norm_prev {
+                let rate = norm / norm_prev;
+                if rate >= T::one() {
+                    return false;
+                }
+                norm * rate / (T::one() - rate) <= T::one()
+            } else {
+                norm <= T::one()
+            };
+            if converged {
+                self.p_curr = from_components(&y);
+                return true;
+            }
+            norm_prev = Some(norm);
+        }
+        false
+    }
+
+    /// Filters the error estimate with `(I - h γ J)⁻¹`, so that it stays bounded for stiff
+    /// components. `γ` is the largest real eigenvalue of the coefficients `a` of the method:
+    /// the diagonal coefficient of diagonally implicit methods, and `1 / γ₀` for the real
+    /// eigenvalue `γ₀` of `a⁻¹` of Radau IIA methods, as in RADAU5.
+    fn filter_error(&self, newton: &mut Newton<T, Y>, error: Y, t_step: T) -> Y {
+        let stages = self.rk.stages();
+        let gamma = newton.filter_gamma(DMatrix::from_fn(stages, stages, |i, j| self.rk.a[i][j]));
+        let (to_components, from_components) = (newton.to_components, newton.from_components);
+        match newton
+            .lu_filter(gamma * t_step)
+            .and_then(|lu| lu.solve(&to_components(&error)))
+        {
+            Some(error) => from_components(&error),
+            None => error,
+        }
+    }
+}
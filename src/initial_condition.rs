@@ -9,6 +9,11 @@ use crate::traits::RealVectorSpace;
 pub trait InitialCondition<T: RealField + Copy, Y: RealVectorSpace<T>> {
     /// evaluate a derivative of order `D`
     fn eval<const D: usize>(&self, t: T) -> Y;
+
+    /// evaluate a derivative of order `D`, or return `None` if it is not available
+    fn try_eval<const D: usize>(&self, t: T) -> Option<Y> {
+        Some(self.eval::<D>(t))
+    }
 }
 
 /// For this type, the value is interpreted as a constant function. All its derivatives are zero
@@ -28,9 +33,14 @@ where
     F: Fn(T) -> Y,
 {
     fn eval<const D: usize>(&self, t: T) -> Y {
+        self.try_eval::<D>(t)
+            .unwrap_or_else(|| unimplemented!("Differentiation is not implemented for this type."))
+    }
+
+    fn try_eval<const D: usize>(&self, t: T) -> Option<Y> {
         match D {
-            0 => (self.0)(t),
-            _ => unimplemented!("Differentiation is not implemented for this type."),
+            0 => Some((self.0)(t)),
+            _ => None,
         }
     }
 }
@@ -41,10 +51,16 @@ where
     DF: Fn(T) -> Y,
 {
     fn eval<const D: usize>(&self, t: T) -> Y {
+        self.try_eval::<D>(t).unwrap_or_else(|| {
+            unimplemented!("Higher order differentiation is not implemented for this type.")
+        })
+    }
+
+    fn try_eval<const D: usize>(&self, t: T) -> Option<Y> {
         match D {
-            0 => (self.0)(t),
-            1 => (self.1)(t),
-            _ => unimplemented!("Higher order differentiation is not implemented for this type."),
+            0 => Some((self.0)(t)),
+            1 => Some((self.1)(t)),
+            _ => None,
         }
     }
 }
@@ -5,7 +5,7 @@ pub trait IntegrationInterval<T: RealField> {
     fn end_bound(&self) -> T;
 }
 
-impl<T: RealField + num_traits::Float, R: std::ops::RangeBounds<T>> IntegrationInterval<T> for R {
+impl<T: RealField + Copy, R: std::ops::RangeBounds<T>> IntegrationInterval<T> for R {
     fn start_bound(&self) -> T {
         use std::ops::Bound::*;
 
@@ -18,7 +18,7 @@ impl<T: RealField + num_traits::Float, R: std::ops::RangeBounds<T>> IntegrationI
     fn end_bound(&self) -> T {
         use std::ops::Bound::*;
         match self.end_bound() {
-            Unbounded => T::infinity(),
+            Unbounded => T::max_value().unwrap_or_else(|| nalgebra::convert(f64::MAX)),
             Included(&value) | Excluded(&value) => value,
         }
     }
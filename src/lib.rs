@@ -1,20 +1,44 @@
 #![allow(clippy::excessive_precision)]
 
+pub mod control;
+pub mod dual;
+pub mod ensemble;
+pub mod error;
+pub mod implicit;
 pub mod initial_condition;
 pub mod interval;
 pub mod loc;
+pub mod multistep;
 pub mod rk;
+pub mod sensitivity;
+pub mod solution;
 pub mod solver;
 pub mod state;
+pub mod stats;
+pub mod stepper;
 pub mod stepsize;
+pub mod stiffness;
 pub mod traits;
 
 mod util;
 
+pub use control::{Control, Limit, Limits, Termination};
+pub use dual::Dual;
+pub use ensemble::{Ensemble, Member, MemberError};
+pub use error::SolverError;
 pub use initial_condition::InitFn;
-pub use loc::{Filter, Locator, periodic::Periodic};
+pub use loc::{
+    Filter, Locator,
+    periodic::Periodic,
+    priority::WithPriority,
+    zeno::{Zeno, ZenoResponse},
+};
+pub use sensitivity::{Linearization, Sensitivity};
+pub use solution::Solution;
 pub use solver::Solver;
 pub use state::{StateFn, StateRef, StateRefMut};
+pub use stats::SolveStats;
+pub use stepper::{StepInfo, Stepper};
 pub use stepsize::AutomaticStepsize;
 
 pub use rk::*;
@@ -31,6 +31,10 @@ pub trait Locate<
     fn detect_and_locate(&mut self, state: &State<T, P, S, I, IC>) -> Option<T> {
         self.detect(state).then(|| self.locate(state))
     }
+    /// Priority of the event, see [priority::WithPriority::with_priority].
+    fn priority(&self) -> i32 {
+        0
+    }
 }
 
 pub struct LocatorStateFn<T, P, Output, F, Detection, Location> {
@@ -40,6 +44,19 @@ pub struct LocatorStateFn<T, P, Output, F, Detection, Location> {
     _phantom: PhantomData<(T, P, Output)>,
 }
 
+impl<T, P, Output, F, Detection, Location> LocatorStateFn<T, P, Output, F, Detection, Location> {
+    /// Returns the locator, which detects and locates the event by the values of `f`, see
+    /// [Locator].
+    pub(crate) fn new(f: F, detection: Detection, location: Location) -> Self {
+        Self {
+            f,
+            detection,
+            location,
+            _phantom: PhantomData,
+        }
+    }
+}
+
 impl<
     T: RealField + Copy,
     P: RealVectorSpace<T>,
@@ -175,6 +192,25 @@ pub mod location_method {
     /// Use regula falsi method to find the location of event for float-valued detection functions. See also: [Bisection]. Current implementation is not as reliable as [Bisection].
     pub struct RegulaFalsi;
 
+    /// Returns the latest of two times in the direction of integration.
+    fn latest<
+        T: RealField + Copy,
+        P: RealVectorSpace<T>,
+        const S: usize,
+        const I: usize,
+        IC: InitialCondition<T, P>,
+    >(
+        state: &State<T, P, S, I, IC>,
+        t1: T,
+        t2: T,
+    ) -> T {
+        if state.history.precedes(t1, t2) {
+            t2
+        } else {
+            t1
+        }
+    }
+
     macro_rules! impl_locate(
         ($locate:ident, $(Output = $fn_output:ty,)? |$f:ident, $state:ident| $body:expr) => {
         impl<
@@ -200,7 +236,7 @@ pub mod location_method {
     impl_locate!(StepBegin, |_f, state| state.t_prev);
     impl_locate!(StepEnd, |_f, state| state.t_curr);
     impl_locate!(StepMiddle, |_f, state| {
-        T::from_f64(0.5).unwrap() * (state.t_curr - state.t_prev)
+        T::from_f64(0.5).unwrap() * (state.t_curr + state.t_prev)
     });
     impl_locate!(Lerp, Output = T, |f, state| {
         let curr = f.eval_curr(state);
@@ -223,6 +259,7 @@ pub mod location_method {
 
         while w < w_prev {
             w_prev = w;
+            state.count_location_iteration();
             match f.eval_at(state, m) {
                 false => l = m,
                 true => r = m,
@@ -230,7 +267,7 @@ pub mod location_method {
             m = T::from_f64(0.5).unwrap() * (l + r);
             w = (r - l).abs();
         }
-        T::max(l, r)
+        latest(state, l, r)
     });
     impl_locate!(Bisection, Output = T, |f, state| {
         let mut l = state.t_prev;
@@ -247,6 +284,7 @@ pub mod location_method {
 
         while w < w_prev {
             w_prev = w;
+            state.count_location_iteration();
             match f.eval_at(state, m) < T::zero() {
                 true => l = m,
                 false => r = m,
@@ -255,7 +293,7 @@ pub mod location_method {
             w = (r - l).abs();
         }
 
-        T::max(l, r)
+        latest(state, l, r)
     });
     impl_locate!(RegulaFalsi, Output = T, |f, state| {
         let mut l = state.t_prev;
@@ -271,6 +309,7 @@ pub mod location_method {
 
         while w < w_prev {
             w_prev = w;
+            state.count_location_iteration();
             let f_l = f.eval_at(state, l);
             let f_r = f.eval_at(state, r);
             let m = (f_r * l - f_l * r) / (f_r - f_l);
@@ -281,20 +320,20 @@ pub mod location_method {
             }
             w = (r - l).abs();
         }
-        T::max(l, r)
+        latest(state, l, r)
     });
 }
 pub mod periodic {
 
     use super::*;
-    use num_traits::Float;
+    use num_traits::Zero;
 
     pub struct Periodic<T> {
         pub period: T,
         pub offset: T,
     }
 
-    impl<T: Float> Periodic<T> {
+    impl<T: Zero> Periodic<T> {
         pub fn new(period: T) -> Self {
             Periodic {
                 period,
@@ -316,9 +355,15 @@ pub mod periodic {
     > Detect<T, Y, S, I, IC> for Periodic<T>
     {
         fn detect(&mut self, state: &State<T, Y, S, I, IC>) -> bool {
-            let prev = ((state.t_prev - self.offset) / (self.period)).floor();
-            let curr = ((state.t_curr - self.offset) / (self.period)).floor();
-            prev < curr
+            if state.history.backward {
+                let prev = ((state.t_prev - self.offset) / (self.period)).ceil();
+                let curr = ((state.t_curr - self.offset) / (self.period)).ceil();
+                prev > curr
+            } else {
+                let prev = ((state.t_prev - self.offset) / (self.period)).floor();
+                let curr = ((state.t_curr - self.offset) / (self.period)).floor();
+                prev < curr
+            }
         }
     }
     impl<
@@ -330,7 +375,13 @@ pub mod periodic {
     > Locate<T, Y, S, I, IC> for Periodic<T>
     {
         fn locate(&mut self, state: &State<T, Y, S, I, IC>) -> T {
-            ((state.t_curr - self.offset) / self.period).floor() * self.period + self.offset
+            let periods = (state.t_curr - self.offset) / self.period;
+            let periods = if state.history.backward {
+                periods.ceil()
+            } else {
+                periods.floor()
+            };
+            periods * self.period + self.offset
         }
     }
 }
@@ -385,12 +436,12 @@ pub mod propagation {
             let partition_prev = partition_point_linear(
                 &state.history.disco_deque,
                 propagator.tracked_queue_index,
-                |&(t, _order)| t <= prev,
+                |&(t, _order)| state.history.precedes(t, prev),
             );
             let partition_curr = partition_point_linear(
                 &state.history.disco_deque,
                 partition_prev,
-                |&(t, _order)| t <= curr,
+                |&(t, _order)| state.history.precedes(t, curr),
             );
 
             propagator.tracked_queue_index = partition_prev.min(partition_curr);
@@ -502,6 +553,9 @@ pub mod filter {
         fn locate(&mut self, state: &crate::state::State<T, Y, S, I, IC>) -> T {
             self.loc.locate(state)
         }
+        fn priority(&self) -> i32 {
+            self.loc.priority()
+        }
     }
 
     pub struct FilterBeforeDetection<T, Y, const S: usize, const I: usize, IC, L, F> {
@@ -544,6 +598,9 @@ pub mod filter {
         fn locate(&mut self, state: &crate::state::State<T, Y, S, I, IC>) -> T {
             self.loc.locate(state)
         }
+        fn priority(&self) -> i32 {
+            self.loc.priority()
+        }
     }
 
     pub struct FilterLocated<T, Y, const S: usize, const I: usize, IC, L, F> {
@@ -598,6 +655,9 @@ pub mod filter {
                 None
             }
         }
+        fn priority(&self) -> i32 {
+            self.loc.priority()
+        }
     }
 
     pub trait Filter<
@@ -635,12 +695,12 @@ pub mod filter {
             self,
             diff: T,
         ) -> FilterLocated<T, Y, S, I, IC, Self, impl EvalState<T, Y, S, I, IC, bool>> {
-            let mut prev = T::min_value().expect("Numerical type does not have mininimum value");
+            let mut prev = None;
             FilterLocated {
                 loc: self,
                 filter: StateFn::new(move |&StateRef::<T, Y, S, I, IC> { t, .. }| {
-                    if t >= prev + diff {
-                        prev = t;
+                    if prev.is_none_or(|prev: T| (t - prev).abs() >= diff) {
+                        prev = Some(t);
                         true
                     } else {
                         false
@@ -701,6 +761,258 @@ pub mod filter {
 }
 pub use filter::Filter;
 
+pub mod priority {
+
+    use super::*;
+
+    /// Event with the priority set by [WithPriority::with_priority].
+    pub struct Prioritized<L> {
+        pub loc: L,
+        pub priority: i32,
+    }
+
+    pub trait WithPriority: Sized {
+        /// Sets the priority of the event. Of the events located at the same time (or within
+        /// [crate::Solver::event_tolerance]), the ones with higher priority fire first. The
+        /// default priority is `0`, and events with equal priority fire in the order of their
+        /// addition to the solver.
+        fn with_priority(self, priority: i32) -> Prioritized<Self> {
+            Prioritized {
+                loc: self,
+                priority,
+            }
+        }
+    }
+
+    impl<L> WithPriority for L {}
+
+    impl<
+        T: RealField + Copy,
+        Y: RealVectorSpace<T>,
+        const S: usize,
+        const I: usize,
+        IC: InitialCondition<T, Y>,
+        L: Detect<T, Y, S, I, IC>,
+    > Detect<T, Y, S, I, IC> for Prioritized<L>
+    {
+        fn detect(&mut self, state: &crate::state::State<T, Y, S, I, IC>) -> bool {
+            self.loc.detect(state)
+        }
+    }
+
+    impl<
+        T: RealField + Copy,
+        Y: RealVectorSpace<T>,
+        const S: usize,
+        const I: usize,
+        IC: InitialCondition<T, Y>,
+        L: Locate<T, Y, S, I, IC>,
+    > Locate<T, Y, S, I, IC> for Prioritized<L>
+    {
+        fn locate(&mut self, state: &crate::state::State<T, Y, S, I, IC>) -> T {
+            self.loc.locate(state)
+        }
+        fn detect_and_locate(&mut self, state: &crate::state::State<T, Y, S, I, IC>) -> Option<T> {
+            self.loc.detect_and_locate(state)
+        }
+        fn priority(&self) -> i32 {
+            self.priority
+        }
+    }
+}
+
+pub mod zeno {
+
+    use crate::{StateRefMut, state::EvalMutState};
+
+    use super::*;
+
+    /// Criterion of Zeno behavior of an event: the event fires `count` times in a row with
+    /// decreasing intervals between the firings, all within `window` time.
+    #[derive(Clone, Copy, Debug)]
+    pub struct Zeno<T> {
+        pub count: usize,
+        pub window: T,
+    }
+
+    impl<T> Zeno<T> {
+        pub fn new(count: usize, window: T) -> Self {
+            Self { count, window }
+        }
+    }
+
+    /// Response to the detected Zeno behavior, see [crate::Solver::on_mut_zeno].
+    #[derive(Clone, Copy, Debug, PartialEq)]
+    pub enum ZenoResponse {
+        /// Stop the integration with [crate::Termination::Stopped] (with the reason
+        /// `"Zeno behavior"`).
+        Stop,
+        /// Fail the integration with [crate::SolverError::Zeno].
+        Error,
+    }
+
+    /// Respond to the detected Zeno behavior in place of the event callback.
+    pub trait Respond<
+        T: RealField + Copy,
+        Y: RealVectorSpace<T>,
+        const S: usize,
+        const I: usize,
+        IC: InitialCondition<T, Y>,
+    >
+    {
+        fn respond(&mut self, state: &mut State<T, Y, S, I, IC>);
+    }
+
+    impl<
+        T: RealField + Copy,
+        Y: RealVectorSpace<T>,
+        const S: usize,
+        const I: usize,
+        IC: InitialCondition<T, Y>,
+    > Respond<T, Y, S, I, IC> for ZenoResponse
+    {
+        fn respond(&mut self, state: &mut State<T, Y, S, I, IC>) {
+            match self {
+                ZenoResponse::Stop => state
+                    .control
+                    .request(crate::Control::Stop(String::from("Zeno behavior"))),
+                ZenoResponse::Error => state.zeno = Some((state.t_curr, None)),
+            }
+        }
+    }
+
+    impl<
+        T: RealField + Copy,
+        Y: RealVectorSpace<T>,
+        const S: usize,
+        const I: usize,
+        IC: InitialCondition<T, Y>,
+        F: FnMut(&mut StateRefMut<T, Y, S, I, IC>),
+    > Respond<T, Y, S, I, IC> for StateFn<T, Y, (), F, true>
+    {
+        fn respond(&mut self, state: &mut State<T, Y, S, I, IC>) {
+            self.eval_mut(state)
+        }
+    }
+
+    /// Event with Zeno detection, which reuses the time of the previous firing
+    /// ([DedupLocF::last_call]) to count the firings with decreasing intervals, and evaluates
+    /// `response` in place of the event callback when [Zeno] behavior is detected.
+    pub struct ZenoLocF<T, L, R> {
+        pub zeno: Zeno<T>,
+        /// Interval between the last two firings.
+        pub interval: Option<T>,
+        /// Number of the firings in a row with decreasing intervals.
+        pub count: usize,
+        /// Time of the first of the counted firings.
+        pub start: T,
+        pub loc_f: DedupLocF<T, L>,
+        pub response: R,
+    }
+
+    impl<T: RealField + Copy, L, R> ZenoLocF<T, L, R> {
+        pub fn new(zeno: Zeno<T>, loc_f: L, response: R) -> Self {
+            Self {
+                zeno,
+                interval: None,
+                count: 0,
+                start: T::zero(),
+                loc_f: DedupLocF {
+                    last_call: None,
+                    loc_f,
+                },
+                response,
+            }
+        }
+
+        /// Records the firing at time `t`, and returns `true` if Zeno behavior is detected. A run
+        /// of firings, which stops decreasing its intervals or exceeds the window, is restarted
+        /// from the previous firing.
+        pub fn record(&mut self, t: T) -> bool {
+            let Some(last_call) = self.loc_f.last_call else {
+                self.count = 1;
+                return false;
+            };
+            let interval = (t - last_call).abs();
+            if self.interval.is_some_and(|prev| interval < prev)
+                && (t - self.start).abs() <= self.zeno.window
+            {
+                self.count += 1;
+            } else if interval <= self.zeno.window {
+                self.count = 2;
+                self.start = last_call;
+            } else {
+                self.count = 1;
+                self.start = t;
+            }
+            self.interval = Some(interval);
+            if self.count >= self.zeno.count {
+                self.count = 0;
+                self.interval = None;
+                true
+            } else {
+                false
+            }
+        }
+    }
+
+    impl<
+        T: RealField + Copy,
+        Y: RealVectorSpace<T>,
+        const S: usize,
+        const I: usize,
+        IC: InitialCondition<T, Y>,
+        L: Detect<T, Y, S, I, IC>,
+        R,
+    > Detect<T, Y, S, I, IC> for ZenoLocF<T, L, R>
+    {
+        fn detect(&mut self, state: &State<T, Y, S, I, IC>) -> bool {
+            self.loc_f.detect(state)
+        }
+    }
+
+    impl<
+        T: RealField + Copy,
+        Y: RealVectorSpace<T>,
+        const S: usize,
+        const I: usize,
+        IC: InitialCondition<T, Y>,
+        L: Locate<T, Y, S, I, IC>,
+        R,
+    > Locate<T, Y, S, I, IC> for ZenoLocF<T, L, R>
+    {
+        fn locate(&mut self, state: &State<T, Y, S, I, IC>) -> T {
+            self.loc_f.locate(state)
+        }
+        fn detect_and_locate(&mut self, state: &State<T, Y, S, I, IC>) -> Option<T> {
+            self.loc_f.detect_and_locate(state)
+        }
+        fn priority(&self) -> i32 {
+            self.loc_f.priority()
+        }
+    }
+
+    impl<
+        T: RealField + Copy,
+        Y: RealVectorSpace<T>,
+        const S: usize,
+        const I: usize,
+        IC: InitialCondition<T, Y>,
+        L: EvalMutState<T, Y, S, I, IC, ()>,
+        R: Respond<T, Y, S, I, IC>,
+    > EvalMutState<T, Y, S, I, IC, ()> for ZenoLocF<T, L, R>
+    {
+        fn eval_mut(&mut self, state: &mut State<T, Y, S, I, IC>) {
+            if self.record(state.t_curr) {
+                self.loc_f.last_call = Some(state.t_curr);
+                self.response.respond(state)
+            } else {
+                self.loc_f.eval_mut(state)
+            }
+        }
+    }
+}
+
 pub mod loc_callback {
 
     use crate::state::EvalMutState;
@@ -743,6 +1055,12 @@ pub mod loc_callback {
         fn locate(&mut self, state: &State<T, Y, S, I, IC>) -> T {
             self.0.locate(state)
         }
+        fn detect_and_locate(&mut self, state: &State<T, Y, S, I, IC>) -> Option<T> {
+            self.0.detect_and_locate(state)
+        }
+        fn priority(&self) -> i32 {
+            self.0.priority()
+        }
     }
 
     impl<
@@ -792,7 +1110,16 @@ pub mod loc_hlist {
 
     use super::*;
 
-    pub trait LocateEarliestImpl<
+    /// Event located in the current step, see [HListLocateEarliest::locate_simultaneous].
+    #[derive(Clone, Copy, Debug, PartialEq)]
+    pub struct LocatedEvent<T> {
+        /// Index of the event in the order of addition to the solver
+        pub index: usize,
+        pub time: T,
+        pub priority: i32,
+    }
+
+    pub trait LocateAllImpl<
         T: RealField + Copy,
         Y: RealVectorSpace<T>,
         const S: usize,
@@ -800,15 +1127,25 @@ pub mod loc_hlist {
         IC: InitialCondition<T, Y>,
     >
     {
-        fn locate_earliest_impl(
+        fn locate_all_impl(
             &mut self,
             state: &State<T, Y, S, I, IC>,
             self_index: &mut usize,
-            earliest_index: &mut usize,
-            earliest_time: &mut Option<T>,
+            located: &mut Vec<LocatedEvent<T>>,
         );
     }
 
+    pub trait DetectImpl<
+        T: RealField + Copy,
+        Y: RealVectorSpace<T>,
+        const S: usize,
+        const I: usize,
+        IC: InitialCondition<T, Y>,
+    >
+    {
+        fn detect_impl(&mut self, state: &State<T, Y, S, I, IC>, detected: &mut bool);
+    }
+
     impl<
         T: RealField + Copy,
         Y: RealVectorSpace<T>,
@@ -816,40 +1153,70 @@ pub mod loc_hlist {
         const I: usize,
         IC: InitialCondition<T, Y>,
         L: Locate<T, Y, S, I, IC>,
-    > LocateEarliestImpl<T, Y, S, I, IC> for L
+    > LocateAllImpl<T, Y, S, I, IC> for L
     {
-        fn locate_earliest_impl(
+        fn locate_all_impl(
             &mut self,
             state: &State<T, Y, S, I, IC>,
             self_index: &mut usize,
-            earliest_index: &mut usize,
-            earliest_time: &mut Option<T>,
+            located: &mut Vec<LocatedEvent<T>>,
         ) {
-            if let Some(self_time) = self.detect_and_locate(state)
-                && earliest_time.is_none_or(|t| self_time < t)
-            {
-                *earliest_index = *self_index;
-                *earliest_time = Some(self_time);
+            if let Some(time) = self.detect_and_locate(state) {
+                located.push(LocatedEvent {
+                    index: *self_index,
+                    time,
+                    priority: self.priority(),
+                });
             }
             *self_index += 1;
         }
     }
 
+    impl<
+        T: RealField + Copy,
+        Y: RealVectorSpace<T>,
+        const S: usize,
+        const I: usize,
+        IC: InitialCondition<T, Y>,
+        L: Detect<T, Y, S, I, IC>,
+    > DetectImpl<T, Y, S, I, IC> for L
+    {
+        fn detect_impl(&mut self, state: &State<T, Y, S, I, IC>, detected: &mut bool) {
+            *detected = self.detect(state);
+        }
+    }
+
     TraitHList! {
-        pub HListLocateEarliestImpl for
-            trait LocateEarliestImpl<
+        pub HListLocateAllImpl for
+            trait LocateAllImpl<
                 T: RealField + Copy,
                 Y: RealVectorSpace<T>,
                 const S: usize,
                 const I: usize,
                 IC: InitialCondition<T, Y>,
             > {
-            fn locate_earliest_impl(
+            fn locate_all_impl(
                 &mut self,
                 state: &State<T, Y, S, I, IC>,
                 self_index: &mut usize,
-                earliest_index: &mut usize,
-                earliest_time: &mut Option<T>,
+                located: &mut Vec<LocatedEvent<T>>,
+            );
+        }
+    }
+
+    TraitHList! {
+        pub HListDetectImpl for
+            trait DetectImpl<
+                T: RealField + Copy,
+                Y: RealVectorSpace<T>,
+                const S: usize,
+                const I: usize,
+                IC: InitialCondition<T, Y>,
+            > {
+            fn detect_impl(
+                &mut self,
+                state: &State<T, Y, S, I, IC>,
+                detected: &mut bool,
             );
         }
     }
@@ -862,7 +1229,58 @@ pub mod loc_hlist {
         IC: InitialCondition<T, Y>,
     >
     {
-        fn locate_earliest(&mut self, state: &State<T, Y, S, I, IC>) -> Option<(usize, T)>;
+        /// Locates all the events detected in the current step.
+        fn locate_all(&mut self, state: &State<T, Y, S, I, IC>) -> Vec<LocatedEvent<T>>;
+
+        /// Returns the index and the time of the earliest located event. Of the events located
+        /// at the same time, the one with the highest priority is returned.
+        fn locate_earliest(&mut self, state: &State<T, Y, S, I, IC>) -> Option<(usize, T)> {
+            self.locate_simultaneous(state, None)
+                .map(|(time, indices)| (indices[0], time))
+        }
+
+        /// Returns the time of the earliest located event, and the indices of the events located
+        /// within `tolerance` from it (only the first one if `tolerance` is `None`), ordered by
+        /// decreasing priority, then by time, then by index.
+        fn locate_simultaneous(
+            &mut self,
+            state: &State<T, Y, S, I, IC>,
+            tolerance: Option<T>,
+        ) -> Option<(T, Vec<usize>)> {
+            let located = self.locate_all(state);
+            let time = located.iter().map(|event| event.time).reduce(|t1, t2| {
+                if state.history.precedes(t1, t2) {
+                    t1
+                } else {
+                    t2
+                }
+            })?;
+            let mut simultaneous: Vec<_> = located
+                .into_iter()
+                .filter(|event| (event.time - time).abs() <= tolerance.unwrap_or(T::zero()))
+                .collect();
+            simultaneous.sort_by(|a, b| {
+                b.priority.cmp(&a.priority).then_with(|| {
+                    if a.time == b.time {
+                        a.index.cmp(&b.index)
+                    } else if state.history.precedes(a.time, b.time) {
+                        std::cmp::Ordering::Less
+                    } else {
+                        std::cmp::Ordering::Greater
+                    }
+                })
+            });
+            if tolerance.is_none() {
+                simultaneous.truncate(1);
+            }
+            Some((
+                time,
+                simultaneous.into_iter().map(|event| event.index).collect(),
+            ))
+        }
+
+        /// Evaluates the detection of the event with the given index in the current step.
+        fn detect_at_index(&mut self, state: &State<T, Y, S, I, IC>, index: usize) -> bool;
     }
 
     impl<
@@ -871,14 +1289,19 @@ pub mod loc_hlist {
         const S: usize,
         const I: usize,
         IC: InitialCondition<T, Y>,
-        U: HListLocateEarliestImpl<T, Y, S, I, IC>,
+        U: HListLocateAllImpl<T, Y, S, I, IC> + HListDetectImpl<T, Y, S, I, IC>,
     > HListLocateEarliest<T, Y, S, I, IC> for U
     {
-        fn locate_earliest(&mut self, state: &State<T, Y, S, I, IC>) -> Option<(usize, T)> {
-            let mut index = 0;
-            let mut earliest_time = None;
-            self.locate_earliest_impl(state, &mut 0, &mut index, &mut earliest_time);
-            Some((index, earliest_time?))
+        fn locate_all(&mut self, state: &State<T, Y, S, I, IC>) -> Vec<LocatedEvent<T>> {
+            let mut located = Vec::new();
+            self.locate_all_impl(state, &mut 0, &mut located);
+            located
+        }
+
+        fn detect_at_index(&mut self, state: &State<T, Y, S, I, IC>, index: usize) -> bool {
+            let mut detected = false;
+            self.detect_impl_at_index(state, &mut detected, index);
+            detected
         }
     }
 }
@@ -983,8 +1406,8 @@ impl<
 {
     fn detect(&mut self, state: &State<T, P, S, I, IC>) -> bool {
         self.last_call
-            .is_none_or(|last_call| state.t_prev > last_call)
-            && self.detect(state)
+            .is_none_or(|last_call| !state.history.precedes(state.t_prev, last_call))
+            && self.loc_f.detect(state)
     }
 }
 impl<
@@ -1002,13 +1425,16 @@ impl<
     fn detect_and_locate(&mut self, state: &State<T, P, S, I, IC>) -> Option<T> {
         if self
             .last_call
-            .is_none_or(|last_call| state.t_prev > last_call)
+            .is_none_or(|last_call| !state.history.precedes(state.t_prev, last_call))
         {
             self.loc_f.detect_and_locate(state)
         } else {
             None
         }
     }
+    fn priority(&self) -> i32 {
+        self.loc_f.priority()
+    }
 }
 impl<
     T: RealField + Copy,
@@ -0,0 +1,568 @@
+//! Defines [Multistep], the variable-step, variable-order Adams-Bashforth-Moulton and BDF
+//! methods, which replace the Runge-Kutta method of the solver, see [crate::Solver::adams] and
+//! [crate::Solver::bdf].
+
+use std::{collections::VecDeque, fmt};
+
+use nalgebra::RealField;
+
+use crate::{
+    initial_condition::InitialCondition,
+    rk::ButcherTableu,
+    state::{EvalState, State},
+    stepsize::{StepStatus, StepsizeController},
+    traits::RealVectorSpace,
+};
+
+/// Largest order of the Adams method of [Multistep].
+pub const MAX_ORDER: usize = 12;
+
+/// Largest order of the BDF method of [Multistep], above which the formulas are not
+/// zero-stable.
+pub const MAX_BDF_ORDER: usize = 5;
+
+/// Formula of [Multistep].
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum Formula {
+    /// Adams-Bashforth-Moulton formulas for non-stiff equations, see [crate::Solver::adams].
+    Adams,
+    /// Backward differentiation formulas for stiff equations, see [crate::Solver::bdf].
+    Bdf,
+}
+
+impl Formula {
+    /// Returns the largest order of the formula, [MAX_ORDER] or [MAX_BDF_ORDER].
+    pub fn max_order(self) -> usize {
+        match self {
+            Formula::Adams => MAX_ORDER,
+            Formula::Bdf => MAX_BDF_ORDER,
+        }
+    }
+}
+
+/// Variable-step, variable-order multistep method, stored in [State::multistep].
+///
+/// The past steps are kept as the divided differences, from which the formulas are computed
+/// for the actual stepsizes (L. F. Shampine, M. K. Gordon - Computer Solution of Ordinary
+/// Differential Equations).
+///
+/// The Adams method keeps the divided differences of the derivatives and works in PECE mode:
+/// a step of order `q` predicts the position with the Adams-Bashforth formula of order `q`,
+/// evaluates the right hand side, corrects the position with the Adams-Moulton formula of order
+/// `q + 1`, and evaluates the right hand side again for the next step, so it costs two
+/// evaluations. The error estimate is the difference of the correctors of orders `q + 1` and
+/// `q`, and the dense output is the integral of the corrector polynomial.
+///
+/// The BDF method keeps the divided differences of the positions. A step of order `q`
+/// extrapolates the polynomial through the last `q + 1` positions, and solves for the position,
+/// at which the polynomial through it and the last `q` positions has the derivative given by
+/// the right hand side, with the Newton iterations of [crate::implicit::Newton]. The error
+/// estimate is computed from the divided difference of order `q + 1`, and the dense output is
+/// the corrector polynomial, whose derivative is also used as the derivative at the end of the
+/// step.
+///
+/// The dense output of a step of order `q` is the polynomial of degree `q + 1` for the Adams
+/// method and `q` for the BDF method, whose coefficients are stored in
+/// [crate::state::StateHistory::polynomials].
+///
+/// After each step, the order is changed to `q - 1` or `q + 1`, if their error estimates,
+/// measured by [StepsizeController::error_norm], allow a larger stepsize (with the safety
+/// factors of LSODE), and the stepsize is proposed for the new order. If the controller does
+/// not measure errors (e.g. for constant stepsize), the order increases by one in every step up
+/// to [Multistep::max_order]. The method restarts at order 1 after located events, stop times
+/// and other changes of the state, see [State::make_zero_step].
+#[derive(Clone)]
+pub struct Multistep<T, Y> {
+    /// Largest order of the method, from 1 to [Formula::max_order].
+    max_order: usize,
+    /// Formula of the method.
+    formula: Formula,
+    /// Order of the current step.
+    order: usize,
+    /// Times of the past steps, starting from the latest one. After a restart of the BDF
+    /// method, the first time is repeated, so that the derivative is interpolated at it.
+    times: VecDeque<T>,
+    /// Divided differences at `times`: `v[t_0]`, `v[t_0, t_1]`, ..., where `v` is the
+    /// derivative for the Adams method and the position for the BDF method.
+    differences: Vec<Y>,
+    /// Position at the latest of `times`.
+    position: Y,
+    /// Time, position and derivative at the end of the current step, or `None` if the step
+    /// has zero length or fails.
+    end: Option<(T, Y, Y)>,
+    /// Error estimates of the orders `q - 1` and `q + 1` in the current step, if available.
+    errors: [Option<Y>; 2],
+    /// Coefficients `c_m` of the dense output `y_prev + h Σ c_m θ^(m + 1)` of the current step,
+    /// see [dense_output].
+    pub(crate) polynomial: Vec<Y>,
+}
+
+impl<T, Y> fmt::Debug for Multistep<T, Y> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("Multistep")
+            .field("max_order", &self.max_order)
+            .field("formula", &self.formula)
+            .field("order", &self.order)
+            .finish_non_exhaustive()
+    }
+}
+
+impl<T, Y: RealVectorSpace<T>> Multistep<T, Y>
+where
+    T: RealField + Copy,
+{
+    /// Multistep method with the formula and orders up to [Formula::max_order].
+    pub fn new(formula: Formula) -> Self {
+        Self {
+            max_order: formula.max_order(),
+            formula,
+            order: 1,
+            times: VecDeque::new(),
+            differences: Vec::new(),
+            position: Y::zero(),
+            end: None,
+            errors: [None; 2],
+            polynomial: Vec::new(),
+        }
+    }
+
+    /// Adams-Bashforth-Moulton method of orders up to [MAX_ORDER].
+    pub fn adams() -> Self {
+        Self::new(Formula::Adams)
+    }
+
+    /// BDF method of orders up to [MAX_BDF_ORDER].
+    pub fn bdf() -> Self {
+        Self::new(Formula::Bdf)
+    }
+
+    /// Sets the largest order of the method, which is clamped to the orders from 1 to
+    /// [Formula::max_order]. Returns self.
+    pub fn with_max_order(mut self, max_order: usize) -> Self {
+        self.max_order = max_order.clamp(1, self.formula.max_order());
+        self
+    }
+
+    /// Returns the tableau without stages, which the solver keeps in place of the Runge-Kutta
+    /// method. Its order is the degree of the dense output of [Multistep::max_order], up to
+    /// which the discontinuities of delay equations are tracked.
+    pub(crate) fn tableau(&self) -> ButcherTableu<T, 0> {
+        let order = match self.formula {
+            Formula::Adams => self.max_order + 1,
+            Formula::Bdf => self.max_order,
+        };
+        ButcherTableu {
+            order,
+            order_embedded: order - 1,
+            order_interpolant: order,
+            a: [],
+            b: [],
+            b2: [],
+            b3: None,
+            c: [],
+            bi: [],
+            rosenbrock: None,
+        }
+    }
+}
+
+impl<T, Y> Multistep<T, Y> {
+    /// Returns the formula of the method.
+    pub fn formula(&self) -> Formula {
+        self.formula
+    }
+
+    /// Returns the order of the current step.
+    pub fn order(&self) -> usize {
+        self.order
+    }
+
+    /// Returns the largest order of the method, see [Multistep::with_max_order].
+    pub fn max_order(&self) -> usize {
+        self.max_order
+    }
+}
+
+/// Evaluates the derivative of order `D` of the dense output `y_prev + h Σ c_m θ^(m + 1)` of a
+/// step of [Multistep] with the stepsize `h` and the `coefficients` `c_m` at `theta` in the step.
+pub(crate) fn dense_output<const D: usize, T: RealField + Copy, Y: RealVectorSpace<T>>(
+    y_prev: &Y,
+    t_step: T,
+    theta: T,
+    coefficients: &[Y],
+) -> Y {
+    // Horner's scheme for `Σ c_m θ^(m + 1)` or its derivative of order `D`
+    // `Σ (m + 1) m ... (m + 2 - D) c_m θ^(m + 1 - D)`, in which the terms with `m + 1 < D` vanish
+    let delta = coefficients
+        .iter()
+        .enumerate()
+        .rev()
+        .fold(Y::zero(), |acc, (m, &c)| match D {
+            0 => (acc + c) * theta,
+            _ if m + 1 < D => acc,
+            _ => acc * theta + c * T::from_usize((m + 2 - D..=m + 1).product()).unwrap(),
+        });
+    match D {
+        0 => *y_prev + delta * t_step,
+        // `d/dt = 1/h d/dθ`
+        _ => delta / t_step.powi(D as i32 - 1),
+    }
+}
+
+impl<T: RealField + Copy, Y: RealVectorSpace<T>> Multistep<T, Y> {
+    /// Adds the step ending at `t` with the position `y` and the derivative `f` to the past
+    /// steps.
+    fn push(&mut self, t: T, y: Y, f: Y) {
+        let mut difference = match self.formula {
+            Formula::Adams => f,
+            Formula::Bdf => y,
+        };
+        for (k, &t_k) in self.times.iter().enumerate() {
+            let next = (difference - self.differences[k]) / (t - t_k);
+            self.differences[k] = difference;
+            difference = next;
+        }
+        self.differences.push(difference);
+        self.times.push_front(t);
+        // the BDF method of order `q` interpolates `q + 1` past positions
+        let len = match self.formula {
+            Formula::Adams => self.max_order,
+            Formula::Bdf => self.max_order + 1,
+        };
+        self.times.truncate(len);
+        self.differences.truncate(len);
+        self.position = y;
+    }
+
+    /// Starts the method at order 1 from time `t`, position `y` and derivative `f`.
+    fn restart(&mut self, t: T, y: Y, f: Y) {
+        self.times.clear();
+        self.differences.clear();
+        match self.formula {
+            Formula::Adams => self.push(t, y, f),
+            Formula::Bdf => {
+                self.times.extend([t, t]);
+                self.differences.extend([y, f]);
+                self.position = y;
+            }
+        }
+        self.order = 1;
+    }
+
+    /// Updates the stepsize controller with the error estimate `error` of the step of the order
+    /// of the step, and selects the order of the next step, see [Multistep]. Returns whether the
+    /// step is accepted, or `None` if the step has zero length or fails.
+    pub(crate) fn update_stepsize(
+        &mut self,
+        stepsize: &mut impl StepsizeController<T, Y>,
+        error: &Y,
+        t_step: T,
+    ) -> Option<StepStatus> {
+        self.end.as_ref()?;
+        let status = stepsize.update_with_order(error, self.order as u32);
+        self.select_order(stepsize, status, error, t_step);
+        Some(status)
+    }
+
+    /// Changes the order after the step with the error estimate `error`, and proposes the
+    /// stepsize for the new order, if the step is accepted, see [Multistep].
+    fn select_order(
+        &mut self,
+        stepsize: &mut impl StepsizeController<T, Y>,
+        status: StepStatus,
+        error: &Y,
+        t_step: T,
+    ) {
+        let order = self.order;
+        let Some(norm) = stepsize.error_norm(error) else {
+            if status == StepStatus::Accepted {
+                self.order = (order + 1).min(self.max_order);
+            }
+            return;
+        };
+
+        // stepsize ratio allowed by the error of the order
+        let ratio = |norm: T, order: usize, safety: f64| {
+            T::one()
+                / (T::from_f64(safety).unwrap()
+                    * norm.powf(T::one() / T::from_usize(order + 1).unwrap()))
+        };
+        let same = ratio(norm, order, 1.2);
+        let lower = self.errors[0].map(|error| (order - 1, error, 1.3));
+        let higher = self.errors[1]
+            .filter(|_| status == StepStatus::Accepted && order < self.max_order)
+            .map(|error| (order + 1, error, 1.4));
+        let Some((new_order, new_error, _)) = [lower, higher]
+            .into_iter()
+            .flatten()
+            .filter_map(|(order, error, safety)| {
+                let ratio = ratio(stepsize.error_norm(&error)?, order, safety);
+                (ratio > same).then_some((order, error, ratio))
+            })
+            .max_by(|a, b| a.2.partial_cmp(&b.2).unwrap())
+        else {
+            return;
+        };
+
+        self.order = new_order;
+        if status == StepStatus::Accepted {
+            stepsize.set(t_step);
+            stepsize.update_with_order(&new_error, new_order as u32);
+        }
+    }
+}
+
+/// Multiplies the polynomial with the coefficients `polynomial` by `x - root`.
+fn multiply_root<T: RealField + Copy>(polynomial: &mut Vec<T>, root: T) {
+    polynomial.push(T::zero());
+    for m in (1..polynomial.len()).rev() {
+        polynomial[m] = polynomial[m - 1] - root * polynomial[m];
+    }
+    polynomial[0] *= -root;
+}
+
+/// Returns the integral of the polynomial with the coefficients `polynomial` over `[0, 1]`.
+fn integral<T: RealField + Copy>(polynomial: &[T]) -> T {
+    polynomial
+        .iter()
+        .enumerate()
+        .fold(T::zero(), |acc, (m, &c)| {
+            acc + c / T::from_usize(m + 1).unwrap()
+        })
+}
+
+impl<
+    T: RealField + Copy,
+    Y: RealVectorSpace<T>,
+    const S: usize,
+    const I: usize,
+    IC: InitialCondition<T, Y>,
+> State<T, Y, S, I, IC>
+{
+    /// Makes the step of the multistep method, see [State::make_step] and [Multistep].
+    pub(crate) fn make_multistep_step(
+        &mut self,
+        rhs: &mut impl EvalState<T, Y, S, I, IC, Y>,
+        t_step: T,
+    ) {
+        let mut multistep = self
+            .multistep
+            .take()
+            .expect("multistep methods are set with Solver::adams or Solver::bdf");
+
+        // the previous step is accepted, unless it is undone or followed by a zero step
+        if self.t_prev != self.t_curr
+            && let Some((t, y, f)) = multistep.end
+            && t == self.t_curr
+        {
+            multistep.push(t, y, f);
+        }
+        multistep.end = None;
+        multistep.errors = [None; 2];
+
+        self.t_prev = self.t_curr;
+        self.p_prev = self.p_curr;
+        self.d_prev = self.d_curr;
+
+        if t_step == T::zero() {
+            multistep.polynomial.clear();
+            self.e_curr = Y::zero();
+            self.multistep = Some(multistep);
+            return;
+        }
+
+        if multistep.times.front() != Some(&self.t_prev)
+            || !(multistep.position - self.p_prev).is_zero()
+        {
+            self.d_curr = rhs.eval_curr(self);
+            self.d_prev = self.d_curr;
+            self.stats.rhs_evals += 1;
+            multistep.restart(self.t_prev, self.p_prev, self.d_prev);
+        }
+
+        match multistep.formula {
+            Formula::Adams => self.make_adams_step(&mut multistep, rhs, t_step),
+            Formula::Bdf => self.make_bdf_step(&mut multistep, rhs, t_step),
+        }
+        self.multistep = Some(multistep);
+    }
+
+    /// Makes the step of the Adams method, see [Multistep].
+    fn make_adams_step(
+        &mut self,
+        multistep: &mut Multistep<T, Y>,
+        rhs: &mut impl EvalState<T, Y, S, I, IC, Y>,
+        t_step: T,
+    ) {
+        let order = multistep.order.min(multistep.times.len());
+        multistep.order = order;
+
+        // the polynomials are in s = (t - t_prev) / h, in which the nodes are σ_0 = 1 at the
+        // end of the step, and σ_i at the past steps
+        let times = &multistep.times;
+        let differences = &multistep.differences;
+        let sigma = |i: usize| {
+            if i == 0 {
+                T::one()
+            } else {
+                (times[i - 1] - self.t_prev) / t_step
+            }
+        };
+
+        let mut basis = vec![T::one()];
+        let mut scale = t_step;
+        let mut prediction = self.p_prev;
+        for (k, &difference) in differences.iter().enumerate().take(order) {
+            if k > 0 {
+                multiply_root(&mut basis, sigma(k));
+            }
+            prediction += difference * (scale * integral(&basis));
+            scale *= t_step;
+        }
+        self.t_curr = self.t_prev + t_step;
+        self.p_curr = prediction;
+        let derivative = rhs.eval_curr(self);
+        if self.history.failed() {
+            self.stats.rhs_evals += 1;
+            return;
+        }
+
+        // divided differences with the derivative at the predicted position, and the terms
+        // `h^(k + 1) f[t_curr, t_0, ..., t_(k - 1)] Π (s - σ_i)` of the corrector polynomial
+        let available = (order + 2).min(times.len() + 1);
+        let mut corrected = derivative;
+        let mut terms = Vec::with_capacity(available);
+        let mut basis = vec![T::one()];
+        let mut scale = t_step;
+        for k in 0..available {
+            if k > 0 {
+                corrected = (corrected - differences[k - 1]) / (self.t_curr - times[k - 1]);
+                multiply_root(&mut basis, sigma(k - 1));
+            }
+            terms.push((corrected * scale, basis.clone()));
+            scale *= t_step;
+        }
+        let increment = |k: usize| terms[k].0 * integral(&terms[k].1);
+
+        self.p_curr = (0..=order).fold(self.p_prev, |acc, k| acc + increment(k));
+        self.d_curr = rhs.eval_curr(self);
+        self.stats.rhs_evals += 2;
+        self.e_curr = increment(order);
+        multistep.errors = [
+            (order > 1).then(|| increment(order - 1)),
+            (available > order + 1).then(|| increment(order + 1)),
+        ];
+
+        // the integral of the corrector polynomial, see [dense_output]
+        multistep.polynomial = (0..=order)
+            .map(|m| {
+                terms[m..=order]
+                    .iter()
+                    .fold(Y::zero(), |acc, (term, basis)| acc + *term * basis[m])
+                    / (t_step * T::from_usize(m + 1).unwrap())
+            })
+            .collect();
+
+        multistep.end = Some((self.t_curr, self.p_curr, self.d_curr));
+    }
+
+    /// Makes the step of the BDF method, see [Multistep].
+    fn make_bdf_step(
+        &mut self,
+        multistep: &mut Multistep<T, Y>,
+        rhs: &mut impl EvalState<T, Y, S, I, IC, Y>,
+        t_step: T,
+    ) {
+        let order = multistep.order.min(multistep.times.len() - 1);
+        multistep.order = order;
+        let times = &multistep.times;
+        let differences = &multistep.differences;
+        let t_new = self.t_prev + t_step;
+
+        // the polynomial through the last `q + 1` positions and its derivative at `t_new`
+        let mut prediction = Y::zero();
+        let mut derivative = Y::zero();
+        let mut product = T::one();
+        let mut product_derivative = T::zero();
+        for (&difference, &t_k) in differences.iter().zip(times).take(order + 1) {
+            prediction += difference * product;
+            derivative += difference * product_derivative;
+            product_derivative = product_derivative * (t_new - t_k) + product;
+            product *= t_new - t_k;
+        }
+        // the derivative of the corrector polynomial at `t_new` is
+        // `derivative + (y - prediction) / γ`
+        let gamma = T::one()
+            / times
+                .iter()
+                .take(order)
+                .fold(T::zero(), |acc, &t_k| acc + T::one() / (t_new - t_k));
+
+        let mut newton = self
+            .newton
+            .take()
+            .expect("the BDF method is set with Solver::bdf");
+        let converged = self.solve_bdf(&mut newton, rhs, t_step, gamma, prediction, derivative);
+        self.newton = Some(newton);
+        self.t_curr = t_new;
+        if !converged {
+            self.p_curr = self.p_prev;
+            self.d_curr = self.d_prev;
+            multistep.polynomial.clear();
+            self.e_curr = Y::zero();
+            return;
+        }
+        self.d_curr = derivative + (self.p_curr - prediction) / gamma;
+
+        // divided differences with the new position: `y[t_new]`, `y[t_new, t_0]`, ...
+        let mut corrected = Vec::with_capacity(order + 3);
+        corrected.push(self.p_curr);
+        for k in 1..=times.len().min(order + 2) {
+            let difference = (corrected[k - 1] - differences[k - 1]) / (t_new - times[k - 1]);
+            corrected.push(difference);
+        }
+        // the error of the order `j` is `γ_j Π (t_new - t_i) y[t_new, t_0, ..., t_j]`
+        let error = |j: usize| {
+            let (product, alpha) =
+                times
+                    .iter()
+                    .take(j)
+                    .fold((T::one(), T::zero()), |(product, alpha), &t_i| {
+                        (product * (t_new - t_i), alpha + T::one() / (t_new - t_i))
+                    });
+            corrected[j + 1] * (product / alpha)
+        };
+        self.e_curr = error(order);
+        multistep.errors = [
+            (order > 1).then(|| error(order - 1)),
+            (corrected.len() > order + 2).then(|| error(order + 1)),
+        ];
+
+        // the corrector polynomial `Σ y[t_new, t_0, ..., t_(k - 1)] h^k Π (s - σ_i)` in
+        // s = (t - t_prev) / h in the monomial basis, where its value at s = 0 is the previous
+        // position, see [dense_output]
+        let mut coefficients = vec![Y::zero(); order + 1];
+        let mut basis = vec![T::one()];
+        let mut scale = T::one();
+        for (k, &difference) in corrected.iter().enumerate().take(order + 1) {
+            if k > 0 {
+                let sigma = if k == 1 {
+                    T::one()
+                } else {
+                    (times[k - 2] - self.t_prev) / t_step
+                };
+                multiply_root(&mut basis, sigma);
+            }
+            for (coefficient, &b) in coefficients.iter_mut().zip(&basis) {
+                *coefficient += difference * (scale * b);
+            }
+            scale *= t_step;
+        }
+        multistep.polynomial = coefficients[1..]
+            .iter()
+            .map(|&coefficient| coefficient / t_step)
+            .collect();
+
+        multistep.end = Some((self.t_curr, self.p_curr, self.d_curr));
+    }
+}
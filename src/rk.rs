@@ -1,19 +1,90 @@
 use crate::traits::RealVectorSpace;
 use nalgebra::RealField;
 
+mod verner;
+
 #[derive(Clone, Copy, Debug)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+#[cfg_attr(
+    feature = "serde",
+    serde(bound(
+        serialize = "T: serde::Serialize",
+        deserialize = "T: serde::Deserialize<'de>"
+    ))
+)]
 pub struct ButcherTableu<T, const S: usize, const I: usize = S> {
     pub order: usize,
     pub order_embedded: usize,
     pub order_interpolant: usize,
+    #[cfg_attr(feature = "serde", serde(with = "crate::util::serde_array::array2"))]
     pub a: [[T; S]; S],
+    #[cfg_attr(feature = "serde", serde(with = "crate::util::serde_array::array"))]
     pub b: [T; S],
+    #[cfg_attr(feature = "serde", serde(with = "crate::util::serde_array::array"))]
     pub b2: [T; S],
+    /// Weights of the second embedded method of a lower order, if any, whose error estimate is
+    /// combined with the one of `b2` as in the DOP853 code, see [ButcherTableu::dop853].
+    #[cfg_attr(
+        feature = "serde",
+        serde(with = "crate::util::serde_array::option_array")
+    )]
+    pub b3: Option<[T; S]>,
+    #[cfg_attr(feature = "serde", serde(with = "crate::util::serde_array::array"))]
     pub c: [T; S],
+    #[cfg_attr(feature = "serde", serde(with = "crate::util::serde_array::array2"))]
     pub bi: [[T; I]; S],
+    /// Coefficients of the linearly implicit stage equations of Rosenbrock methods, created by
+    /// [Rosenbrock::new]. If they are set, `a`, `b` and `b2` are the coefficients of the stage
+    /// values `U_i = h k_i` in the transformed formulation instead of the Runge-Kutta ones, and
+    /// `None` is to be set for other methods.
+    pub rosenbrock: Option<Rosenbrock<T, S>>,
+}
+
+/// Coefficients of a Rosenbrock method (E. Hairer, G. Wanner - Solving Ordinary Differential
+/// Equations II, Section IV.7) in the transformed formulation, in which the stages are the
+/// solutions of the linear equations
+/// ```text
+/// (I / (h γ) - J) U_i = f(t + c_i h, y + Σ a_ij U_j) + Σ coupling_ij U_j / h + gamma_t_i h f_t
+/// ```
+/// where `J` and `f_t` are the derivatives of the right hand side with respect to the position
+/// and time at the start of the step, and the step is `y + Σ b_i U_i`. They are carried by
+/// [ButcherTableu::rosenbrock] of the tableaus like [ButcherTableu::rodas4], which also provide
+/// the dense output, see [crate::Solver::implicit_rk].
+#[derive(Clone, Copy, Debug)]
+#[non_exhaustive]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+#[cfg_attr(
+    feature = "serde",
+    serde(bound(
+        serialize = "T: serde::Serialize",
+        deserialize = "T: serde::Deserialize<'de>"
+    ))
+)]
+pub struct Rosenbrock<T, const S: usize> {
+    pub gamma: T,
+    #[cfg_attr(feature = "serde", serde(with = "crate::util::serde_array::array2"))]
+    pub coupling: [[T; S]; S],
+    #[cfg_attr(feature = "serde", serde(with = "crate::util::serde_array::array"))]
+    pub gamma_t: [T; S],
+}
+
+impl<T, const S: usize> Rosenbrock<T, S> {
+    /// Returns the coefficients of a Rosenbrock method for [ButcherTableu::rosenbrock], which
+    /// changes the meaning of `a`, `b` and `b2` of the tableau to the coefficients of the stage
+    /// values `U_i` in the transformed formulation.
+    pub fn new(gamma: T, coupling: [[T; S]; S], gamma_t: [T; S]) -> Self {
+        Self {
+            gamma,
+            coupling,
+            gamma_t,
+        }
+    }
 }
 
 impl<T: RealField + Copy, const S: usize, const I: usize> ButcherTableu<T, S, I> {
+    /// Evaluates the derivative of order `D` (at most 1) of the dense output of the step of size
+    /// `t_step` from `y_prev` with the stages `k` at `theta` in the step. At the end of the step,
+    /// the weights `b` are used instead of `bi(1)`, so that the dense output is the step itself.
     pub fn dense_output<const D: usize, P: RealVectorSpace<T>>(
         &self,
         y_prev: &P,
@@ -25,10 +96,13 @@ impl<T: RealField + Copy, const S: usize, const I: usize> ButcherTableu<T, S, I>
             0 => {
                 let mut delta = P::zero();
                 for i in 0..S {
-                    let mut b_i = T::zero();
-                    for j in 0..I {
-                        b_i += self.bi[i][j] * theta.powi(j as i32)
-                    }
+                    let b_i = if theta == T::one() {
+                        self.b[i]
+                    } else {
+                        (0..I).fold(T::zero(), |b_i, j| {
+                            b_i + self.bi[i][j] * theta.powi(j as i32)
+                        })
+                    };
                     delta += k[i] * b_i;
                 }
                 *y_prev + delta * t_step
@@ -50,6 +124,116 @@ impl<T: RealField + Copy, const S: usize, const I: usize> ButcherTableu<T, S, I>
             }
         }
     }
+
+    /// Returns `true` if `a` is strictly lower triangular, so that the stages are evaluated
+    /// explicitly. Otherwise, or for Rosenbrock methods, the method is implicit, see
+    /// [crate::Solver::implicit_rk].
+    pub fn is_explicit(&self) -> bool {
+        self.rosenbrock.is_none() && (0..S).all(|i| (i..S).all(|j| self.a[i][j] == T::zero()))
+    }
+
+    /// Returns the number of the leading stages, on which the step and its error estimate
+    /// depend. For explicit methods, the rest of the stages are used only by the dense output,
+    /// and they are evaluated lazily, only for the accepted steps, see
+    /// [crate::state::State::make_dense_stages].
+    pub fn main_stages(&self) -> usize {
+        (0..S)
+            .rev()
+            .find(|&i| self.b[i] != T::zero() || self.b2[i] != T::zero())
+            .map_or(0, |i| i + 1)
+    }
+
+    /// Returns the number of the leading stages, which are used by the step, its error estimate
+    /// or the dense output. Implicit methods evaluate only these stages, so the trailing stages
+    /// added by [ButcherTableu::padded] cost nothing.
+    pub fn stages(&self) -> usize {
+        (0..S)
+            .rev()
+            .find(|&i| {
+                self.b[i] != T::zero()
+                    || self.b2[i] != T::zero()
+                    || self.bi[i].iter().any(|&b| b != T::zero())
+            })
+            .map_or(0, |i| i + 1)
+    }
+
+    /// Returns the same method with `S_` stages and `I_` interpolation coefficients, where the
+    /// added trailing stages and coefficients are zero, e.g. to switch between methods with
+    /// different numbers of stages, see [crate::Solver::auto_switch].
+    ///
+    /// # Panics
+    /// Panics if `S_ < S` or `I_ < I`.
+    pub fn padded<const S_: usize, const I_: usize>(&self) -> ButcherTableu<T, S_, I_> {
+        assert!(
+            S <= S_ && I <= I_,
+            "the method with {S} stages and {I} interpolation coefficients cannot be padded to {S_} stages and {I_} interpolation coefficients"
+        );
+        fn pad<T: RealField + Copy, const N: usize>(row: &[T]) -> [T; N] {
+            std::array::from_fn(|j| row.get(j).copied().unwrap_or(T::zero()))
+        }
+        fn pad_rows<T: RealField + Copy, const N: usize, const M: usize>(
+            rows: &[[T; N]],
+        ) -> [[T; M]; M] {
+            std::array::from_fn(|i| rows.get(i).map_or([T::zero(); M], |row| pad(row)))
+        }
+        ButcherTableu {
+            order: self.order,
+            order_embedded: self.order_embedded,
+            order_interpolant: self.order_interpolant,
+            a: pad_rows(&self.a),
+            b: pad(&self.b),
+            b2: pad(&self.b2),
+            b3: self.b3.as_ref().map(|b3| pad(b3)),
+            c: pad(&self.c),
+            bi: std::array::from_fn(|i| self.bi.get(i).map_or([T::zero(); I_], |row| pad(row))),
+            rosenbrock: self.rosenbrock.map(|rosenbrock| Rosenbrock {
+                gamma: rosenbrock.gamma,
+                coupling: pad_rows(&rosenbrock.coupling),
+                gamma_t: pad(&rosenbrock.gamma_t),
+            }),
+        }
+    }
+
+    /// Returns `true` if the stage `i` is evaluated at the end of the step, i.e. it is the
+    /// derivative at the start of the next step, which explicit methods do not evaluate again.
+    pub fn is_end_stage(&self, i: usize) -> bool {
+        self.c[i] == T::one() && self.a[i] == self.b
+    }
+
+    /// Returns the length of the stability interval of the explicit method on the negative real
+    /// axis, i.e. the largest `x`, for which the stability function satisfies `|R(z)| <= 1` for
+    /// `-x <= z <= 0` (up to 100). It is the default stability boundary of the stiffness
+    /// detection, see [crate::stiffness::Stiffness].
+    pub fn stability_boundary(&self) -> T {
+        let stability_function = |z: T| {
+            let mut g = [T::zero(); S];
+            for i in 0..S {
+                g[i] = T::one() + z * (0..i).fold(T::zero(), |acc, j| acc + self.a[i][j] * g[j]);
+            }
+            T::one() + z * (0..S).fold(T::zero(), |acc, j| acc + self.b[j] * g[j])
+        };
+        let is_stable = |x: T| stability_function(-x).abs() <= T::one();
+
+        let step = T::from_f64(0.01).unwrap();
+        let max = T::from_f64(100.).unwrap();
+        let mut x = T::zero();
+        while x < max && is_stable(x + step) {
+            x += step;
+        }
+        if x >= max {
+            return max;
+        }
+        let mut upper = x + step;
+        for _ in 0..30 {
+            let middle = (x + upper) / (T::one() + T::one());
+            if is_stable(middle) {
+                x = middle;
+            } else {
+                upper = middle;
+            }
+        }
+        x
+    }
 }
 
 impl<T> ButcherTableu<T, 1, 2>
@@ -65,8 +249,10 @@ where
             a: [[T::zero()]],
             b: [T::one()],
             b2: [T::zero()],
+            b3: None,
             c: [T::zero()],
             bi: [[T::zero(), T::one()]],
+            rosenbrock: None,
         }
     }
 }
@@ -90,8 +276,10 @@ where
             a: a.map(|row| row.map(|x| T::from_f64(x).unwrap())),
             b: b.map(|x| T::from_f64(x).unwrap()),
             b2: b2.map(|x| T::from_f64(x).unwrap()),
+            b3: None,
             c: c.map(|x| T::from_f64(x).unwrap()),
             bi: bi.map(|row| row.map(|x| T::from_f64(x).unwrap())),
+            rosenbrock: None,
         }
     }
 
@@ -114,6 +302,12 @@ where
 {
     // J.C. Butcher - Numerical Methods for Ordinary Differential Equations, p. 186
     pub fn generic_order_3(c2: f64, c3: f64, b3: Option<f64>) -> Self {
+        Self::try_generic_order_3(c2, c3, b3).expect("Provided arguments are incorrect.")
+    }
+
+    /// Same as [Self::generic_order_3], but returns `None` if the arguments do not define a
+    /// method of order 3.
+    pub fn try_generic_order_3(c2: f64, c3: f64, b3: Option<f64>) -> Option<Self> {
         if c2 != 0. && c3 != 0. && c2 != c3 && c2 != 2. / 3. && b3.is_none() {
             // solvable case I
             let a = [
@@ -134,16 +328,18 @@ where
             let c = [0., c2, c3];
             let bi = [[0., b[0]], [0., b[1]], [0., b[2]]];
 
-            ButcherTableu {
+            Some(ButcherTableu {
                 order: 2,
                 order_embedded: 1,
                 order_interpolant: 1,
                 a: a.map(|row| row.map(|x| T::from_f64(x).unwrap())),
                 b: b.map(|x| T::from_f64(x).unwrap()),
                 b2: b2.map(|x| T::from_f64(x).unwrap()),
+                b3: None,
                 c: c.map(|x| T::from_f64(x).unwrap()),
                 bi: bi.map(|row| row.map(|x| T::from_f64(x).unwrap())),
-            }
+                rosenbrock: None,
+            })
         } else if c2 == 2. / 3.
             && c3 == 2. / 3.
             && let Some(b3) = b3
@@ -160,16 +356,18 @@ where
             let c = [0., c2, c3];
             let bi = [[0., b[0]], [0., b[1]], [0., b[2]]];
 
-            ButcherTableu {
+            Some(ButcherTableu {
                 order: 2,
                 order_embedded: 1,
                 order_interpolant: 1,
                 a: a.map(|row| row.map(|x| T::from_f64(x).unwrap())),
                 b: b.map(|x| T::from_f64(x).unwrap()),
                 b2: b2.map(|x| T::from_f64(x).unwrap()),
+                b3: None,
                 c: c.map(|x| T::from_f64(x).unwrap()),
                 bi: bi.map(|row| row.map(|x| T::from_f64(x).unwrap())),
-            }
+                rosenbrock: None,
+            })
         } else if c2 == 2. / 3.
             && c3 == 0.
             && let Some(b3) = b3
@@ -186,18 +384,20 @@ where
             let c = [0., c2, c3];
             let bi = [[0., b[0]], [0., b[1]], [0., b[2]]];
 
-            ButcherTableu {
+            Some(ButcherTableu {
                 order: 2,
                 order_embedded: 1,
                 order_interpolant: 1,
                 a: a.map(|row| row.map(|x| T::from_f64(x).unwrap())),
                 b: b.map(|x| T::from_f64(x).unwrap()),
                 b2: b2.map(|x| T::from_f64(x).unwrap()),
+                b3: None,
                 c: c.map(|x| T::from_f64(x).unwrap()),
                 bi: bi.map(|row| row.map(|x| T::from_f64(x).unwrap())),
-            }
+                rosenbrock: None,
+            })
         } else {
-            panic!("Provided arguments are incorrect.")
+            None
         }
     }
 
@@ -242,8 +442,10 @@ where
             a: a.map(|row| row.map(|x| T::from_f64(x).unwrap())),
             b: b.map(|x| T::from_f64(x).unwrap()),
             b2: b2.map(|x| T::from_f64(x).unwrap()),
+            b3: None,
             c: c.map(|x| T::from_f64(x).unwrap()),
             bi: bi.map(|row| row.map(|x| T::from_f64(x).unwrap())),
+            rosenbrock: None,
         }
     }
 
@@ -267,8 +469,10 @@ where
             a: a.map(|row| row.map(|x| T::from_f64(x).unwrap())),
             b: b.map(|x| T::from_f64(x).unwrap()),
             b2: b2.map(|x| T::from_f64(x).unwrap()),
+            b3: None,
             c: c.map(|x| T::from_f64(x).unwrap()),
             bi: bi.map(|row| row.map(|x| T::from_f64(x).unwrap())),
+            rosenbrock: None,
         }
     }
 }
@@ -303,8 +507,47 @@ where
             a: a.map(|row| row.map(|x| T::from_f64(x).unwrap())),
             b: b.map(|x| T::from_f64(x).unwrap()),
             b2: b2.map(|x| T::from_f64(x).unwrap()),
+            b3: None,
             c: c.map(|x| T::from_f64(x).unwrap()),
             bi: bi.map(|row| row.map(|x| T::from_f64(x).unwrap())),
+            rosenbrock: None,
+        }
+    }
+}
+
+impl<T: RealField + Copy> ButcherTableu<T, 4, 4> {
+    /// Bogacki-Shampine method (P. Bogacki, L. F. Shampine - A 3(2) pair of Runge-Kutta
+    /// formulas, 1989) of order 3 with the embedded method of order 2, and the free continuous
+    /// extension of order 3, which is the Hermite interpolation. The last stage is the derivative
+    /// at the end of the step.
+    pub fn bs3() -> Self {
+        let a = [
+            [0., 0., 0., 0.],
+            [0.5, 0., 0., 0.],
+            [0., 0.75, 0., 0.],
+            [2. / 9., 1. / 3., 4. / 9., 0.],
+        ];
+        let b = a[3];
+        let b2 = [7. / 24., 0.25, 1. / 3., 0.125];
+        let c = [0., 0.5, 0.75, 1.];
+        let bi = [
+            [0., 1., -4. / 3., 5. / 9.],
+            [0., 0., 1., -2. / 3.],
+            [0., 0., 4. / 3., -8. / 9.],
+            [0., 0., -1., 1.],
+        ];
+
+        ButcherTableu {
+            order: 3,
+            order_embedded: 2,
+            order_interpolant: 3,
+            a: a.map(|row| row.map(|x| T::from_f64(x).unwrap())),
+            b: b.map(|x| T::from_f64(x).unwrap()),
+            b2: b2.map(|x| T::from_f64(x).unwrap()),
+            b3: None,
+            c: c.map(|x| T::from_f64(x).unwrap()),
+            bi: bi.map(|row| row.map(|x| T::from_f64(x).unwrap())),
+            rosenbrock: None,
         }
     }
 }
@@ -423,8 +666,873 @@ impl<T: RealField + Copy> ButcherTableu<T, 7, 5> {
             a: a.map(|row| row.map(|x| T::from_f64(x).unwrap())),
             b: b.map(|x| T::from_f64(x).unwrap()),
             b2: b2.map(|x| T::from_f64(x).unwrap()),
+            b3: None,
+            c: c.map(|x| T::from_f64(x).unwrap()),
+            bi: bi.map(|row| row.map(|x| T::from_f64(x).unwrap())),
+            rosenbrock: None,
+        }
+    }
+
+    /// Dormand-Prince method (J. R. Dormand, P. J. Prince - A family of embedded Runge-Kutta
+    /// formulae, 1980) of order 5 with the embedded method of order 4, and the continuous
+    /// extension of order 4 by L. F. Shampine (E. Hairer, S. P. Nørsett, G. Wanner - Solving
+    /// Ordinary Differential Equations I, Section II.6). The last stage is the derivative at
+    /// the end of the step.
+    pub fn dopri5() -> Self {
+        let a = [
+            [0., 0., 0., 0., 0., 0., 0.],
+            [1. / 5., 0., 0., 0., 0., 0., 0.],
+            [3. / 40., 9. / 40., 0., 0., 0., 0., 0.],
+            [44. / 45., -56. / 15., 32. / 9., 0., 0., 0., 0.],
+            [
+                19372. / 6561.,
+                -25360. / 2187.,
+                64448. / 6561.,
+                -212. / 729.,
+                0.,
+                0.,
+                0.,
+            ],
+            [
+                9017. / 3168.,
+                -355. / 33.,
+                46732. / 5247.,
+                49. / 176.,
+                -5103. / 18656.,
+                0.,
+                0.,
+            ],
+            [
+                35. / 384.,
+                0.,
+                500. / 1113.,
+                125. / 192.,
+                -2187. / 6784.,
+                11. / 84.,
+                0.,
+            ],
+        ];
+        let b = a[6];
+        let b2 = [
+            5179. / 57600.,
+            0.,
+            7571. / 16695.,
+            393. / 640.,
+            -92097. / 339200.,
+            187. / 2100.,
+            1. / 40.,
+        ];
+        let c = [0., 1. / 5., 3. / 10., 4. / 5., 8. / 9., 1., 1.];
+        let bi = [
+            [
+                0.,
+                1.,
+                -8048581381. / 2820520608.,
+                8663915743. / 2820520608.,
+                -12715105075. / 11282082432.,
+            ],
+            [0., 0., 0., 0., 0.],
+            [
+                0.,
+                0.,
+                131558114200. / 32700410799.,
+                -68118460800. / 10900136933.,
+                87487479700. / 32700410799.,
+            ],
+            [
+                0.,
+                0.,
+                -1754552775. / 470086768.,
+                14199869525. / 1410260304.,
+                -10690763975. / 1880347072.,
+            ],
+            [
+                0.,
+                0.,
+                127303824393. / 49829197408.,
+                -318862633887. / 49829197408.,
+                701980252875. / 199316789632.,
+            ],
+            [
+                0.,
+                0.,
+                -282668133. / 205662961.,
+                2019193451. / 616988883.,
+                -1453857185. / 822651844.,
+            ],
+            [
+                0.,
+                0.,
+                40617522. / 29380423.,
+                -110615467. / 29380423.,
+                69997945. / 29380423.,
+            ],
+        ];
+
+        ButcherTableu {
+            order: 5,
+            order_embedded: 4,
+            order_interpolant: 4,
+            a: a.map(|row| row.map(|x| T::from_f64(x).unwrap())),
+            b: b.map(|x| T::from_f64(x).unwrap()),
+            b2: b2.map(|x| T::from_f64(x).unwrap()),
+            b3: None,
+            c: c.map(|x| T::from_f64(x).unwrap()),
+            bi: bi.map(|row| row.map(|x| T::from_f64(x).unwrap())),
+            rosenbrock: None,
+        }
+    }
+
+    /// Tsitouras 5(4) method (Ch. Tsitouras - Runge-Kutta pairs of order 5(4) satisfying only
+    /// the first column simplifying assumption, 2011) of order 5 with the embedded method of
+    /// order 4, and the free continuous extension of order 4. The last stage is the derivative
+    /// at the end of the step.
+    pub fn tsit5() -> Self {
+        let a = [
+            [0., 0., 0., 0., 0., 0., 0.],
+            [0.161, 0., 0., 0., 0., 0., 0.],
+            [-0.008480655492356989, 0.335480655492357, 0., 0., 0., 0., 0.],
+            [
+                2.897153057105493,
+                -6.359448489975075,
+                4.3622954328695815,
+                0.,
+                0.,
+                0.,
+                0.,
+            ],
+            [
+                5.325864828439257,
+                -11.748883564062828,
+                7.4955393428898365,
+                -0.09249506636175525,
+                0.,
+                0.,
+                0.,
+            ],
+            [
+                5.86145544294642,
+                -12.92096931784711,
+                8.159367898576159,
+                -0.071584973281401,
+                -0.028269050394068383,
+                0.,
+                0.,
+            ],
+            [
+                0.09646076681806523,
+                0.01,
+                0.4798896504144996,
+                1.379008574103742,
+                -3.290069515436081,
+                2.324710524099774,
+                0.,
+            ],
+        ];
+        let b = a[6];
+        // differences of the main and the embedded methods
+        let b_diff = [
+            -0.00178001105222577714,
+            -0.0008164344596567469,
+            0.007880878010261995,
+            -0.1447110071732629,
+            0.5823571654525552,
+            -0.45808210592918697,
+            1. / 66.,
+        ];
+        let b2 = std::array::from_fn(|i| b[i] - b_diff[i]);
+        let c = [0., 0.161, 0.327, 0.9, 0.9800255409045097, 1., 1.];
+        let bi = [
+            [
+                0.,
+                1.,
+                -2.763706197274826,
+                2.9132554618219126,
+                -1.0530884977290216,
+            ],
+            [0., 0., 0.1317, -0.2234, 0.1017],
+            [
+                0.,
+                0.,
+                3.9302962368947516,
+                -5.941033872131505,
+                2.490627285651253,
+            ],
+            [
+                0.,
+                0.,
+                -12.411077166933676,
+                30.33818863028232,
+                -16.548102889244902,
+            ],
+            [
+                0.,
+                0.,
+                37.50931341651104,
+                -88.1789048947664,
+                47.37952196281928,
+            ],
+            [
+                0.,
+                0.,
+                -27.896526289197286,
+                65.09189467479366,
+                -34.87065786149661,
+            ],
+            [0., 0., 1.5, -4., 2.5],
+        ];
+
+        ButcherTableu {
+            order: 5,
+            order_embedded: 4,
+            order_interpolant: 4,
+            a: a.map(|row| row.map(|x| T::from_f64(x).unwrap())),
+            b: b.map(|x| T::from_f64(x).unwrap()),
+            b2: b2.map(|x| T::from_f64(x).unwrap()),
+            b3: None,
+            c: c.map(|x| T::from_f64(x).unwrap()),
+            bi: bi.map(|row| row.map(|x| T::from_f64(x).unwrap())),
+            rosenbrock: None,
+        }
+    }
+}
+
+impl<T: RealField + Copy> ButcherTableu<T, 16, 8> {
+    /// DOP853 method (E. Hairer, S. P. Nørsett, G. Wanner - Solving Ordinary Differential
+    /// Equations I, Section II.10) of order 8 with the embedded method of order 5, and the dense
+    /// output of order 7. The stage 13 is the derivative at the end of the step, and the last
+    /// three stages are evaluated only for the dense output.
+    ///
+    /// As in the DOP853 code, the error estimates `err5` and `err3` of the embedded methods of
+    /// orders 5 and 3 (see [ButcherTableu::b3]) are combined as
+    /// `err5² / sqrt(err5² + 0.01 err3²)`, measured with the norm of the stepsize controller.
+    pub fn dop853() -> Self {
+        let mut a = [[0.; 16]; 16];
+        a[1][0..1].copy_from_slice(&[0.0526001519587677318785587544488]);
+        a[2][0..2].copy_from_slice(&[
+            0.0197250569845378994544595329183,
+            0.0591751709536136983633785987549,
+        ]);
+        a[3][0..3].copy_from_slice(&[
+            0.0295875854768068491816892993775,
+            0.,
+            0.0887627564304205475450678981324,
+        ]);
+        a[4][0..4].copy_from_slice(&[
+            0.241365134159266685502369798665,
+            0.,
+            -0.884549479328286085344864962717,
+            0.924834003261792003115737966543,
+        ]);
+        a[5][0..5].copy_from_slice(&[
+            0.037037037037037037037037037037,
+            0.,
+            0.,
+            0.170828608729473871279604482173,
+            0.125467687566822425016691814123,
+        ]);
+        a[6][0..6].copy_from_slice(&[
+            0.037109375,
+            0.,
+            0.,
+            0.170252211019544039314978060272,
+            0.0602165389804559606850219397283,
+            -0.017578125,
+        ]);
+        a[7][0..7].copy_from_slice(&[
+            0.0370920001185047927108779319836,
+            0.,
+            0.,
+            0.170383925712239993810214054705,
+            0.107262030446373284651809199168,
+            -0.0153194377486244017527936158236,
+            0.00827378916381402288758473766002,
+        ]);
+        a[8][0..8].copy_from_slice(&[
+            0.624110958716075717114429577812,
+            0.,
+            0.,
+            -3.36089262944694129406857109825,
+            -0.868219346841726006818189891453,
+            27.5920996994467083049415600797,
+            20.1540675504778934086186788979,
+            -43.4898841810699588477366255144,
+        ]);
+        a[9][0..9].copy_from_slice(&[
+            0.477662536438264365890433908527,
+            0.,
+            0.,
+            -2.48811461997166764192642586468,
+            -0.590290826836842996371446475743,
+            21.2300514481811942347288949897,
+            15.2792336328824235832596922938,
+            -33.2882109689848629194453265587,
+            -0.0203312017085086261358222928593,
+        ]);
+        a[10][0..10].copy_from_slice(&[
+            -0.93714243008598732571704021658,
+            0.,
+            0.,
+            5.18637242884406370830023853209,
+            1.09143734899672957818500254654,
+            -8.14978701074692612513997267357,
+            -18.5200656599969598641566180701,
+            22.7394870993505042818970056734,
+            2.49360555267965238987089396762,
+            -3.0467644718982195003823669022,
+        ]);
+        a[11][0..11].copy_from_slice(&[
+            2.27331014751653820792359768449,
+            0.,
+            0.,
+            -10.5344954667372501984066689879,
+            -2.00087205822486249909675718444,
+            -17.9589318631187989172765950534,
+            27.9488845294199600508499808837,
+            -2.85899827713502369474065508674,
+            -8.87285693353062954433549289258,
+            12.3605671757943030647266201528,
+            0.643392746015763530355970484046,
+        ]);
+        a[12][0..12].copy_from_slice(&[
+            0.0542937341165687622380535766363,
+            0.,
+            0.,
+            0.,
+            0.,
+            4.45031289275240888144113950566,
+            1.89151789931450038304281599044,
+            -5.8012039600105847814672114227,
+            0.31116436695781989440891606237,
+            -0.152160949662516078556178806805,
+            0.201365400804030348374776537501,
+            0.0447106157277725905176885569043,
+        ]);
+        a[13][0..13].copy_from_slice(&[
+            0.0561675022830479523392909219681,
+            0.,
+            0.,
+            0.,
+            0.,
+            0.,
+            0.253500210216624811088794765333,
+            -0.246239037470802489917441475441,
+            -0.124191423263816360469010140626,
+            0.15329179827876569731206322685,
+            0.00820105229563468988491666602057,
+            0.00756789766054569976138603589584,
+            -0.008298,
+        ]);
+        a[14][0..14].copy_from_slice(&[
+            0.0318346481635021405060768473261,
+            0.,
+            0.,
+            0.,
+            0.,
+            0.0283009096723667755288322961402,
+            0.0535419883074385676223797384372,
+            -0.0549237485713909884646569340306,
+            0.,
+            0.,
+            -1.08347328697249322858509316994e-4,
+            3.82571090835658412954920192323e-4,
+            -3.40465008687404560802977114492e-4,
+            0.141312443674632500278074618366,
+        ]);
+        a[15][0..15].copy_from_slice(&[
+            -0.428896301583791923408573538692,
+            0.,
+            0.,
+            0.,
+            0.,
+            -4.69762141536116384314449447206,
+            7.68342119606259904184240953878,
+            4.06898981839711007970213554331,
+            0.356727187455281109270669543021,
+            0.,
+            0.,
+            0.,
+            -0.00139902416515901462129418009734,
+            2.9475147891527723389556272149,
+            -9.15095847217987001081870187138,
+        ]);
+        let b = [
+            0.0542937341165687622380535766363,
+            0.,
+            0.,
+            0.,
+            0.,
+            4.45031289275240888144113950566,
+            1.89151789931450038304281599044,
+            -5.8012039600105847814672114227,
+            0.31116436695781989440891606237,
+            -0.152160949662516078556178806805,
+            0.201365400804030348374776537501,
+            0.0447106157277725905176885569043,
+            0.,
+            0.,
+            0.,
+            0.,
+        ];
+        let b2 = [
+            0.0411736891223738815055525466763,
+            0.,
+            0.,
+            0.,
+            0.,
+            5.67546933912861332216170925866,
+            2.38727684897175057456422398564,
+            -7.4655811424655713184287418377,
+            0.66149321570779357609756479137,
+            -0.486340068375533557585910690905,
+            0.119442194318914635909069111371,
+            0.0670659235916588857765328353543,
+            0.,
+            0.,
+            0.,
+            0.,
+        ];
+        // the quadrature of order 3 with the nodes 0, 127/195 and 1
+        let mut b3 = [0.; 16];
+        (b3[0], b3[8], b3[11]) = (31. / 127., 12675. / 17272., 3. / 136.);
+        let c = [
+            0.,
+            0.0526001519587677318785587544488,
+            0.0789002279381515978178381316732,
+            0.118350341907227396726757197510,
+            0.281649658092772603273242802490,
+            1. / 3.,
+            0.25,
+            4. / 13.,
+            127. / 195.,
+            0.6,
+            6. / 7.,
+            1.,
+            1.,
+            0.1,
+            0.2,
+            7. / 9.,
+        ];
+        let bi = [
+            [
+                0.,
+                1.0,
+                -10.2660570737593065784211883843,
+                48.1618509685664566301953957011,
+                -114.933048749978332538237198112,
+                147.464468756697683076313870247,
+                -97.066853630113680830925412004,
+                25.693933462703749003312586129,
+            ],
+            [0., 0., 0., 0., 0., 0., 0., 0.],
+            [0., 0., 0., 0., 0., 0., 0., 0.],
+            [0., 0., 0., 0., 0., 0., 0., 0.],
+            [0., 0., 0., 0., 0., 0., 0., 0.],
+            [
+                0.,
+                0.,
+                13.9176536317766044139487363529,
+                -154.787872666637155968890178913,
+                522.921908960821874913658927446,
+                -456.25918840208781254725549026,
+                -75.53193732135753567056079139,
+                154.18974869023643374053993627,
+            ],
+            [
+                0.,
+                0.,
+                2.60560375199360945784871749862,
+                -21.6228223846265042267806054455,
+                2.5351820289667551481771305373,
+                292.2541746599040625262099723,
+                -505.40999933296891819777278999,
+                231.52937917604549567536039109,
+            ],
+            [
+                0.,
+                0.,
+                -15.0189442235196845156288193001,
+                160.094477089730476115815838249,
+                -474.307182603764347814837943472,
+                135.96036916173837287308688176,
+                545.10919452641872234295033044,
+                -357.6391179106141237828534991,
+            ],
+            [
+                0.,
+                0.,
+                3.05052768331848795994226318171,
+                -38.5439672918906325046616718259,
+                174.471400092198840731590697257,
+                -337.051347023877126426419627092,
+                291.789875090832560137864946246,
+                -93.405324183624310003907691704,
+            ],
+            [
+                0.,
+                0.,
+                -1.32787443276552122773643549531,
+                16.6617704300495419971752379103,
+                -74.4402781412630338777638949858,
+                140.752100161916063360485884732,
+                -119.256202104051199487592110319,
+                37.458323136451633156875139351,
+            ],
+            [
+                0.,
+                0.,
+                2.8445336326728793209785067775,
+                -36.558295489910119270837507129,
+                170.690071691475136612404224551,
+                -345.97484854804955105743375744,
+                313.299553623577985194735771618,
+                -104.09964950896230045147246184,
+            ],
+            [
+                0.,
+                0.,
+                0.765710625952786589708768163613,
+                -9.90699553561936636937481161281,
+                46.8029919188743947246274324409,
+                -96.5198694669957042802037349354,
+                88.7431665001761650491043980796,
+                -29.840293426660503123344363579,
+            ],
+            [
+                0.,
+                0.,
+                -1.0889903364513333108206981174,
+                14.0970130423200021011792868759,
+                -66.6823059129436396177354540237,
+                137.962990634743749929648014947,
+                -127.82216401767992285670332474,
+                43.533456590011143754432175058,
+            ],
+            [
+                0.,
+                0.,
+                18.148505520854727256656404962,
+                -127.633109492538752948863041181,
+                357.341951612965727834419198922,
+                -500.703150790922388797269844749,
+                349.170357108828969603452232646,
+                -96.3245539591882829483949506,
+            ],
+            [
+                0.,
+                0.,
+                -9.1946323924783554000451984436,
+                93.3567459327893934316789144048,
+                -282.627261870436320846613623435,
+                361.140077188033322163602783602,
+                -201.8521905335234785138543623,
+                39.177261675615439165231486172,
+            ],
+            [
+                0.,
+                0.,
+                -4.4360363875948939664310572,
+                56.68120539776666101336314297,
+                -261.77342902691705526968949713,
+                520.97422366889932917923504691,
+                -461.17279991013966677069888831,
+                149.72683625798562581422125276,
+            ],
+        ];
+
+        ButcherTableu {
+            order: 8,
+            order_embedded: 5,
+            order_interpolant: 7,
+            a: a.map(|row| row.map(|x| T::from_f64(x).unwrap())),
+            b: b.map(|x| T::from_f64(x).unwrap()),
+            b2: b2.map(|x| T::from_f64(x).unwrap()),
+            b3: Some(b3.map(|x| T::from_f64(x).unwrap())),
+            c: c.map(|x| T::from_f64(x).unwrap()),
+            bi: bi.map(|row| row.map(|x| T::from_f64(x).unwrap())),
+            rosenbrock: None,
+        }
+    }
+}
+
+impl<T: RealField + Copy> ButcherTableu<T, 3, 4> {
+    /// TR-BDF2 method (M.E. Hosea, L.F. Shampine - Analysis and implementation of TR-BDF2,
+    /// 1996), an L-stable ESDIRK method of order 2 with the embedded method of order 3, and
+    /// cubic Hermite interpolation. It is implicit, see [crate::Solver::implicit_rk].
+    pub fn tr_bdf2() -> Self {
+        let sqrt2 = 2f64.sqrt();
+        let gamma = 2. - sqrt2;
+        let d = gamma / 2.;
+        let w = sqrt2 / 4.;
+
+        let a = [[0., 0., 0.], [d, d, 0.], [w, w, d]];
+        let b = [w, w, d];
+        let b2 = [(1. - w) / 3., (3. * w + 1.) / 3., d / 3.];
+        let c = [0., gamma, 1.];
+        let bi = [
+            [0., 1., 3. * w - 2., 1. - 2. * w],
+            [0., 0., 3. * w, -2. * w],
+            [0., 0., 3. * d - 1., 1. - 2. * d],
+        ];
+
+        ButcherTableu {
+            order: 2,
+            order_embedded: 3,
+            order_interpolant: 3,
+            a: a.map(|row| row.map(|x| T::from_f64(x).unwrap())),
+            b: b.map(|x| T::from_f64(x).unwrap()),
+            b2: b2.map(|x| T::from_f64(x).unwrap()),
+            b3: None,
+            c: c.map(|x| T::from_f64(x).unwrap()),
+            bi: bi.map(|row| row.map(|x| T::from_f64(x).unwrap())),
+            rosenbrock: None,
+        }
+    }
+}
+
+impl<T: RealField + Copy> ButcherTableu<T, 5, 4> {
+    /// L-stable SDIRK method of order 4 with the embedded method of order 3 (E. Hairer, G.
+    /// Wanner - Solving Ordinary Differential Equations II, Table 6.5), with the interpolant of
+    /// order 3. It is implicit, see [crate::Solver::implicit_rk].
+    pub fn sdirk4() -> Self {
+        let a = [
+            [1. / 4., 0., 0., 0., 0.],
+            [1. / 2., 1. / 4., 0., 0., 0.],
+            [17. / 50., -1. / 25., 1. / 4., 0., 0.],
+            [371. / 1360., -137. / 2720., 15. / 544., 1. / 4., 0.],
+            [25. / 24., -49. / 48., 125. / 16., -85. / 12., 1. / 4.],
+        ];
+        let b = a[4];
+        let b2 = [59. / 48., -17. / 96., 225. / 32., -85. / 12., 0.];
+        let c = [1. / 4., 3. / 4., 11. / 20., 1. / 2., 1.];
+        let bi = [
+            [0., 11083. / 5816., 6311. / 5816., -34007. / 17448.],
+            [0., -28205. / 11632., 113505. / 11632., -291523. / 34896.],
+            [0., 4575. / 11632., 177625. / 11632., -91325. / 11632.],
+            [0., 0., -85. / 4., 85. / 6.],
+            [0., 1637. / 1454., -14143. / 2908., 2899. / 727.],
+        ];
+
+        ButcherTableu {
+            order: 4,
+            order_embedded: 3,
+            order_interpolant: 3,
+            a: a.map(|row| row.map(|x| T::from_f64(x).unwrap())),
+            b: b.map(|x| T::from_f64(x).unwrap()),
+            b2: b2.map(|x| T::from_f64(x).unwrap()),
+            b3: None,
+            c: c.map(|x| T::from_f64(x).unwrap()),
+            bi: bi.map(|row| row.map(|x| T::from_f64(x).unwrap())),
+            rosenbrock: None,
+        }
+    }
+}
+
+impl<T: RealField + Copy> ButcherTableu<T, 4, 4> {
+    /// Radau IIA method of order 5 (E. Hairer, G. Wanner - Solving Ordinary Differential
+    /// Equations II, Section IV.8), a fully implicit L-stable collocation method with the
+    /// interpolant given by the collocation polynomial. It is implicit, see
+    /// [crate::Solver::implicit_rk].
+    ///
+    /// The first stage is the explicit evaluation at the start of the step, which is only used
+    /// by the embedded method of order 3, as in RADAU5 code by E. Hairer and G. Wanner.
+    pub fn radau5() -> Self {
+        let sqrt6 = 6f64.sqrt();
+        let a = [
+            [0., 0., 0., 0.],
+            [
+                0.,
+                (88. - 7. * sqrt6) / 360.,
+                (296. - 169. * sqrt6) / 1800.,
+                (-2. + 3. * sqrt6) / 225.,
+            ],
+            [
+                0.,
+                (296. + 169. * sqrt6) / 1800.,
+                (88. + 7. * sqrt6) / 360.,
+                (-2. - 3. * sqrt6) / 225.,
+            ],
+            [0., (16. - sqrt6) / 36., (16. + sqrt6) / 36., 1. / 9.],
+        ];
+        let b = a[3];
+        let b2 = [
+            0.27488882959567734,
+            -0.05189523141490089,
+            0.7575249005733381,
+            0.01948150124588535,
+        ];
+        let c = [0., (4. - sqrt6) / 10., (4. + sqrt6) / 10., 1.];
+        let bi = [
+            [0., 0., 0., 0.],
+            [
+                0.,
+                1.5580782047249226,
+                -1.986947221348443,
+                0.8052720793239878,
+            ],
+            [
+                0.,
+                -0.891411538058256,
+                3.3202805546817764,
+                -1.9163831904350987,
+            ],
+            [0., 1. / 3., -4. / 3., 10. / 9.],
+        ];
+
+        ButcherTableu {
+            order: 5,
+            order_embedded: 3,
+            order_interpolant: 3,
+            a: a.map(|row| row.map(|x| T::from_f64(x).unwrap())),
+            b: b.map(|x| T::from_f64(x).unwrap()),
+            b2: b2.map(|x| T::from_f64(x).unwrap()),
+            b3: None,
+            c: c.map(|x| T::from_f64(x).unwrap()),
+            bi: bi.map(|row| row.map(|x| T::from_f64(x).unwrap())),
+            rosenbrock: None,
+        }
+    }
+}
+
+impl<T: RealField + Copy> ButcherTableu<T, 3, 3> {
+    /// ROS3P Rosenbrock method (J. Lang, J. Verwer - ROS3P - An accurate third-order Rosenbrock
+    /// solver designed for parabolic problems, 2001) of order 3 with the embedded method of
+    /// order 2, and the interpolant of order 2. It is implicit, see [crate::Solver::implicit_rk].
+    pub fn ros3p() -> Self {
+        let a = [
+            [0., 0., 0.],
+            [1.267949192431123, 0., 0.],
+            [1.267949192431123, 0., 0.],
+        ];
+        let b = [2., 5.773502691896258e-01, 4.226497308103742e-01];
+        let b2 = [2.113248654051871, 1., 4.226497308103742e-01];
+        let c = [0., 1., 1.];
+        let bi = [
+            [0., 2.7938566201357364, -0.793856620135737],
+            [0., -0.1919872981077816, 0.7693375672974075],
+            [0., 1.1547005383792501, -0.7320508075688763],
+        ];
+        let coupling = [
+            [0., 0., 0.],
+            [-1.607695154586736, 0., 0.],
+            [-3.464101615137755, -1.732050807568877, 0.],
+        ];
+        let gamma_t = [
+            7.886751345948129e-01,
+            -2.113248654051871e-01,
+            -1.077350269189626,
+        ];
+
+        ButcherTableu {
+            order: 3,
+            order_embedded: 2,
+            order_interpolant: 2,
+            a: a.map(|row| row.map(|x| T::from_f64(x).unwrap())),
+            b: b.map(|x| T::from_f64(x).unwrap()),
+            b2: b2.map(|x| T::from_f64(x).unwrap()),
+            b3: None,
+            c: c.map(|x| T::from_f64(x).unwrap()),
+            bi: bi.map(|row| row.map(|x| T::from_f64(x).unwrap())),
+            rosenbrock: Some(Rosenbrock {
+                gamma: T::from_f64(7.886751345948129e-01).unwrap(),
+                coupling: coupling.map(|row| row.map(|x| T::from_f64(x).unwrap())),
+                gamma_t: gamma_t.map(|x| T::from_f64(x).unwrap()),
+            }),
+        }
+    }
+}
+
+impl<T: RealField + Copy> ButcherTableu<T, 6, 4> {
+    /// RODAS4 Rosenbrock method (E. Hairer, G. Wanner - Solving Ordinary Differential Equations
+    /// II, Section VI.4), a stiffly accurate method of order 4, whose embedded solution of order
+    /// 3 is the argument of the last stage `y + Σ a_6j U_j`, and the interpolant of order 3. It
+    /// is implicit, see [crate::Solver::implicit_rk].
+    pub fn rodas4() -> Self {
+        let mut a = [[0.; 6]; 6];
+        a[1][0..1].copy_from_slice(&[1.544]);
+        a[2][0..2].copy_from_slice(&[0.9466785280815826, 0.2557011698983284]);
+        a[3][0..3].copy_from_slice(&[3.314825187068521, 2.896124015972201, 0.9986419139977817]);
+        a[4][0..4].copy_from_slice(&[
+            1.221224509226641,
+            6.019134481288629,
+            12.53708332932087,
+            -0.6878860361058950,
+        ]);
+        a[5][0..5].copy_from_slice(&[
+            1.221224509226641,
+            6.019134481288629,
+            12.53708332932087,
+            -0.6878860361058950,
+            1.,
+        ]);
+        let b = [
+            1.221224509226641,
+            6.019134481288629,
+            12.53708332932087,
+            -0.6878860361058950,
+            1.,
+            1.,
+        ];
+        // the embedded solution is the argument of the last stage
+        let b2 = a[5];
+        let c = [0., 0.386, 0.21, 0.63, 1., 1.];
+        // the interpolant y0 + θ (y1 - y0) + θ (1 - θ) Σ (h2_i + θ h3_i) U_i
+        let h2 = [
+            10.12623508344586,
+            -7.487995877610167,
+            -34.80091861555747,
+            -7.992771707568823,
+            1.025137723295662,
+            0.,
+        ];
+        let h3 = [
+            -0.6762803392801253,
+            6.087714651680015,
+            16.43084320892478,
+            24.76722511418386,
+            -6.594389125716872,
+            0.,
+        ];
+        let bi: [[f64; 4]; 6] = std::array::from_fn(|i| [0., b[i] + h2[i], h3[i] - h2[i], -h3[i]]);
+
+        let mut coupling = [[0.; 6]; 6];
+        coupling[1][0..1].copy_from_slice(&[-5.6688]);
+        coupling[2][0..2].copy_from_slice(&[-2.430093356833875, -0.2063599157091915]);
+        coupling[3][0..3].copy_from_slice(&[
+            -0.1073529058151375,
+            -9.594562251023355,
+            -20.47028614809616,
+        ]);
+        coupling[4][0..4].copy_from_slice(&[
+            7.496443313967647,
+            -10.24680431464352,
+            -33.99990352819905,
+            11.70890893206160,
+        ]);
+        coupling[5][0..5].copy_from_slice(&[
+            8.083246795921522,
+            -7.981132988064893,
+            -31.52159432874371,
+            16.31930543123136,
+            -6.058818238834054,
+        ]);
+        let gamma_t = [0.25, -0.1043, 0.1035, -0.0362, 0., 0.];
+
+        ButcherTableu {
+            order: 4,
+            order_embedded: 3,
+            order_interpolant: 3,
+            a: a.map(|row| row.map(|x| T::from_f64(x).unwrap())),
+            b: b.map(|x| T::from_f64(x).unwrap()),
+            b2: b2.map(|x| T::from_f64(x).unwrap()),
+            b3: None,
             c: c.map(|x| T::from_f64(x).unwrap()),
             bi: bi.map(|row| row.map(|x| T::from_f64(x).unwrap())),
+            rosenbrock: Some(Rosenbrock {
+                gamma: T::from_f64(0.25).unwrap(),
+                coupling: coupling.map(|row| row.map(|x| T::from_f64(x).unwrap())),
+                gamma_t: gamma_t.map(|x| T::from_f64(x).unwrap()),
+            }),
         }
     }
 }
@@ -0,0 +1,1808 @@
+//! Verner's "most efficient" pairs of explicit Runge-Kutta methods (J. H. Verner - Numerically
+//! optimal Runge-Kutta pairs with interpolants, 2010), see [ButcherTableu::vern6],
+//! [ButcherTableu::vern7], [ButcherTableu::vern8] and [ButcherTableu::vern9].
+//!
+//! The main stages are Verner's, but the stages appended for the dense output and `bi` are not
+//! transcribed from his interpolants. They are solved in high precision with as many stages as
+//! Verner's interpolants, 12, 16, 21 and 26, and `bi` are rounded so that their rows sum to `b` up
+//! to the roundoff relative to the magnitude of the row.
+
+use nalgebra::RealField;
+
+use crate::rk::ButcherTableu;
+
+impl<T: RealField + Copy> ButcherTableu<T, 12, 7> {
+    /// Verner's "most efficient" 6(5) pair (J. H. Verner - Numerically optimal Runge-Kutta pairs
+    /// with interpolants, 2010) of order 6 with the embedded method of order 5, and the dense
+    /// output of order 6. The stage 9 is the derivative at the end of the step, which is also used
+    /// by the embedded method. The last three stages are evaluated only for the dense output (see
+    /// [ButcherTableu::main_stages]).
+    ///
+    /// The dense output has as many stages as Verner's interpolant. The appended stages are chosen
+    /// so that the continuous order conditions are solvable, and `bi` are their least squares
+    /// solution, with `bi(1) = b` imposed on the coefficients of the highest degree. The conditions
+    /// hold up to the roundoff of the coefficients of the method, as the order conditions of `b`
+    /// do.
+    pub fn vern6() -> Self {
+        let mut a = [[0.; 12]; 12];
+        a[1][0..1].copy_from_slice(&[0.06]);
+        a[2][0..2].copy_from_slice(&[0.019239962962962962, 0.07669337037037037]);
+        a[3][0..3].copy_from_slice(&[0.035975, 0., 0.107925]);
+        a[4][0..4].copy_from_slice(&[
+            1.3186834152331484,
+            0.,
+            -5.042058063628562,
+            4.220674648395414,
+        ]);
+        a[5][0..5].copy_from_slice(&[
+            -41.872591664327516,
+            0.,
+            159.4325621631375,
+            -122.11921356501003,
+            5.531743066200054,
+        ]);
+        a[6][0..6].copy_from_slice(&[
+            -54.430156935316504,
+            0.,
+            207.06725136501848,
+            -158.61081378459,
+            6.991816585950242,
+            -0.018597231062203234,
+        ]);
+        a[7][0..7].copy_from_slice(&[
+            -54.66374178728198,
+            0.,
+            207.95280625538936,
+            -159.2889574744995,
+            7.018743740796944,
+            -0.018338785905045722,
+            -0.0005119484997882099,
+        ]);
+        a[8][0..8].copy_from_slice(&[
+            0.03438957868357036,
+            0.,
+            0.,
+            0.2582624555633503,
+            0.4209371189673537,
+            4.40539646966931,
+            -176.48311902429865,
+            172.36413340141507,
+        ]);
+        a[9][0..9].copy_from_slice(&[
+            -0.04451433542223075,
+            0.,
+            0.2074933451252756,
+            0.15956550041804937,
+            0.17245792149212255,
+            -0.12558445763404968,
+            0.10868171887938106,
+            -0.007881951277913694,
+            0.006280342043943003,
+        ]);
+        a[10][0..10].copy_from_slice(&[
+            0.037808819880746296,
+            0.,
+            0.18200890184993113,
+            -0.08032289913439088,
+            -0.297630565496542,
+            0.003665281182489757,
+            0.0824954980416207,
+            -0.0925200980407163,
+            0.008168515114160058,
+            0.3243696406998882,
+        ]);
+        a[11][0..11].copy_from_slice(&[
+            -0.0352006850363411,
+            0.,
+            0.20868452558777656,
+            0.16746526706293394,
+            -0.0701008134315394,
+            -0.03584674578519977,
+            -0.0718180220272714,
+            0.0968101433374584,
+            0.006214700359279105,
+            0.3573872533709187,
+            -0.04260862511021041,
+        ]);
+        let b = [
+            0.03438957868357036,
+            0.,
+            0.,
+            0.2582624555633503,
+            0.4209371189673537,
+            4.40539646966931,
+            -176.48311902429865,
+            172.36413340141507,
+            0.,
+            0.,
+            0.,
+            0.,
+        ];
+        let b2 = [
+            0.0490996764838249,
+            0.,
+            0.,
+            0.22511122295165242,
+            0.4694682253029562,
+            0.8065792249988868,
+            0.,
+            -0.607119489177796,
+            0.056861139440475696,
+            0.,
+            0.,
+            0.,
+        ];
+        let c = [
+            0.,
+            0.06,
+            0.09593333333333333,
+            0.1439,
+            0.4973,
+            0.9725,
+            0.9995,
+            1.0,
+            1.0,
+            0.47649808362457746,
+            0.16804309409718698,
+            0.5809869983278046,
+        ];
+        let bi = [
+            [
+                0.,
+                1.0,
+                -6.613231481101632,
+                18.76103101393892,
+                -26.387816724601652,
+                18.151803791357338,
+                -4.8773970209094015,
+            ],
+            [0., 0., 0., 0., 0., 0., 0.],
+            [0., 0., 0., 0., 0., 0., 0.],
+            [
+                0.,
+                0.,
+                13.581261744845222,
+                -58.24933380687017,
+                102.22585776191772,
+                -82.47918641478095,
+                25.17966317045153,
+            ],
+            [
+                0.,
+                0.,
+                -7.832443738621491,
+                59.58202263996186,
+                -159.10856001180372,
+                173.3264506094803,
+                -65.5465323800496,
+            ],
+            [
+                0.,
+                0.,
+                -0.4400882192733145,
+                38.37497909134208,
+                -95.41177628491809,
+                103.89134142215094,
+                -42.009059539632304,
+            ],
+            [
+                0.,
+                0.,
+                -183.30740607966226,
+                -109.43006433845893,
+                0.016443195610187533,
+                2.588473649294409,
+                113.64943454891792,
+            ],
+            [
+                0.,
+                0.,
+                182.66498363236582,
+                81.04797180659286,
+                69.11510831653324,
+                -77.84951764218115,
+                -82.61441271189567,
+            ],
+            [
+                0.,
+                0.,
+                0.311743815944603,
+                -3.15748374471779,
+                8.652701912997008,
+                -10.079927768642202,
+                4.272965784418381,
+            ],
+            [
+                0.,
+                0.,
+                -1.590928663418358,
+                12.47242630433608,
+                -1.4942986027556364,
+                -28.064968058239796,
+                18.67776902007771,
+            ],
+            [
+                0.,
+                0.,
+                -4.8226611141200415,
+                24.541266043649266,
+                -49.98622060360398,
+                45.63928766464175,
+                -15.371671990566991,
+            ],
+            [
+                0.,
+                0.,
+                8.04877010304144,
+                -63.942815009774165,
+                152.37856104062493,
+                -145.1237572530806,
+                48.6392411191884,
+            ],
+        ];
+        ButcherTableu {
+            order: 6,
+            order_embedded: 5,
+            order_interpolant: 6,
+            a: a.map(|row| row.map(|x| T::from_f64(x).unwrap())),
+            b: b.map(|x| T::from_f64(x).unwrap()),
+            b2: b2.map(|x| T::from_f64(x).unwrap()),
+            b3: None,
+            c: c.map(|x| T::from_f64(x).unwrap()),
+            bi: bi.map(|row| row.map(|x| T::from_f64(x).unwrap())),
+            rosenbrock: None,
+        }
+    }
+}
+
+impl<T: RealField + Copy> ButcherTableu<T, 16, 8> {
+    /// Verner's "most efficient" 7(6) pair of order 7 with the embedded method of order 6, and the
+    /// dense output of order 7 with as many stages as Verner's interpolant, which is solved as for
+    /// [ButcherTableu::vern6]. The stage 11 is the derivative at the end of the step, which is
+    /// evaluated with the last five stages only for the dense output.
+    pub fn vern7() -> Self {
+        let mut a = [[0.; 16]; 16];
+        a[1][0..1].copy_from_slice(&[0.005]);
+        a[2][0..2].copy_from_slice(&[-1.07679012345679, 1.185679012345679]);
+        a[3][0..3].copy_from_slice(&[0.04083333333333333, 0., 0.1225]);
+        a[4][0..4].copy_from_slice(&[
+            0.6389139236255726,
+            0.,
+            -2.455672638223657,
+            2.272258714598084,
+        ]);
+        a[5][0..5].copy_from_slice(&[
+            -2.6615773750187572,
+            0.,
+            10.804513886456137,
+            -8.3539146573962,
+            0.820487594956657,
+        ]);
+        a[6][0..6].copy_from_slice(&[
+            6.067741434696771,
+            0.,
+            -24.711273635911084,
+            20.427517930788895,
+            -1.9061579788166472,
+            1.006172249242068,
+        ]);
+        a[7][0..7].copy_from_slice(&[
+            12.054670076253203,
+            0.,
+            -49.75478495046899,
+            41.142888638604674,
+            -4.461760149974004,
+            2.042334822239175,
+            -0.09834843665406108,
+        ]);
+        a[8][0..8].copy_from_slice(&[
+            10.138146522881808,
+            0.,
+            -42.6411360317175,
+            35.76384003992257,
+            -4.3480228403929075,
+            2.0098622683770357,
+            0.3487490460338272,
+            -0.27143900510483127,
+        ]);
+        a[9][0..9].copy_from_slice(&[
+            -45.030072034298676,
+            0.,
+            187.3272437654589,
+            -154.02882369350186,
+            18.56465306347536,
+            -7.141809679295079,
+            1.3088085781613785,
+            0.,
+            0.,
+        ]);
+        a[10][0..10].copy_from_slice(&[
+            0.04715561848627222,
+            0.,
+            0.,
+            0.25750564298434153,
+            0.2621665397741262,
+            0.15216092656738558,
+            0.49399691700324844,
+            -0.29430311714032503,
+            0.0813174723249511,
+            0.,
+        ]);
+        a[11][0..11].copy_from_slice(&[
+            0.09027256046880243,
+            0.,
+            -0.06329172895398037,
+            0.2601318950502367,
+            0.23670970749013523,
+            0.03879469366169059,
+            -0.05526373586411954,
+            -0.035897120970999376,
+            -0.010773035012854183,
+            -0.02066437019422079,
+            0.08343271638873367,
+        ]);
+        a[12][0..12].copy_from_slice(&[
+            0.011722877551867234,
+            0.,
+            0.34478907989943464,
+            -0.13456398118684468,
+            0.02804802407716613,
+            0.014442053477728027,
+            -0.06670668447313757,
+            -0.03269444735902321,
+            0.015343419174787494,
+            -0.01646302870436485,
+            0.05712623876114497,
+            0.07775675270734338,
+        ]);
+        a[13][0..13].copy_from_slice(&[
+            0.05510846599377403,
+            0.,
+            0.02785029369180221,
+            0.1558863986794466,
+            0.3337637291636759,
+            0.10075180014805736,
+            0.176327989118626,
+            -0.05948791431857046,
+            -0.011902273569506239,
+            0.0035338878482462843,
+            -0.027001961295726244,
+            -0.08494418363735137,
+            0.0924316962273531,
+        ]);
+        a[14][0..14].copy_from_slice(&[
+            0.008218313439652802,
+            0.,
+            0.0425266757182586,
+            0.2300477068178079,
+            0.21365891213723912,
+            0.32196381676982677,
+            0.34422193578408294,
+            0.06305110224410845,
+            -0.06301781802291533,
+            0.03525040669237223,
+            -0.08312489072270454,
+            -0.19070479636589027,
+            0.12582289129400775,
+            -0.10131067283362438,
+        ]);
+        a[15][0..15].copy_from_slice(&[
+            -0.09028158934551922,
+            0.,
+            0.377716797409211,
+            0.06671250118097242,
+            0.00043910667888083873,
+            0.06015416363537079,
+            -0.06833216738539231,
+            0.12776348189047798,
+            -0.05349260128896261,
+            0.011445840494446396,
+            -0.04423063171317638,
+            0.010918165172521423,
+            -0.09323169265993989,
+            -0.07028716647270809,
+            0.06329176546979405,
+        ]);
+        let b = [
+            0.04715561848627222,
+            0.,
+            0.,
+            0.25750564298434153,
+            0.2621665397741262,
+            0.15216092656738558,
+            0.49399691700324844,
+            -0.29430311714032503,
+            0.0813174723249511,
+            0.,
+            0.,
+            0.,
+            0.,
+            0.,
+            0.,
+            0.,
+        ];
+        let b2 = [
+            0.044608606606341174,
+            0.,
+            0.,
+            0.26716403785713727,
+            0.2201018300177293,
+            0.2188431703143157,
+            0.22898717054112028,
+            0.,
+            0.,
+            0.020295184663356284,
+            0.,
+            0.,
+            0.,
+            0.,
+            0.,
+            0.,
+        ];
+        let c = [
+            0.,
+            0.005,
+            0.10888888888888888,
+            0.16333333333333333,
+            0.4555,
+            0.6095094489978381,
+            0.884,
+            0.925,
+            1.0,
+            1.0,
+            1.0,
+            0.5234515820634243,
+            0.2988003039261016,
+            0.7623179280498272,
+            0.9466035829522221,
+            0.29858597306597645,
+        ];
+        let bi = [
+            [
+                0.,
+                1.0,
+                -7.413971765444103,
+                27.01496714812702,
+                -53.46158973557812,
+                58.72834242289743,
+                -33.73183607494143,
+                7.911243623425477,
+            ],
+            [0., 0., 0., 0., 0., 0., 0., 0.],
+            [
+                0.,
+                0.,
+                3.509853448861827e-11,
+                -3.8804387656554755e-10,
+                1.288775472369988e-9,
+                -1.843221343836797e-9,
+                1.17423736823186e-9,
+                -2.668461546881216e-10,
+            ],
+            [
+                0.,
+                0.,
+                18.400063799490646,
+                -105.13523849438901,
+                253.89897816250783,
+                -311.8272270771889,
+                192.30069414787744,
+                -47.37976489531363,
+            ],
+            [
+                0.,
+                0.,
+                3.5894436168379134,
+                -24.753484706351113,
+                74.6640453212915,
+                -115.0248771757829,
+                88.95950490732535,
+                -27.172465423546623,
+            ],
+            [
+                0.,
+                0.,
+                3.14625264157668,
+                -21.708774098132167,
+                61.34071041909508,
+                -89.67896642774383,
+                67.50476126882201,
+                -20.451822877050386,
+            ],
+            [
+                0.,
+                0.,
+                5.101175059081416,
+                -7.053294987597847,
+                -29.21518184891887,
+                57.417026803620544,
+                -21.023224986442962,
+                -4.732503122739032,
+            ],
+            [
+                0.,
+                0.,
+                -2.347547170455026,
+                -4.113418935112144,
+                46.674191352997774,
+                -78.59788275039914,
+                43.30448121452248,
+                -5.214126828694271,
+            ],
+            [
+                0.,
+                0.,
+                0.20763721711222943,
+                8.52388077869075,
+                -46.020333392871805,
+                83.40934006236212,
+                -63.32216684014895,
+                17.282959647180615,
+            ],
+            [
+                0.,
+                0.,
+                -0.014929437992602943,
+                0.6549006784015948,
+                -3.936764161581488,
+                8.58471604647425,
+                -7.904095131847187,
+                2.6161720065454332,
+            ],
+            [
+                0.,
+                0.,
+                -1.8450880103684257,
+                12.66459970213193,
+                -39.151181297762705,
+                65.28774770190478,
+                -55.55491026721226,
+                18.598832171306675,
+            ],
+            [
+                0.,
+                0.,
+                3.683152518170213,
+                -36.34672788234588,
+                126.35725485109776,
+                -189.63288575499382,
+                127.16515589523337,
+                -31.22594962716164,
+            ],
+            [
+                0.,
+                0.,
+                -9.161384842535634,
+                65.39800533319693,
+                -180.0862895605009,
+                239.4708843658458,
+                -154.46799717031234,
+                38.846781874306146,
+            ],
+            [
+                0.,
+                0.,
+                -8.147095243958239,
+                57.674102877523715,
+                -178.9386080054736,
+                288.89112470323363,
+                -230.92736068036584,
+                71.44783634904036,
+            ],
+            [
+                0.,
+                0.,
+                2.821726897484674,
+                -35.062715052416785,
+                142.83821179363258,
+                -254.28741270087374,
+                206.20241574308935,
+                -62.512226680916086,
+            ],
+            [
+                0.,
+                0.,
+                -8.01943527903484,
+                62.24319763866105,
+                -174.96344389922382,
+                237.26006978248705,
+                -158.5054220267733,
+                41.985033783883836,
+            ],
+        ];
+        ButcherTableu {
+            order: 7,
+            order_embedded: 6,
+            order_interpolant: 7,
+            a: a.map(|row| row.map(|x| T::from_f64(x).unwrap())),
+            b: b.map(|x| T::from_f64(x).unwrap()),
+            b2: b2.map(|x| T::from_f64(x).unwrap()),
+            b3: None,
+            c: c.map(|x| T::from_f64(x).unwrap()),
+            bi: bi.map(|row| row.map(|x| T::from_f64(x).unwrap())),
+            rosenbrock: None,
+        }
+    }
+}
+
+impl<T: RealField + Copy> ButcherTableu<T, 21, 9> {
+    /// Verner's "most efficient" 8(7) pair of order 8 with the embedded method of order 7, and the
+    /// dense output of order 8 with as many stages as Verner's interpolant. The stage 14 is the
+    /// derivative at the end of the step, which is evaluated with the last seven stages only for
+    /// the dense output.
+    ///
+    /// The first two appended stages have the stage order 6, and the other five the stage order 7.
+    /// The first and the third of them are at roots of polynomials, where the conditions of their
+    /// stage orders are solvable, and the others are free. Together with the first stage, the end
+    /// of the step and `b`, the stages of order 7 determine `bi` uniquely. The coefficients of the
+    /// dense output reach 8e3, so that the rows of `bi` sum to `b` only up to their roundoff, and
+    /// the end of the step is evaluated with `b`, see [ButcherTableu::dense_output].
+    pub fn vern8() -> Self {
+        let mut a = [[0.; 21]; 21];
+        a[1][0..1].copy_from_slice(&[0.05]);
+        a[2][0..2].copy_from_slice(&[-0.0069931640625, 0.1135556640625]);
+        a[3][0..3].copy_from_slice(&[0.0399609375, 0., 0.1198828125]);
+        a[4][0..4].copy_from_slice(&[
+            0.36139756280045754,
+            0.,
+            -1.3415240667004928,
+            1.3701265039000352,
+        ]);
+        a[5][0..5].copy_from_slice(&[
+            0.049047202797202795,
+            0.,
+            0.,
+            0.23509720422144048,
+            0.18085559298135673,
+        ]);
+        a[6][0..6].copy_from_slice(&[
+            0.06169289044289044,
+            0.,
+            0.,
+            0.11236568314640277,
+            -0.03885046071451367,
+            0.01979188712522046,
+        ]);
+        a[7][0..7].copy_from_slice(&[
+            -1.767630240222327,
+            0.,
+            0.,
+            -62.5,
+            -6.061889377376669,
+            5.6508231982227635,
+            65.62169641937624,
+        ]);
+        a[8][0..8].copy_from_slice(&[
+            -1.1809450665549708,
+            0.,
+            0.,
+            -41.50473441114321,
+            -4.434438319103725,
+            4.260408188586133,
+            43.75364022446172,
+            0.00787142548991231,
+        ]);
+        a[9][0..9].copy_from_slice(&[
+            -1.2814059994414884,
+            0.,
+            0.,
+            -45.047139960139866,
+            -4.731362069449576,
+            4.514967016593808,
+            47.44909557172985,
+            0.01059228297111661,
+            -0.0057468422638446166,
+        ]);
+        a[10][0..10].copy_from_slice(&[
+            -1.7244701342624853,
+            0.,
+            0.,
+            -60.92349008483054,
+            -5.95151837622239,
+            5.556523730698456,
+            63.98301198033305,
+            0.014642028250414961,
+            0.06460408772358203,
+            -0.0793032316900888,
+        ]);
+        a[11][0..11].copy_from_slice(&[
+            -3.301622667747079,
+            0.,
+            0.,
+            -118.01127235975251,
+            -10.141422388456112,
+            9.139311332232058,
+            123.37594282840426,
+            4.62324437887459,
+            -3.3832777380682018,
+            4.527592100324618,
+            -5.828495485811623,
+        ]);
+        a[12][0..12].copy_from_slice(&[
+            -3.039515033766309,
+            0.,
+            0.,
+            -109.26086808941763,
+            -9.290642497400293,
+            8.43050498176491,
+            114.20100103783314,
+            -0.9637271342145479,
+            -5.034884088802548,
+            5.958130824003279,
+            0.,
+            0.,
+        ]);
+        a[13][0..13].copy_from_slice(&[
+            0.04427989419007951,
+            0.,
+            0.,
+            0.,
+            0.,
+            0.3541049391724449,
+            0.24796921549564377,
+            -15.694202038838085,
+            25.084064965558564,
+            -31.738367786260277,
+            22.938283273988784,
+            -0.2361324633071542,
+            0.,
+        ]);
+        a[14][0..14].copy_from_slice(&[
+            0.045278786152944256,
+            0.,
+            0.,
+            0.,
+            0.,
+            0.0373164551303897,
+            0.23659138139561436,
+            0.08618313260230921,
+            -0.07129465745317033,
+            -0.051346524869003866,
+            0.07083361634070767,
+            -0.12823897488097558,
+            0.09828958464625492,
+            -0.01259503554776971,
+        ]);
+        a[15][0..15].copy_from_slice(&[
+            0.04366719279237607,
+            0.,
+            0.,
+            0.,
+            0.,
+            0.3611954227593101,
+            0.2523967271094008,
+            -0.2650738700768896,
+            0.2574819651658175,
+            0.1717192011682124,
+            -0.2246743698509045,
+            0.2760933950425929,
+            -0.1888688263255674,
+            0.02695307456570982,
+            -0.010889912350058187,
+        ]);
+        a[16][0..16].copy_from_slice(&[
+            0.04644464468671886,
+            0.,
+            0.,
+            0.,
+            0.,
+            0.193662678486539,
+            0.23024759373571568,
+            0.02949598964386659,
+            0.3947865139538115,
+            -0.2654441051807201,
+            -0.1586007424415817,
+            0.019071429792681026,
+            0.011090498930061782,
+            0.002633480207317276,
+            0.06605917831085269,
+            -0.06402419026406875,
+        ]);
+        a[17][0..17].copy_from_slice(&[
+            0.033792886939936075,
+            0.,
+            0.,
+            0.,
+            0.,
+            0.031103336660261084,
+            0.034737267135778606,
+            0.11366279216776277,
+            0.2963504276501469,
+            -0.3192009330667073,
+            -0.08126197796476245,
+            0.003225525428217828,
+            -0.004058641213318652,
+            -0.0006962244870371298,
+            -0.037363863850508716,
+            -0.015856730952565568,
+            -0.004433864447203476,
+        ]);
+        a[18][0..18].copy_from_slice(&[
+            0.02517045533305289,
+            0.,
+            0.,
+            0.,
+            0.,
+            0.08281460627843962,
+            0.0997898325320117,
+            -0.052041882722526266,
+            0.030550170907654793,
+            0.05065332568772257,
+            -0.034706114886818434,
+            0.008058899327467358,
+            0.008208909761085901,
+            0.001730234119381487,
+            -0.06528639400014531,
+            -0.02208356453209558,
+            -0.038892302712788145,
+            0.05603382490755741,
+        ]);
+        a[19][0..19].copy_from_slice(&[
+            0.030819388247898597,
+            0.,
+            0.,
+            0.,
+            0.,
+            0.03308542784390641,
+            0.03982742807023171,
+            -0.01885726434258579,
+            0.01634138981184101,
+            0.015325788264314355,
+            -0.014856108924407047,
+            0.0032225128933257297,
+            0.00317586470120416,
+            0.0006329116166023102,
+            0.03985461551517693,
+            -0.007995805610629503,
+            -0.02149462799802694,
+            0.03582284798594465,
+            0.14509563192520342,
+        ]);
+        a[20][0..20].copy_from_slice(&[
+            0.02050963465197442,
+            0.,
+            0.,
+            0.,
+            0.,
+            0.03805473145659767,
+            0.04578069069704157,
+            -0.020296842121978662,
+            0.021774195376583153,
+            0.014091508870454716,
+            -0.017800767537778536,
+            0.003708600270496876,
+            0.0035782001237505885,
+            0.0007824733322064159,
+            0.07000852711705843,
+            -0.01114290639375667,
+            -0.008197327773664005,
+            0.06298670623250334,
+            0.10174756029748376,
+            0.07441501540102695,
+        ]);
+        let b = [
+            0.04427989419007951,
+            0.,
+            0.,
+            0.,
+            0.,
+            0.3541049391724449,
+            0.24796921549564377,
+            -15.694202038838085,
+            25.084064965558564,
+            -31.738367786260277,
+            22.938283273988784,
+            -0.2361324633071542,
+            0.,
+            0.,
+            0.,
+            0.,
+            0.,
+            0.,
+            0.,
+            0.,
+            0.,
+        ];
+        let b2 = [
+            0.04431261522961032,
+            0.,
+            0.,
+            0.,
+            0.,
+            0.3546095642355314,
+            0.24784804313558945,
+            4.448134732474648,
+            19.846886366059618,
+            -23.58162337740292,
+            0.,
+            0.,
+            -0.3601679437320754,
+            0.,
+            0.,
+            0.,
+            0.,
+            0.,
+            0.,
+            0.,
+            0.,
+        ];
+        let c = [
+            0.,
+            0.05,
+            0.1065625,
+            0.15984375,
+            0.39,
+            0.465,
+            0.155,
+            0.943,
+            0.9018020417358569,
+            0.909,
+            0.94,
+            1.0,
+            1.0,
+            1.0,
+            0.31101776351730065,
+            0.7,
+            0.5054229698611938,
+            0.05,
+            0.15,
+            0.3,
+            0.4,
+        ];
+        let bi = [
+            [
+                0.,
+                1.0,
+                -18.340733917773257,
+                146.22714496111442,
+                -606.4885531414461,
+                1404.5868040475655,
+                -1819.1640342464557,
+                1222.784786769371,
+                -330.5611345781858,
+            ],
+            [0., 0., 0., 0., 0., 0., 0., 0., 0.],
+            [0., 0., 0., 0., 0., 0., 0., 0., 0.],
+            [0., 0., 0., 0., 0., 0., 0., 0., 0.],
+            [0., 0., 0., 0., 0., 0., 0., 0., 0.],
+            [
+                0.,
+                0.,
+                -0.05431940915383583,
+                1.2847822523212769,
+                -10.755333766757074,
+                42.36025497732612,
+                -84.6969646263488,
+                82.06934409090404,
+                -29.853658579119283,
+            ],
+            [
+                0.,
+                0.,
+                -0.03803827548294192,
+                0.8996950092652537,
+                -7.531642113810617,
+                29.663633630782883,
+                -59.31077917502874,
+                57.47073423995272,
+                -20.90563410018291,
+            ],
+            [
+                0.,
+                0.,
+                2.4074778130734016,
+                -56.94253330085967,
+                476.68462875363457,
+                -1877.438938883736,
+                3753.83433040175,
+                -3637.376175880939,
+                1323.1370090582388,
+            ],
+            [
+                0.,
+                0.,
+                -3.847875138690719,
+                91.01133022176583,
+                -761.8857055743725,
+                3000.713269059264,
+                -5999.758634996227,
+                5813.62340458906,
+                -2114.771723195241,
+            ],
+            [
+                0.,
+                0.,
+                4.868639772759466,
+                -115.15482340546858,
+                963.9988083484428,
+                -3796.7427323996712,
+                7591.375100183648,
+                -7355.861900503022,
+                2675.7785402170516,
+            ],
+            [
+                0.,
+                0.,
+                -3.5187139756357433,
+                83.22589169815208,
+                -696.7112453080967,
+                2744.021397382297,
+                -5486.517570042568,
+                5316.305020768183,
+                -1933.8664972483425,
+            ],
+            [
+                0.,
+                0.,
+                0.036222527602780726,
+                -0.856748283586792,
+                7.172120975506603,
+                -28.24764714736543,
+                56.47959324682114,
+                -54.727382408548415,
+                19.907708626262963,
+            ],
+            [0., 0., 0., 0., 0., 0., 0., 0., 0.],
+            [
+                0.,
+                0.,
+                0.014176307629475868,
+                -0.33620710225333544,
+                2.8303080767431723,
+                -11.251284285771051,
+                22.822034098590784,
+                -22.61546998150248,
+                8.536442886563433,
+            ],
+            [0., 0., 0., 0., 0., 0., 0., 0., 0.],
+            [0., 0., 0., 0., 0., 0., 0., 0., 0.],
+            [
+                0.,
+                0.,
+                1.3708234950826532,
+                -31.745831096138243,
+                254.40615712943156,
+                -935.0096796235985,
+                1698.0855272710196,
+                -1458.2624296736974,
+                471.1554324979003,
+            ],
+            [
+                0.,
+                0.,
+                25.57033137108286,
+                -284.3786890991308,
+                1343.2426819845587,
+                -3323.850290475622,
+                4473.368824148709,
+                -3079.686047536366,
+                845.7331896067685,
+            ],
+            [
+                0.,
+                0.,
+                -10.856554375743428,
+                217.12733393042794,
+                -1292.6973593339337,
+                3614.270290560792,
+                -5227.590252423606,
+                3762.6617271015766,
+                -1062.9151854595134,
+            ],
+            [
+                0.,
+                0.,
+                7.357813312614127,
+                -163.6152559196639,
+                1205.7795985504108,
+                -3932.955173345724,
+                6334.394689352513,
+                -4919.112853139241,
+                1468.1511811890914,
+            ],
+            [
+                0.,
+                0.,
+                -4.96924950736484,
+                113.25391013405451,
+                -878.0444645803112,
+                3069.880096503461,
+                -5253.321863192817,
+                4272.727241564271,
+                -1319.525670921294,
+            ],
+        ];
+        ButcherTableu {
+            order: 8,
+            order_embedded: 7,
+            order_interpolant: 8,
+            a: a.map(|row| row.map(|x| T::from_f64(x).unwrap())),
+            b: b.map(|x| T::from_f64(x).unwrap()),
+            b2: b2.map(|x| T::from_f64(x).unwrap()),
+            b3: None,
+            c: c.map(|x| T::from_f64(x).unwrap()),
+            bi: bi.map(|row| row.map(|x| T::from_f64(x).unwrap())),
+            rosenbrock: None,
+        }
+    }
+}
+
+impl<T: RealField + Copy> ButcherTableu<T, 26, 10> {
+    /// Verner's "most efficient" 9(8) pair of order 9 with the embedded method of order 8, and the
+    /// dense output of order 9 with as many stages as Verner's interpolant, which is solved as for
+    /// [ButcherTableu::vern8]. The stage 17 is the derivative at the end of the step, which is
+    /// evaluated with the last nine stages only for the dense output.
+    ///
+    /// The first three appended stages have the stage order 7, and the other six the stage order 8,
+    /// with the first and the fourth of them at roots of polynomials. The coefficients of the dense
+    /// output reach 7e3, as for [ButcherTableu::vern8].
+    pub fn vern9() -> Self {
+        let mut a = [[0.; 26]; 26];
+        a[1][0..1].copy_from_slice(&[0.03462]);
+        a[2][0..2].copy_from_slice(&[-0.0389335438857287, 0.1359578945245091]);
+        a[3][0..3].copy_from_slice(&[0.03638413148954267, 0., 0.10915239446862801]);
+        a[4][0..4].copy_from_slice(&[
+            2.0257639143939694,
+            0.,
+            -7.638023836496291,
+            6.173259922102322,
+        ]);
+        a[5][0..5].copy_from_slice(&[
+            0.05112275589406061,
+            0.,
+            0.,
+            0.17708237945550218,
+            0.0008027762409222536,
+        ]);
+        a[6][0..6].copy_from_slice(&[
+            0.13160063579752163,
+            0.,
+            0.,
+            -0.2957276252669636,
+            0.08781378035642955,
+            0.6213052975225274,
+        ]);
+        a[7][0..7].copy_from_slice(&[
+            0.07166666666666667,
+            0.,
+            0.,
+            0.,
+            0.,
+            0.33055335789153195,
+            0.2427799754418014,
+        ]);
+        a[8][0..8].copy_from_slice(&[
+            0.071806640625,
+            0.,
+            0.,
+            0.,
+            0.,
+            0.3294380283228177,
+            0.1165190029271823,
+            -0.034013671875,
+        ]);
+        a[9][0..9].copy_from_slice(&[
+            0.04836757646340646,
+            0.,
+            0.,
+            0.,
+            0.,
+            0.03928989925676164,
+            0.10547409458903446,
+            -0.021438652846483126,
+            -0.10412291746271944,
+        ]);
+        a[10][0..10].copy_from_slice(&[
+            -0.026645614872014785,
+            0.,
+            0.,
+            0.,
+            0.,
+            0.03333333333333333,
+            -0.1631072244872467,
+            0.03396081684127761,
+            0.1572319413814626,
+            0.21522674780318796,
+        ]);
+        a[11][0..11].copy_from_slice(&[
+            0.03689009248708622,
+            0.,
+            0.,
+            0.,
+            0.,
+            -0.1465181576725543,
+            0.2242577768172024,
+            0.02294405717066073,
+            -0.0035850052905728597,
+            0.08669223316444385,
+            0.43838406519683376,
+        ]);
+        a[12][0..12].copy_from_slice(&[
+            -0.4866012215113341,
+            0.,
+            0.,
+            0.,
+            0.,
+            -6.304602650282853,
+            -0.2812456182894729,
+            -2.679019236219849,
+            0.5188156639241577,
+            1.3653531876033418,
+            5.8850910885039465,
+            2.8028087862720628,
+        ]);
+        a[13][0..13].copy_from_slice(&[
+            0.4185367457753472,
+            0.,
+            0.,
+            0.,
+            0.,
+            6.724547581906459,
+            -0.42544428016461133,
+            3.3432791530012653,
+            0.6170816631175374,
+            -0.9299661239399329,
+            -6.099948804751011,
+            -3.002206187889399,
+            0.2553202529443446,
+        ]);
+        a[14][0..14].copy_from_slice(&[
+            -0.7793740861228848,
+            0.,
+            0.,
+            0.,
+            0.,
+            -13.937342538107776,
+            1.2520488533793563,
+            -14.691500408016868,
+            -0.494705058533141,
+            2.2429749091462368,
+            13.36789380382865,
+            14.396650486650678,
+            -0.7975813331776801,
+            0.4409353709534278,
+        ]);
+        a[15][0..15].copy_from_slice(&[
+            2.0580513374668867,
+            0.,
+            0.,
+            0.,
+            0.,
+            22.357937727968032,
+            0.9094981099755646,
+            35.89110098240264,
+            -3.442515027624454,
+            -4.865481358036368,
+            -18.909803813543427,
+            -34.26354448030452,
+            1.2647565216956427,
+            0.,
+            0.,
+        ]);
+        a[16][0..16].copy_from_slice(&[
+            0.014611976858423152,
+            0.,
+            0.,
+            0.,
+            0.,
+            0.,
+            0.,
+            -0.3915211862331339,
+            0.23109325002895065,
+            0.12747667699928525,
+            0.2246434176204158,
+            0.5684352689748513,
+            0.05825871557215827,
+            0.13643174034822156,
+            0.030570139830827976,
+            0.,
+        ]);
+        a[17][0..17].copy_from_slice(&[
+            0.016933098418773586,
+            0.,
+            0.,
+            0.,
+            0.,
+            0.,
+            0.,
+            0.08440174485754236,
+            0.20122124876429234,
+            0.12212858870379588,
+            0.2334846827063247,
+            0.07404762654919765,
+            0.003645357936669788,
+            0.0065108799913188445,
+            -0.014732174945151088,
+            -0.014404221621819339,
+            0.027181715702087283,
+        ]);
+        a[18][0..18].copy_from_slice(&[
+            0.018709388848273473,
+            0.,
+            0.,
+            0.,
+            0.,
+            0.,
+            0.,
+            0.08245262778988392,
+            0.19232865644831396,
+            0.11819797566809966,
+            0.23891737059010845,
+            0.07461194199473375,
+            0.04543736259233232,
+            0.055171923117223326,
+            0.005930299762873187,
+            -0.004212389735758518,
+            -0.00895930864844147,
+            0.06941415157235795,
+        ]);
+        a[19][0..19].copy_from_slice(&[
+            0.017377432752907623,
+            0.,
+            0.,
+            0.,
+            0.,
+            0.,
+            0.,
+            0.08220715659542818,
+            0.19865885625932744,
+            0.12113508991669933,
+            0.2349127632027767,
+            0.07195102435509694,
+            0.0005584096311713569,
+            0.002175935802158853,
+            -0.015566875497116118,
+            -0.014435133773648813,
+            0.027461930439116076,
+            -0.03996473270222309,
+            0.009528143018305478,
+        ]);
+        a[20][0..20].copy_from_slice(&[
+            0.008201809940438448,
+            0.,
+            0.,
+            0.,
+            0.,
+            0.,
+            0.,
+            -0.07680422678923428,
+            -0.03133681506391061,
+            0.14550494737795236,
+            0.11598505716859998,
+            -0.0752642852443568,
+            -0.11708801126748913,
+            -0.19578934763449024,
+            -0.05366863254995464,
+            -0.0069101145780929955,
+            0.05904965865752114,
+            -0.16385155965791992,
+            0.28738374325502625,
+            0.35483377641923286,
+        ]);
+        a[21][0..21].copy_from_slice(&[
+            0.025337150346767943,
+            0.,
+            0.,
+            0.,
+            0.,
+            0.,
+            0.,
+            -0.0057437450810744585,
+            0.008121381491364411,
+            0.028722075395619155,
+            0.030943904093253892,
+            -0.005758169696198063,
+            -0.010072245325079858,
+            -0.021392490869849172,
+            -0.006207658784457583,
+            -0.0004448287582251696,
+            0.00746679942759036,
+            0.018657390561578287,
+            0.024787237225840197,
+            -0.006362373946994521,
+            -0.03805442608013542,
+        ]);
+        a[22][0..22].copy_from_slice(&[
+            -0.0007505669399469877,
+            0.,
+            0.,
+            0.,
+            0.,
+            0.,
+            0.,
+            -0.019847431859578907,
+            -0.024317203374244825,
+            0.009853912400965854,
+            -0.004633424254323007,
+            -0.01917678841127771,
+            -0.02816502498545947,
+            -0.040028466924566124,
+            -0.010439266073391656,
+            -0.0018947364917184571,
+            0.010449417381709786,
+            -0.09189021060793853,
+            0.06930031012998993,
+            0.14421027598128006,
+            0.04134168348182488,
+            0.11598752054667516,
+        ]);
+        a[23][0..23].copy_from_slice(&[
+            0.005507852539726653,
+            0.,
+            0.,
+            0.,
+            0.,
+            0.,
+            0.,
+            0.07729322076249308,
+            0.1912615486816462,
+            0.12685550766057643,
+            0.22410662258217307,
+            0.07302858587170477,
+            0.09720640623932017,
+            0.0929347455113796,
+            0.020224613374296246,
+            0.008027050907502756,
+            -0.02934529105851487,
+            -0.09842972349934673,
+            -0.1334837206215869,
+            0.07300154718460565,
+            0.03574913564220566,
+            0.02534219168585813,
+            -0.03928029346403988,
+        ]);
+        a[24][0..24].copy_from_slice(&[
+            -0.005845583368644493,
+            0.,
+            0.,
+            0.,
+            0.,
+            0.,
+            0.,
+            0.07212096540648912,
+            0.1776255333209483,
+            0.11696366392039835,
+            0.20735102169206196,
+            0.06813382082453935,
+            0.09079346502940763,
+            0.087229464623202,
+            0.019039421891165656,
+            0.007483525508971617,
+            -0.031478424272480376,
+            0.010414386585497626,
+            -0.046889012129691696,
+            0.05512674212785406,
+            0.08277244729798933,
+            0.06193404491896451,
+            -0.07679807031892244,
+            0.0040225869422495465,
+        ]);
+        a[25][0..25].copy_from_slice(&[
+            -0.0013701308999566318,
+            0.,
+            0.,
+            0.,
+            0.,
+            0.,
+            0.,
+            0.07980817778504005,
+            0.1817994741356406,
+            0.10416130701934935,
+            0.19794289504065107,
+            0.07565982270645444,
+            0.1023864022214474,
+            0.10616481577410244,
+            0.024195834094191498,
+            0.008182475960066318,
+            -0.030143215270591286,
+            -0.004321979712319116,
+            -0.009473489339431714,
+            0.05145950826293043,
+            0.07604085906670914,
+            0.05878610766196229,
+            -0.05286286704313926,
+            -0.012285257170686145,
+            -0.006130740292420943,
+        ]);
+        let b = [
+            0.014611976858423152,
+            0.,
+            0.,
+            0.,
+            0.,
+            0.,
+            0.,
+            -0.3915211862331339,
+            0.23109325002895065,
+            0.12747667699928525,
+            0.2246434176204158,
+            0.5684352689748513,
+            0.05825871557215827,
+            0.13643174034822156,
+            0.030570139830827976,
+            0.,
+            0.,
+            0.,
+            0.,
+            0.,
+            0.,
+            0.,
+            0.,
+            0.,
+            0.,
+            0.,
+        ];
+        let b2 = [
+            0.01996996514886773,
+            0.,
+            0.,
+            0.,
+            0.,
+            0.,
+            0.,
+            2.19149930494933,
+            0.08857071848208438,
+            0.11405602348659671,
+            0.2533163805345107,
+            -2.056564386240941,
+            0.3408096799013119,
+            0.,
+            0.,
+            0.04834231373823958,
+            0.,
+            0.,
+            0.,
+            0.,
+            0.,
+            0.,
+            0.,
+            0.,
+            0.,
+            0.,
+        ];
+        let c = [
+            0.,
+            0.03462,
+            0.09702435063878045,
+            0.14553652595817068,
+            0.561,
+            0.22900791159048503,
+            0.544992088409515,
+            0.645,
+            0.48375,
+            0.06757,
+            0.25,
+            0.6590650618730999,
+            0.8206,
+            0.9012,
+            1.0,
+            1.0,
+            1.0,
+            0.740418547063032,
+            0.888,
+            0.696,
+            0.2502460000333224,
+            0.05,
+            0.15,
+            0.75,
+            0.9,
+            0.95,
+        ];
+        let bi = [
+            [
+                0.,
+                1.0,
+                -18.622917903840317,
+                152.89456698298568,
+                -663.1580127165751,
+                1647.6084096680827,
+                -2426.31294111398,
+                2095.39874361184,
+                -981.3777077505023,
+                192.5844711988475,
+            ],
+            [0., 0., 0., 0., 0., 0., 0., 0., 0., 0.],
+            [0., 0., 0., 0., 0., 0., 0., 0., 0., 0.],
+            [0., 0., 0., 0., 0., 0., 0., 0., 0., 0.],
+            [0., 0., 0., 0., 0., 0., 0., 0., 0., 0.],
+            [0., 0., 0., 0., 0., 0., 0., 0., 0., 0.],
+            [0., 0., 0., 0., 0., 0., 0., 0., 0., 0.],
+            [
+                0.,
+                0.,
+                1.9946742029030975,
+                -46.75491140031549,
+                384.3133285176448,
+                -1475.104843228343,
+                2913.148362241377,
+                -3087.862822341573,
+                1678.1763465896233,
+                -368.30165576754985,
+            ],
+            [
+                0.,
+                0.,
+                -1.1773455958628682,
+                27.596832075138856,
+                -226.8388512285756,
+                870.6726081303627,
+                -1719.4699712824172,
+                1822.5942307906262,
+                -990.5344581421035,
+                217.38804850286033,
+            ],
+            [
+                0.,
+                0.,
+                -0.6494525661095119,
+                15.223086127536561,
+                -125.1298467854848,
+                480.28426112089005,
+                -948.5016032054979,
+                1005.3874617033873,
+                -546.4029830558908,
+                119.9165533381683,
+            ],
+            [
+                0.,
+                0.,
+                -1.1444857794130854,
+                26.826602127563593,
+                -220.50775945647558,
+                846.3720610436843,
+                -1671.4794170726461,
+                1771.7254696658795,
+                -962.8885565570987,
+                211.3207294461265,
+            ],
+            [
+                0.,
+                0.,
+                -2.8959944108133593,
+                67.8817432422426,
+                -557.9704443805905,
+                2141.6506891887852,
+                -4229.493399297066,
+                4483.154924174641,
+                -2436.482766467248,
+                534.7236832190246,
+            ],
+            [
+                0.,
+                0.,
+                -0.29680937107026883,
+                6.957174172593246,
+                -57.18617966045287,
+                219.49696854979499,
+                -433.47917768941875,
+                459.47685136703996,
+                -249.7141965606575,
+                54.80362790774337,
+            ],
+            [
+                0.,
+                0.,
+                -0.6950757264215072,
+                16.292487243304965,
+                -133.92004849920497,
+                514.0235778669572,
+                -1015.1325520292958,
+                1076.0145647885151,
+                -584.7870501726891,
+                128.34052826918227,
+            ],
+            [
+                0.,
+                0.,
+                -0.15574500549123102,
+                3.6506432590273947,
+                -30.00734725160585,
+                115.1768101148569,
+                -227.45985635845045,
+                241.1016353057491,
+                -131.03271899479256,
+                28.757149070537494,
+            ],
+            [0., 0., 0., 0., 0., 0., 0., 0., 0., 0.],
+            [
+                0.,
+                0.,
+                -0.7287510489130604,
+                17.61193913044797,
+                -153.98951888116264,
+                650.6247916220753,
+                -1461.2644494575388,
+                1783.3041508533358,
+                -1116.9369028568415,
+                281.3787406385968,
+            ],
+            [0., 0., 0., 0., 0., 0., 0., 0., 0., 0.],
+            [0., 0., 0., 0., 0., 0., 0., 0., 0., 0.],
+            [0., 0., 0., 0., 0., 0., 0., 0., 0., 0.],
+            [
+                0.,
+                0.,
+                6.68655811501634,
+                -149.246371609173,
+                1113.3353429375643,
+                -3773.459962073848,
+                6746.480766272397,
+                -6635.606027783668,
+                3407.605213207646,
+                -715.795519065935,
+            ],
+            [
+                0.,
+                0.,
+                26.797886545839116,
+                -313.09045158260534,
+                1581.083309908014,
+                -4290.150081489963,
+                6696.8521397007535,
+                -6028.09871544931,
+                2911.782291890908,
+                -585.1763795236366,
+            ],
+            [
+                0.,
+                0.,
+                -12.225781224824157,
+                247.82071718039936,
+                -1527.45481908142,
+                4498.519614616803,
+                -7271.957584841635,
+                6639.971653519082,
+                -3222.218750081986,
+                647.5449499135816,
+            ],
+            [
+                0.,
+                0.,
+                2.4703247977418563,
+                -58.50816659869039,
+                491.28184896120007,
+                -1950.1741746959,
+                4029.2007316833756,
+                -4476.89258440308,
+                2544.227153741584,
+                -581.6051334862309,
+            ],
+            [
+                0.,
+                0.,
+                -2.6653088706001,
+                64.24590310288056,
+                -558.7597192364374,
+                2340.3986191541735,
+                -5188.085349299542,
+                6218.457585908713,
+                -3807.273260876395,
+                933.6815301172078,
+            ],
+            [
+                0.,
+                0.,
+                3.308223841859056,
+                -79.40179345333661,
+                684.9087168535624,
+                -2835.9393495884115,
+                6206.954301749585,
+                -7368.127121711179,
+                4487.858346086444,
+                -1099.5613237785237,
+            ],
+        ];
+        ButcherTableu {
+            order: 9,
+            order_embedded: 8,
+            order_interpolant: 9,
+            a: a.map(|row| row.map(|x| T::from_f64(x).unwrap())),
+            b: b.map(|x| T::from_f64(x).unwrap()),
+            b2: b2.map(|x| T::from_f64(x).unwrap()),
+            b3: None,
+            c: c.map(|x| T::from_f64(x).unwrap()),
+            bi: bi.map(|row| row.map(|x| T::from_f64(x).unwrap())),
+            rosenbrock: None,
+        }
+    }
+}
@@ -0,0 +1,689 @@
+//! Defines [Sensitivity] for forward sensitivity analysis, in which the solution is integrated
+//! together with its derivatives with respect to the initial position and parameters. The
+//! derivatives are computed with [Dual] numbers from the equation generic over the scalar type
+//! (see [DualSensitivityEquation]), or from the Jacobians given by [Linearization].
+
+use std::ops::{Add, AddAssign, Div, Mul, Neg, Sub};
+
+use hlist2::{HList, ops::Append};
+use nalgebra::{RealField, RowSVector, SMatrix, SVector};
+
+use crate::{
+    Dual, Solver, StateFn, StateRef,
+    dual::{DualStateRef, Lift},
+    initial_condition::InitialCondition,
+    loc::{
+        DedupLocF, Locate, LocatorStateFn, detection_method::DetectionMethod,
+        loc_callback::LocCallback, location_method::Bisection,
+    },
+    state::{EvalMutState, EvalState, State, StateHistory},
+};
+
+/// Position of the solution together with its sensitivities, which is used as the position type
+/// of the solver, see [Solver::sensitivity_equation].
+///
+/// It is converted from the position `SVector<T, N>`, so that the initial condition can be given
+/// by the position alone, with `dx0` set to identity and `dp` set to zero, see
+/// [Sensitivity::new].
+#[derive(Clone, Copy, Debug, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct Sensitivity<T: RealField + Copy, const N: usize, const M: usize> {
+    /// Position of the solution
+    pub x: SVector<T, N>,
+    /// Derivative of the position with respect to the initial position
+    pub dx0: SMatrix<T, N, N>,
+    /// Derivative of the position with respect to the parameters
+    pub dp: SMatrix<T, N, M>,
+}
+
+impl<T: RealField + Copy, const N: usize, const M: usize> Sensitivity<T, N, M> {
+    /// Returns the initial position `x` with identity `dx0` and zero `dp`.
+    pub fn new(x: SVector<T, N>) -> Self {
+        Self {
+            x,
+            dx0: SMatrix::identity(),
+            dp: SMatrix::zeros(),
+        }
+    }
+
+    /// Updates the sensitivities at the jump `x -> g(x, p)` made by a state-mutating event at a
+    /// time independent of the position and parameters (e.g. [crate::Periodic]), given the
+    /// Jacobians `g_x` and `g_p` of the jump at the position before it. The position itself is
+    /// not changed.
+    pub fn jump(&mut self, g_x: &SMatrix<T, N, N>, g_p: &SMatrix<T, N, M>) {
+        self.dx0 = g_x * self.dx0;
+        self.dp = g_x * self.dp + g_p;
+    }
+
+    /// Updates the sensitivities at the jump `x -> g(x, p)` made by a state-mutating event
+    /// located as a zero crossing of `h(x)`, accounting for the dependence of the event time on
+    /// the initial position and parameters.
+    ///
+    /// `g_x` and `g_p` are the Jacobians of the jump, and `h_x` is the gradient of `h` at the
+    /// position before the jump, while `f_before` and `f_after` are the derivatives of the
+    /// position before and after the jump. The position itself is not changed.
+    pub fn jump_at_crossing(
+        &mut self,
+        g_x: &SMatrix<T, N, N>,
+        g_p: &SMatrix<T, N, M>,
+        h_x: &RowSVector<T, N>,
+        f_before: &SVector<T, N>,
+        f_after: &SVector<T, N>,
+    ) {
+        let h_f = (h_x * f_before)[0];
+        // derivatives of the event time
+        let t_x0 = -(h_x * self.dx0) / h_f;
+        let t_p = -(h_x * self.dp) / h_f;
+        self.dx0 = g_x * (self.dx0 + f_before * t_x0) - f_after * t_x0;
+        self.dp = g_x * (self.dp + f_before * t_p) + g_p - f_after * t_p;
+    }
+}
+
+impl<T: RealField + Copy, const N: usize, const M: usize> Sensitivity<T, N, M> {
+    /// Returns the position with the sensitivities given by the derivatives of the dual numbers,
+    /// the first `N` of which are taken with respect to the initial position, and the other `M`
+    /// with respect to the parameters.
+    fn from_dual<const K: usize>(x: &SVector<Dual<T, K>, N>) -> Self {
+        const { assert!(K == N + M) };
+        Self {
+            x: x.map(|x| x.re),
+            dx0: SMatrix::from_fn(|i, j| x[i].eps[j]),
+            dp: SMatrix::from_fn(|i, j| x[i].eps[N + j]),
+        }
+    }
+}
+
+impl<T: RealField + Copy, const N: usize, const M: usize, const K: usize> Lift<T, K, N>
+    for Sensitivity<T, N, M>
+{
+    fn lift(&self) -> SVector<Dual<T, K>, N> {
+        const { assert!(K == N + M) };
+        SVector::from_fn(|i, _| {
+            Dual::new(
+                self.x[i],
+                SVector::from_fn(|j, _| {
+                    if j < N {
+                        self.dx0[(i, j)]
+                    } else {
+                        self.dp[(i, j - N)]
+                    }
+                }),
+            )
+        })
+    }
+}
+
+/// Returns the parameters as the independent variables with the indices from `N`, following the
+/// initial position, see [Sensitivity::from_dual].
+fn lift_parameters<T: RealField + Copy, const N: usize, const M: usize, const K: usize>(
+    parameters: &SVector<T, M>,
+) -> SVector<Dual<T, K>, M> {
+    SVector::from_fn(|j, _| Dual::variable(parameters[j], N + j))
+}
+
+impl<T: RealField + Copy, const N: usize, const M: usize> From<SVector<T, N>>
+    for Sensitivity<T, N, M>
+{
+    fn from(x: SVector<T, N>) -> Self {
+        Self::new(x)
+    }
+}
+
+impl<T: RealField + Copy, const N: usize, const M: usize> Add for Sensitivity<T, N, M> {
+    type Output = Self;
+
+    fn add(self, rhs: Self) -> Self {
+        Self {
+            x: self.x + rhs.x,
+            dx0: self.dx0 + rhs.dx0,
+            dp: self.dp + rhs.dp,
+        }
+    }
+}
+
+impl<T: RealField + Copy, const N: usize, const M: usize> Sub for Sensitivity<T, N, M> {
+    type Output = Self;
+
+    fn sub(self, rhs: Self) -> Self {
+        Self {
+            x: self.x - rhs.x,
+            dx0: self.dx0 - rhs.dx0,
+            dp: self.dp - rhs.dp,
+        }
+    }
+}
+
+impl<T: RealField + Copy, const N: usize, const M: usize> AddAssign for Sensitivity<T, N, M> {
+    fn add_assign(&mut self, rhs: Self) {
+        self.x += rhs.x;
+        self.dx0 += rhs.dx0;
+        self.dp += rhs.dp;
+    }
+}
+
+impl<T: RealField + Copy, const N: usize, const M: usize> Mul<T> for Sensitivity<T, N, M> {
+    type Output = Self;
+
+    fn mul(self, rhs: T) -> Self {
+        Self {
+            x: self.x * rhs,
+            dx0: self.dx0 * rhs,
+            dp: self.dp * rhs,
+        }
+    }
+}
+
+impl<T: RealField + Copy, const N: usize, const M: usize> Div<T> for Sensitivity<T, N, M> {
+    type Output = Self;
+
+    fn div(self, rhs: T) -> Self {
+        Self {
+            x: self.x / rhs,
+            dx0: self.dx0 / rhs,
+            dp: self.dp / rhs,
+        }
+    }
+}
+
+impl<T: RealField + Copy, const N: usize, const M: usize> Neg for Sensitivity<T, N, M> {
+    type Output = Self;
+
+    fn neg(self) -> Self {
+        Self {
+            x: -self.x,
+            dx0: -self.dx0,
+            dp: -self.dp,
+        }
+    }
+}
+
+impl<T: RealField + Copy, const N: usize, const M: usize> num_traits::Zero
+    for Sensitivity<T, N, M>
+{
+    fn zero() -> Self {
+        Self {
+            x: SVector::zeros(),
+            dx0: SMatrix::zeros(),
+            dp: SMatrix::zeros(),
+        }
+    }
+
+    fn is_zero(&self) -> bool {
+        self.x.is_zero() && self.dx0.is_zero() && self.dp.is_zero()
+    }
+}
+
+/// Right hand side of the equation `x' = f(t, x(t), x(t_1), ..., x(t_D), p)` together with its
+/// Jacobians at the current state, returned by the closure of [Solver::sensitivity_equation].
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub struct Linearization<T: RealField + Copy, const N: usize, const M: usize, const D: usize> {
+    /// Right hand side of the equation
+    pub f: SVector<T, N>,
+    /// Jacobian of `f` with respect to the current position
+    pub dfdx: SMatrix<T, N, N>,
+    /// Jacobian of `f` with respect to the parameters
+    pub dfdp: SMatrix<T, N, M>,
+    /// Times of the delayed arguments with the Jacobians of `f` with respect to the positions
+    /// at these times (empty for ordinary differential equations)
+    pub delayed: [(T, SMatrix<T, N, N>); D],
+}
+
+impl<T: RealField + Copy, const N: usize, const M: usize, const D: usize>
+    Linearization<T, N, M, D>
+{
+    /// Returns the derivative of [Sensitivity] at the `state`, evaluating the sensitivities at
+    /// the delayed arguments from the history of the state.
+    pub fn derivative<
+        const S: usize,
+        const I: usize,
+        IC: InitialCondition<T, Sensitivity<T, N, M>>,
+    >(
+        &self,
+        state: &StateRef<T, Sensitivity<T, N, M>, S, I, IC>,
+    ) -> Sensitivity<T, N, M> {
+        let mut d = Sensitivity {
+            x: self.f,
+            dx0: self.dfdx * state.p.dx0,
+            dp: self.dfdx * state.p.dp + self.dfdp,
+        };
+        for (t, dfdx) in &self.delayed {
+            let delayed = state.p(*t);
+            d.dx0 += dfdx * delayed.dx0;
+            d.dp += dfdx * delayed.dp;
+        }
+        d
+    }
+}
+
+impl<
+    T: RealField + Copy,
+    const N: usize,
+    const M: usize,
+    const S: usize,
+    const I: usize,
+    Equation,
+    Initial,
+    Interval,
+    Stepsize,
+    EventsOnStep: HList + Append,
+    EventsOnStart: HList + Append,
+    EventsOnStop: HList + Append,
+    EventsOnLoc: HList + Append,
+>
+    Solver<
+        T,
+        Sensitivity<T, N, M>,
+        S,
+        I,
+        Equation,
+        Initial,
+        Interval,
+        Stepsize,
+        EventsOnStep,
+        EventsOnStart,
+        EventsOnStop,
+        EventsOnLoc,
+    >
+{
+    /// Sets the equation, given by the closure, which returns its right hand side together with
+    /// the Jacobians (see [Linearization]), so that the sensitivities of the solution with
+    /// respect to the initial position and parameters are integrated along with it (see
+    /// [Sensitivity]). Sensitivities at the jumps of state-mutating events are updated with
+    /// [Sensitivity::jump] or [Sensitivity::jump_at_crossing] in their callbacks. See
+    /// [Solver::dual_sensitivity_equation] for the sensitivities computed from the equation
+    /// itself.
+    #[allow(clippy::type_complexity)]
+    pub fn sensitivity_equation<
+        const D: usize,
+        F: FnMut(&StateRef<T, Sensitivity<T, N, M>, S, I, Initial>) -> Linearization<T, N, M, D>,
+    >(
+        self,
+        mut linearization: F,
+    ) -> Solver<
+        T,
+        Sensitivity<T, N, M>,
+        S,
+        I,
+        StateFn<
+            T,
+            Sensitivity<T, N, M>,
+            Sensitivity<T, N, M>,
+            impl FnMut(&StateRef<T, Sensitivity<T, N, M>, S, I, Initial>) -> Sensitivity<T, N, M>,
+        >,
+        Initial,
+        Interval,
+        Stepsize,
+        EventsOnStep,
+        EventsOnStart,
+        EventsOnStop,
+        EventsOnLoc,
+    >
+    where
+        Initial: InitialCondition<T, Sensitivity<T, N, M>>,
+    {
+        self.equation(move |state| linearization(state).derivative(state))
+    }
+
+    /// Sets the equation `x' = f(t, x(t), x(t_1), ..., x(t_D), p)`, given by the closure of the
+    /// state lifted into [Dual] numbers (see [DualStateRef]) and the `parameters`, so that the
+    /// sensitivities of the solution with respect to the initial position and the parameters are
+    /// integrated along with it (see [Sensitivity]). The closure is usually a function generic
+    /// over the scalar type.
+    ///
+    /// The position and the delayed arguments carry their sensitivities as the derivatives, and
+    /// the parameters are the independent variables, so that a single evaluation of the closure
+    /// gives the derivative of the sensitivities, without forming the Jacobians of
+    /// [Solver::sensitivity_equation]. The number of the derivatives `K` is to be `N + M`. The
+    /// sensitivities at the jumps of state-mutating events are updated by the events added with
+    /// [Solver::dual_on_mut] and [Solver::dual_on_crossing].
+    #[allow(clippy::type_complexity)]
+    pub fn dual_sensitivity_equation<
+        const K: usize,
+        F: FnMut(&DualStateRef<T, K, N, S, I>, SVector<Dual<T, K>, M>) -> SVector<Dual<T, K>, N>,
+    >(
+        self,
+        parameters: SVector<T, M>,
+        equation: F,
+    ) -> Solver<
+        T,
+        Sensitivity<T, N, M>,
+        S,
+        I,
+        DualSensitivityEquation<T, M, K, F>,
+        Initial,
+        Interval,
+        Stepsize,
+        EventsOnStep,
+        EventsOnStart,
+        EventsOnStop,
+        EventsOnLoc,
+    > {
+        const { assert!(K == N + M) };
+        self.set_equation(DualSensitivityEquation {
+            parameters,
+            equation,
+        })
+    }
+}
+
+/// Equation of [Solver::dual_sensitivity_equation], whose right hand side together with the
+/// derivatives of the sensitivities is evaluated with the state lifted into [Dual] numbers.
+#[derive(Clone)]
+pub struct DualSensitivityEquation<T: RealField + Copy, const M: usize, const K: usize, F> {
+    pub parameters: SVector<T, M>,
+    pub equation: F,
+}
+
+impl<T: RealField + Copy, const M: usize, const K: usize, F> DualSensitivityEquation<T, M, K, F> {
+    fn eval<const N: usize, const S: usize, const I: usize, IC>(
+        &mut self,
+        t: T,
+        p: &Sensitivity<T, N, M>,
+        d: &Sensitivity<T, N, M>,
+        t_prev: T,
+        p_prev: &Sensitivity<T, N, M>,
+        history: &StateHistory<T, Sensitivity<T, N, M>, S, I, IC>,
+    ) -> Sensitivity<T, N, M>
+    where
+        IC: InitialCondition<T, Sensitivity<T, N, M>>,
+        F: FnMut(&DualStateRef<T, K, N, S, I>, SVector<Dual<T, K>, M>) -> SVector<Dual<T, K>, N>,
+    {
+        let (p, d, p_prev) = (p.lift(), d.lift(), p_prev.lift());
+        let state = StateRef::lifted(
+            Dual::constant(t),
+            &p,
+            &d,
+            Dual::constant(t_prev),
+            &p_prev,
+            history,
+        );
+        Sensitivity::from_dual(&(self.equation)(
+            &state,
+            lift_parameters::<T, N, M, K>(&self.parameters),
+        ))
+    }
+}
+
+impl<
+    T: RealField + Copy,
+    const N: usize,
+    const M: usize,
+    const K: usize,
+    const S: usize,
+    const I: usize,
+    IC: InitialCondition<T, Sensitivity<T, N, M>>,
+    F: FnMut(&DualStateRef<T, K, N, S, I>, SVector<Dual<T, K>, M>) -> SVector<Dual<T, K>, N>,
+> EvalState<T, Sensitivity<T, N, M>, S, I, IC, Sensitivity<T, N, M>>
+    for DualSensitivityEquation<T, M, K, F>
+{
+    fn eval_curr(
+        &mut self,
+        state: &State<T, Sensitivity<T, N, M>, S, I, IC>,
+    ) -> Sensitivity<T, N, M> {
+        self.eval(
+            state.t_curr,
+            &state.p_curr,
+            &state.d_curr,
+            state.t_prev,
+            &state.p_prev,
+            &state.history,
+        )
+    }
+
+    fn eval_prev(
+        &mut self,
+        state: &State<T, Sensitivity<T, N, M>, S, I, IC>,
+    ) -> Sensitivity<T, N, M> {
+        self.eval(
+            state.t_prev,
+            &state.p_prev,
+            &state.d_prev,
+            state.t_prev,
+            &state.p_prev,
+            &state.history,
+        )
+    }
+
+    fn eval_at(
+        &mut self,
+        state: &State<T, Sensitivity<T, N, M>, S, I, IC>,
+        t: T,
+    ) -> Sensitivity<T, N, M> {
+        let (p, d) = (state.eval::<0>(t), state.eval::<1>(t));
+        self.eval(t, &p, &d, t, &p, &state.history)
+    }
+}
+
+/// Event of [Solver::dual_on_mut] and [Solver::dual_on_crossing], which makes the jump
+/// `x -> g(t, x, p)` of the position, and updates the sensitivities by evaluating `g` with [Dual]
+/// numbers. For the events located as zero crossings of `h(t, x, p)`, the `crossing` holds `h`
+/// and the equation, with which the dependence of the event time on the initial position and the
+/// parameters is taken into account, see [Sensitivity::jump_at_crossing].
+pub struct SensitivityJump<T: RealField + Copy, const M: usize, const K: usize, G, Crossing = ()> {
+    pub parameters: SVector<T, M>,
+    pub jump: G,
+    pub crossing: Crossing,
+}
+
+impl<
+    T: RealField + Copy,
+    const N: usize,
+    const M: usize,
+    const K: usize,
+    const S: usize,
+    const I: usize,
+    IC: InitialCondition<T, Sensitivity<T, N, M>>,
+    G: FnMut(Dual<T, K>, SVector<Dual<T, K>, N>, SVector<Dual<T, K>, M>) -> SVector<Dual<T, K>, N>,
+> EvalMutState<T, Sensitivity<T, N, M>, S, I, IC, ()> for SensitivityJump<T, M, K, G>
+{
+    fn eval_mut(&mut self, state: &mut State<T, Sensitivity<T, N, M>, S, I, IC>) {
+        let x = (self.jump)(
+            Dual::constant(state.t_curr),
+            state.p_curr.lift(),
+            lift_parameters::<T, N, M, K>(&self.parameters),
+        );
+        state.p_curr = Sensitivity::from_dual(&x);
+    }
+}
+
+impl<
+    T: RealField + Copy,
+    const N: usize,
+    const M: usize,
+    const K: usize,
+    const S: usize,
+    const I: usize,
+    IC: InitialCondition<T, Sensitivity<T, N, M>>,
+    G: FnMut(Dual<T, K>, SVector<Dual<T, K>, N>, SVector<Dual<T, K>, M>) -> SVector<Dual<T, K>, N>,
+    H: FnMut(Dual<T, K>, SVector<Dual<T, K>, N>, SVector<Dual<T, K>, M>) -> Dual<T, K>,
+    Equation: EvalState<T, Sensitivity<T, N, M>, S, I, IC, Sensitivity<T, N, M>>,
+> EvalMutState<T, Sensitivity<T, N, M>, S, I, IC, ()>
+    for SensitivityJump<T, M, K, G, (H, Equation)>
+{
+    fn eval_mut(&mut self, state: &mut State<T, Sensitivity<T, N, M>, S, I, IC>) {
+        let (h, equation) = &mut self.crossing;
+        let (t, parameters) = (
+            state.t_curr,
+            lift_parameters::<T, N, M, K>(&self.parameters),
+        );
+        let f_before = equation.eval_curr(state).x;
+        // derivatives of the event time from h(t, x, p) = 0
+        let h_xp = h(Dual::constant(t), state.p_curr.lift(), parameters).eps;
+        let h_t = h(
+            Dual::variable(t, 0),
+            state.p_curr.x.zip_map(&f_before, |x, f| {
+                Dual::new(
+                    x,
+                    SVector::from_fn(|j, _| if j == 0 { f } else { T::zero() }),
+                )
+            }),
+            self.parameters.map(Dual::constant),
+        )
+        .eps[0];
+        let t_s = -h_xp / h_t;
+
+        let x = state.p_curr.lift().zip_map(&f_before, |x: Dual<T, K>, f| {
+            Dual::new(x.re, x.eps + t_s * f)
+        });
+        let x = (self.jump)(Dual::new(t, t_s), x, parameters);
+        state.p_curr = Sensitivity::from_dual(&x);
+        let f_after = equation.eval_curr(state).x;
+        state.stats.rhs_evals += 2;
+
+        let t_s = t_s.transpose();
+        state.p_curr.dx0 -= f_after * t_s.columns(0, N);
+        state.p_curr.dp -= f_after * t_s.columns(N, M);
+    }
+}
+
+impl<
+    T: RealField + Copy,
+    const N: usize,
+    const M: usize,
+    const K: usize,
+    const S: usize,
+    const I: usize,
+    F: FnMut(&DualStateRef<T, K, N, S, I>, SVector<Dual<T, K>, M>) -> SVector<Dual<T, K>, N>,
+    Initial,
+    Interval,
+    Stepsize,
+    EventsOnStep: HList + Append,
+    EventsOnStart: HList + Append,
+    EventsOnStop: HList + Append,
+    EventsOnLoc: HList + Append,
+>
+    Solver<
+        T,
+        Sensitivity<T, N, M>,
+        S,
+        I,
+        DualSensitivityEquation<T, M, K, F>,
+        Initial,
+        Interval,
+        Stepsize,
+        EventsOnStep,
+        EventsOnStart,
+        EventsOnStop,
+        EventsOnLoc,
+    >
+where
+    Initial: InitialCondition<T, Sensitivity<T, N, M>>,
+{
+    /// Adds a state-mutating event, which makes the jump `x -> g(t, x, p)` of the position when
+    /// `loc` is triggered, and updates the sensitivities with the derivatives of the `jump` `g`,
+    /// evaluated with [Dual] numbers and the parameters of [Solver::dual_sensitivity_equation].
+    /// The time of the event is taken to be independent of the initial position and the
+    /// parameters (e.g. [crate::Periodic]), see [Solver::dual_on_crossing] otherwise.
+    #[allow(clippy::type_complexity)]
+    pub fn dual_on_mut<
+        LocF: Locate<T, Sensitivity<T, N, M>, S, I, Initial>,
+        G: FnMut(Dual<T, K>, SVector<Dual<T, K>, N>, SVector<Dual<T, K>, M>) -> SVector<Dual<T, K>, N>,
+    >(
+        self,
+        loc: LocF,
+        jump: G,
+    ) -> Solver<
+        T,
+        Sensitivity<T, N, M>,
+        S,
+        I,
+        DualSensitivityEquation<T, M, K, F>,
+        Initial,
+        Interval,
+        Stepsize,
+        EventsOnStep,
+        EventsOnStart,
+        EventsOnStop,
+        EventsOnLoc::Output<DedupLocF<T, LocCallback<LocF, SensitivityJump<T, M, K, G>>>>,
+    > {
+        let parameters = self.equation.parameters;
+        self.add_event_on_loc(DedupLocF {
+            last_call: None,
+            loc_f: LocCallback(
+                loc,
+                SensitivityJump {
+                    parameters,
+                    jump,
+                    crossing: (),
+                },
+            ),
+        })
+    }
+
+    /// Adds a state-mutating event like [Solver::dual_on_mut], which is detected by `detection`
+    /// (e.g. [crate::loc::detection_method::BelowZero]) from the values of `h(t, x, p)` and
+    /// located by bisection, as [crate::Locator] does. The sensitivities account for the
+    /// dependence of the time of the event on the initial position and the parameters, computed
+    /// from the derivatives of `h` (see [Sensitivity::jump_at_crossing]), which requires two
+    /// evaluations of the equation.
+    #[allow(clippy::type_complexity)]
+    pub fn dual_on_crossing<
+        Detection: DetectionMethod,
+        H: FnMut(Dual<T, K>, SVector<Dual<T, K>, N>, SVector<Dual<T, K>, M>) -> Dual<T, K> + Clone,
+        G: FnMut(Dual<T, K>, SVector<Dual<T, K>, N>, SVector<Dual<T, K>, M>) -> SVector<Dual<T, K>, N>,
+    >(
+        self,
+        detection: Detection,
+        h: H,
+        jump: G,
+    ) -> Solver<
+        T,
+        Sensitivity<T, N, M>,
+        S,
+        I,
+        DualSensitivityEquation<T, M, K, F>,
+        Initial,
+        Interval,
+        Stepsize,
+        EventsOnStep,
+        EventsOnStart,
+        EventsOnStop,
+        EventsOnLoc::Output<
+            DedupLocF<
+                T,
+                LocCallback<
+                    LocatorStateFn<
+                        T,
+                        Sensitivity<T, N, M>,
+                        T,
+                        StateFn<
+                            T,
+                            Sensitivity<T, N, M>,
+                            T,
+                            impl FnMut(&StateRef<T, Sensitivity<T, N, M>, S, I, Initial>) -> T,
+                        >,
+                        Detection,
+                        Bisection,
+                    >,
+                    SensitivityJump<T, M, K, G, (H, DualSensitivityEquation<T, M, K, F>)>,
+                >,
+            >,
+        >,
+    >
+    where
+        F: Clone,
+    {
+        let parameters = self.equation.parameters;
+        let mut value = h.clone();
+        let f = move |s: &StateRef<T, Sensitivity<T, N, M>, S, I, Initial>| {
+            value(
+                Dual::constant(s.t),
+                s.p.x.map(Dual::constant),
+                parameters.map(Dual::constant),
+            )
+            .re
+        };
+        let equation = self.equation.clone();
+        self.add_event_on_loc(DedupLocF {
+            last_call: None,
+            loc_f: LocCallback(
+                LocatorStateFn::new(StateFn::new(f), detection, Bisection),
+                SensitivityJump {
+                    parameters,
+                    jump,
+                    crossing: (h, equation),
+                },
+            ),
+        })
+    }
+}
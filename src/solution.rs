@@ -0,0 +1,122 @@
+//! Defines [Solution], the dense output of a completed integration.
+
+use nalgebra::RealField;
+
+use crate::{
+    error::HistoryError,
+    initial_condition::InitialCondition,
+    state::{State, StateHistory},
+    traits::RealVectorSpace,
+};
+
+/// Solution, which can be evaluated at any time of the integration interval (and before it, by
+/// the initial condition), returned by [crate::Solver::solve] or [State::into_solution].
+///
+/// It evaluates the dense output of the Runge-Kutta method over the steps stored in
+/// [StateHistory], so the whole integration interval is covered only if the whole history is
+/// kept, see [crate::Solver::keep_history].
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+#[cfg_attr(
+    feature = "serde",
+    serde(bound(
+        serialize = "T: serde::Serialize, Y: serde::Serialize, IC: serde::Serialize",
+        deserialize = "T: serde::Deserialize<'de>, Y: serde::Deserialize<'de>, IC: serde::Deserialize<'de>"
+    ))
+)]
+pub struct Solution<T: RealField + Copy, Y: RealVectorSpace<T>, const S: usize, const I: usize, IC>
+{
+    pub history: StateHistory<T, Y, S, I, IC>,
+}
+
+impl<T: RealField + Copy, Y: RealVectorSpace<T>, const S: usize, const I: usize, IC>
+    State<T, Y, S, I, IC>
+{
+    /// Returns the [Solution], which evaluates the stored history.
+    pub fn into_solution(self) -> Solution<T, Y, S, I, IC> {
+        Solution {
+            history: self.history,
+        }
+    }
+}
+
+impl<
+    T: RealField + Copy,
+    Y: RealVectorSpace<T>,
+    const S: usize,
+    const I: usize,
+    IC: InitialCondition<T, Y>,
+> Solution<T, Y, S, I, IC>
+{
+    /// Earliest time of the stored steps (in the direction of integration).
+    pub fn start(&self) -> T {
+        self.history.t_deque[0]
+    }
+
+    /// Time at the end of the last step.
+    pub fn end(&self) -> T {
+        self.history.t_deque[self.history.t_deque.len() - 1]
+    }
+
+    /// Evaluates the solution at time `t`. Panics if the time is not covered by the solution,
+    /// see [Solution::try_eval].
+    pub fn eval(&self, t: T) -> Y {
+        self.try_eval(t).unwrap_or_else(|error| panic!("{error}"))
+    }
+
+    /// Evaluates the solution at time `t`.
+    pub fn try_eval(&self, t: T) -> Result<Y, HistoryError<T>> {
+        self.try_eval_derivative::<0>(t)
+    }
+
+    /// Evaluates the derivative of the solution at time `t`. Panics if the time is not covered
+    /// by the solution, see [Solution::try_derivative].
+    pub fn derivative(&self, t: T) -> Y {
+        self.try_derivative(t)
+            .unwrap_or_else(|error| panic!("{error}"))
+    }
+
+    /// Evaluates the derivative of the solution at time `t`.
+    pub fn try_derivative(&self, t: T) -> Result<Y, HistoryError<T>> {
+        self.try_eval_derivative::<1>(t)
+    }
+
+    /// Evaluates the solution at each of the `times`. Panics if one of the times is not covered
+    /// by the solution.
+    pub fn sample(&self, times: impl IntoIterator<Item = T>) -> Vec<Y> {
+        times.into_iter().map(|t| self.eval(t)).collect()
+    }
+
+    /// Returns the iterator over times and positions at the step boundaries, starting with
+    /// [Solution::start]. Steps of zero length, made at events, are included.
+    pub fn steps(&self) -> impl Iterator<Item = (T, Y)> + '_ {
+        self.history
+            .t_deque
+            .iter()
+            .copied()
+            .zip(self.history.p_deque.iter().copied())
+    }
+
+    fn try_eval_derivative<const D: usize>(&self, t: T) -> Result<Y, HistoryError<T>> {
+        let t_deque = &self.history.t_deque;
+        let mut steps = (1..t_deque.len()).filter(|&i| t_deque[i - 1] != t_deque[i]);
+        match self.history.try_eval::<D>(t) {
+            // the end of the last step is not covered by the history, which evaluates steps on
+            // half-open intervals
+            Err(error @ HistoryError::NotComputed { t_latest, .. }) if t == t_latest => {
+                if D == 0 {
+                    return Ok(self.history.p_deque[t_deque.len() - 1]);
+                }
+                steps
+                    .next_back()
+                    .map(|i| self.history.eval_step::<D>(i, T::one()))
+                    .ok_or(error)
+            }
+            // the initial condition is evaluated at the initial time, but the derivative of the
+            // solution is that of the first step
+            Ok(initial) if D > 0 && t == self.history.t_init && t == t_deque[0] => Ok(steps
+                .next()
+                .map_or(initial, |i| self.history.eval_step::<D>(i, T::zero()))),
+            result => result,
+        }
+    }
+}
@@ -1,10 +1,18 @@
+use std::{
+    collections::VecDeque,
+    sync::{Arc, atomic::AtomicBool},
+};
+
 use hlist2::{HList, Nil};
 
-use nalgebra::RealField;
+use nalgebra::{DMatrix, RealField, SVector};
 use replace::replace_ident;
 
 use crate::{
     Locator,
+    control::{Control, Limits},
+    error::SolverError,
+    implicit::Newton,
     initial_condition::InitialCondition,
     loc::DedupLocF,
     loc::{
@@ -12,10 +20,15 @@ use crate::{
         loc_callback::LocCallback,
         location_method::Bisection,
         propagation::{Propagation, Propagator},
+        zeno::{Zeno, ZenoLocF, ZenoResponse},
     },
+    multistep::{Formula, Multistep},
     rk::ButcherTableu,
-    stepsize::{StepStatus, StepsizeController},
-    traits::RealVectorSpace,
+    state::{Checkpoint, ControlFn, GridEvent, Progress, ProgressEvent, UniformGrid},
+    stepper::Stepper,
+    stepsize::StepsizeController,
+    stiffness::Stiffness,
+    traits::{Components, RealVectorSpace},
 };
 
 macro_rules! SolverType {
@@ -38,18 +51,31 @@ macro_rules! solver_set {
             replace_ident!(
                 $field, $field,
                 #[allow(unused_variables)]
-                let Solver { equation, initial, initial_disco, interval, max_delay, rk, stepsize, events_on_step, events_on_start, events_on_stop, events_on_loc, _phantom_y } = $self;
+                let Solver { equation, initial, initial_disco, interval, max_delay, keep_history, rk, stepsize, event_tolerance, tstops, tstops_disco, limits, cancel_token, newton, stiffness, rk_switch, multistep, events_on_step, events_on_start, events_on_stop, events_on_loc, _phantom_y } = $self;
             );
             replace_ident!(
                 $field, $field: $value,
-                Solver { equation, initial, initial_disco, interval, max_delay, rk, stepsize, events_on_step, events_on_start, events_on_stop, events_on_loc, _phantom_y }
+                Solver { equation, initial, initial_disco, interval, max_delay, keep_history, rk, stepsize, event_tolerance, tstops, tstops_disco, limits, cancel_token, newton, stiffness, rk_switch, multistep, events_on_step, events_on_start, events_on_stop, events_on_loc, _phantom_y }
+            )
+        }
+    };
+    ($self:expr, $field1:ident: $value1:expr, $field2:ident: $value2:expr) => {
+        {
+            #[allow(unused_variables)]
+            let Solver { equation, initial, initial_disco, interval, max_delay, keep_history, rk, stepsize, event_tolerance, tstops, tstops_disco, limits, cancel_token, newton, stiffness, rk_switch, multistep, events_on_step, events_on_start, events_on_stop, events_on_loc, _phantom_y } = $self;
+            replace_ident!(
+                $field1, $field1: $value1,
+                replace_ident!(
+                    $field2, $field2: $value2,
+                    Solver { equation, initial, initial_disco, interval, max_delay, keep_history, rk, stepsize, event_tolerance, tstops, tstops_disco, limits, cancel_token, newton, stiffness, rk_switch, multistep, events_on_step, events_on_start, events_on_stop, events_on_loc, _phantom_y }
+                )
             )
         }
     };
 }
 
 pub struct Solver<
-    T = f64,
+    T: RealField = f64,
     P = f64,
     const S: usize = 0,
     const I: usize = 0,
@@ -69,6 +95,29 @@ pub struct Solver<
     pub rk: crate::rk::ButcherTableu<T, S, I>,
     pub stepsize: Stepsize,
     pub max_delay: T,
+    /// Whether the whole history is kept, see [Solver::keep_history].
+    pub keep_history: bool,
+    /// Located events within this time of the earliest one fire at its time in the same step,
+    /// see [Solver::event_tolerance].
+    pub event_tolerance: Option<T>,
+    /// Limits of the run, see [Solver::max_steps], [Solver::max_rhs_evals] and
+    /// [Solver::time_limit].
+    pub limits: Limits,
+    /// Times, on which the steps end, see [Solver::tstops].
+    pub tstops: Vec<T>,
+    /// Whether [Solver::tstops] are registered as discontinuities, see [Solver::tstops_disco].
+    pub tstops_disco: bool,
+    /// The integration is cancelled when the token is set, see [Solver::cancel_token].
+    pub cancel_token: Option<Arc<AtomicBool>>,
+    /// Newton iterations of implicit Runge-Kutta methods, see [Solver::implicit_rk].
+    pub newton: Option<Newton<T, P>>,
+    /// Stiffness detection, see [Solver::stiffness_detection].
+    pub stiffness: Option<Stiffness<T, P>>,
+    /// Method, to which the integration switches for stiff equations, see
+    /// [Solver::auto_switch].
+    pub rk_switch: Option<crate::rk::ButcherTableu<T, S, I>>,
+    /// Multistep method, which replaces the Runge-Kutta method, see [Solver::adams].
+    pub multistep: Option<Multistep<T, P>>,
     pub events_on_step: EventsOnStep,
     pub events_on_start: EventsOnStart,
     pub events_on_stop: EventsOnStop,
@@ -85,6 +134,16 @@ impl Solver {
             initial_disco: vec![],
             interval: (),
             max_delay: T::zero(),
+            keep_history: false,
+            event_tolerance: None,
+            limits: Limits::default(),
+            tstops: vec![],
+            tstops_disco: false,
+            cancel_token: None,
+            newton: None,
+            stiffness: None,
+            rk_switch: None,
+            multistep: None,
             rk: crate::rk::ButcherTableu::rktp64(),
             stepsize: T::from_f64(0.05).unwrap(),
             events_on_step: Nil,
@@ -131,6 +190,135 @@ impl<
         Self { max_delay, ..self }
     }
 
+    /// Keeps the whole history of the integration, instead of the last [Solver::max_delay] of it,
+    /// e.g. to evaluate the [crate::Solution] after the integration, see [Solver::solve]. Returns
+    /// self.
+    pub fn keep_history(self) -> Self {
+        Self {
+            keep_history: true,
+            ..self
+        }
+    }
+
+    /// [Solver::event_tolerance] setter. Returns self.
+    ///
+    /// By default, only the earliest located event fires in a step (of the events located at the
+    /// same time, the one with the highest priority, see [crate::WithPriority::with_priority]). With
+    /// the tolerance set, all the events located within `tolerance` from the earliest one fire
+    /// at its time, in the order of decreasing priority. After a callback changes the state,
+    /// the detection of the remaining events is evaluated again with the changed state, and the
+    /// events which are no longer detected do not fire.
+    pub fn event_tolerance(self, tolerance: T) -> Self {
+        Self {
+            event_tolerance: Some(tolerance),
+            ..self
+        }
+    }
+
+    /// Adds the times, on which the steps end exactly, e.g. the times of discontinuities of the
+    /// right hand side of the equation. The step ending on such time is followed by the step,
+    /// for which the derivative is evaluated again. If one of the `times` is not finite, the
+    /// integration fails with [SolverError::NonFiniteTstop]. Returns self.
+    pub fn tstops(mut self, times: impl IntoIterator<Item = T>) -> Self {
+        self.tstops.extend(times);
+        self
+    }
+
+    /// Registers [Solver::tstops] as discontinuities of order 0 when they are reached, so that
+    /// they are propagated through delays (see [Solver::with_delayed_argument]). Returns self.
+    pub fn tstops_disco(self) -> Self {
+        Self {
+            tstops_disco: true,
+            ..self
+        }
+    }
+
+    /// Returns [SolverError::NonFiniteTstop] if one of [Solver::tstops] is not finite.
+    fn check_tstops(&self, t: T) -> Result<(), SolverError<T>> {
+        match self.tstops.iter().find(|tstop| !tstop.is_finite()) {
+            Some(&tstop) => Err(SolverError::NonFiniteTstop { tstop, t, step: 0 }),
+            None => Ok(()),
+        }
+    }
+
+    /// Returns [SolverError::ReversedInterval] if the end of the interval is before the current
+    /// time of `state` in the direction of its history, when `state` has made steps.
+    fn check_direction(
+        &self,
+        state: &crate::state::State<T, P, S, I, Initial>,
+    ) -> Result<(), SolverError<T>>
+    where
+        Interval: crate::interval::IntegrationInterval<T>,
+        Initial: crate::initial_condition::InitialCondition<T, P>,
+    {
+        let t_end = self.interval.end_bound();
+        if state.t_curr == state.history.t_init || state.history.precedes(state.t_curr, t_end) {
+            Ok(())
+        } else {
+            Err(SolverError::ReversedInterval {
+                t_end,
+                t: state.t_curr,
+                step: 0,
+            })
+        }
+    }
+
+    /// Returns `tstops` after `t_curr`, ordered in the direction of integration.
+    fn upcoming_tstops(mut tstops: Vec<T>, t_curr: T, backward: bool) -> VecDeque<T> {
+        if backward {
+            tstops.retain(|&t| t < t_curr);
+            tstops.sort_by(|a, b| b.partial_cmp(a).unwrap());
+        } else {
+            tstops.retain(|&t| t > t_curr);
+            tstops.sort_by(|a, b| a.partial_cmp(b).unwrap());
+        }
+        tstops.dedup();
+        tstops.into()
+    }
+
+    /// Limits the number of committed steps of a run. When the limit is reached, the
+    /// integration ends with [crate::Termination::LimitReached]. Returns self.
+    pub fn max_steps(self, max_steps: usize) -> Self {
+        let limits = Limits {
+            max_steps: Some(max_steps),
+            ..self.limits
+        };
+        Self { limits, ..self }
+    }
+
+    /// Limits the number of evaluations of the right hand side of the equation in a run, see
+    /// [Limits::max_rhs_evals]. When the limit is reached, the integration ends with
+    /// [crate::Termination::LimitReached]. Returns self.
+    pub fn max_rhs_evals(self, max_rhs_evals: usize) -> Self {
+        let limits = Limits {
+            max_rhs_evals: Some(max_rhs_evals),
+            ..self.limits
+        };
+        Self { limits, ..self }
+    }
+
+    /// Limits the wall-clock time of a run, which is checked before each step and after each
+    /// rejected attempt of a step. When the limit is reached, the integration ends with
+    /// [crate::Termination::LimitReached]. Returns self.
+    pub fn time_limit(self, time_limit: std::time::Duration) -> Self {
+        let limits = Limits {
+            time_limit: Some(time_limit),
+            ..self.limits
+        };
+        Self { limits, ..self }
+    }
+
+    /// [Solver::cancel_token] setter. Returns self.
+    ///
+    /// The token is checked before each step, and once it is set (e.g. from another thread), the
+    /// integration ends with [crate::Termination::Cancelled].
+    pub fn cancel_token(self, cancel_token: Arc<AtomicBool>) -> Self {
+        Self {
+            cancel_token: Some(cancel_token),
+            ..self
+        }
+    }
+
     #[allow(unused_parens)]
     pub fn equation<F: FnMut(&crate::StateRef<T, P, S, I, Initial>) -> P>(
         self,
@@ -139,6 +327,24 @@ impl<
         solver_set!(self, equation: crate::StateFn::new(new_equation))
     }
 
+    /// Sets the equation, which is evaluated by [crate::state::EvalState] itself, see
+    /// [Solver::dual_sensitivity_equation].
+    pub(crate) fn set_equation<NewEquation>(
+        self,
+        new_equation: NewEquation,
+    ) -> SolverType!(Equation => NewEquation) {
+        solver_set!(self, equation: new_equation)
+    }
+
+    /// Adds the located event, see [Solver::dual_on_mut].
+    #[allow(unused_parens)]
+    pub(crate) fn add_event_on_loc<Event>(
+        self,
+        event: Event,
+    ) -> SolverType!(EventsOnLoc => (EventsOnLoc::Output::<Event>)) {
+        solver_set!(self, events_on_loc: events_on_loc.append(event))
+    }
+
     pub fn initial<NewInitial>(
         self,
         new_initial: NewInitial,
@@ -153,20 +359,253 @@ impl<
         solver_set!(self, interval: new_interval)
     }
 
+    /// Sets the explicit Runge-Kutta method. Implicit methods are set with
+    /// [Solver::implicit_rk], and the integration with them fails with
+    /// [SolverError::ImplicitMethod] otherwise. Returns self.
     pub fn rk<const S_: usize, const I_: usize>(
         self,
         new_rk: ButcherTableu<T, S_, I_>,
     ) -> SolverType!(S => S_, I => I_) {
-        solver_set!(self, rk: new_rk)
+        let solver = solver_set!(self, rk: new_rk, rk_switch: None);
+        Solver {
+            multistep: None,
+            ..solver
+        }
+    }
+
+    /// Sets the implicit Runge-Kutta method (e.g. [ButcherTableu::radau5],
+    /// [ButcherTableu::sdirk4] or [ButcherTableu::tr_bdf2]) for stiff equations, whose stage
+    /// equations are solved by Newton iterations (see [Newton]), or the Rosenbrock method (e.g.
+    /// [ButcherTableu::rodas4] or [ButcherTableu::ros3p]), whose stages are solved by one linear
+    /// solve each. The position is required to implement [Components]. Returns self.
+    pub fn implicit_rk<const S_: usize, const I_: usize>(
+        mut self,
+        new_rk: ButcherTableu<T, S_, I_>,
+    ) -> SolverType!(S => S_, I => I_)
+    where
+        P: Components<T>,
+    {
+        let newton = Some(self.newton.take().unwrap_or_default());
+        let solver = solver_set!(self, rk: new_rk, rk_switch: None);
+        Solver {
+            newton,
+            multistep: None,
+            ..solver
+        }
+    }
+
+    /// Sets the variable-step, variable-order Adams-Bashforth-Moulton method (see [Multistep])
+    /// instead of the Runge-Kutta method. It evaluates the right hand side twice per step, which
+    /// is efficient for smooth equations with expensive right hand sides. Its dense output is
+    /// stored in the history as the interpolating polynomial of each step. Setting a
+    /// Runge-Kutta method afterwards disables it. See [Solver::multistep] for the method with a
+    /// lower [Multistep::max_order]. Returns self.
+    #[allow(clippy::type_complexity)]
+    pub fn adams(
+        self,
+    ) -> Solver<
+        T,
+        P,
+        0,
+        0,
+        Equation,
+        Initial,
+        Interval,
+        Stepsize,
+        EventsOnStep,
+        EventsOnStart,
+        EventsOnStop,
+        EventsOnLoc,
+    > {
+        self.set_multistep(Multistep::adams())
+    }
+
+    /// Sets the variable-step, variable-order BDF method of orders 1 to
+    /// [crate::multistep::MAX_BDF_ORDER] (see [Multistep]) instead of the Runge-Kutta method,
+    /// for stiff equations with many components, e.g. discretizations of partial differential
+    /// equations. The corrector equation of each step is solved by simplified Newton iterations
+    /// (see [Solver::newton]), which evaluate the right hand side once per iteration, and reuse
+    /// the Jacobian and the LU factorization of the iteration matrix across steps. For many
+    /// components, the Jacobian is better set with [Solver::jacobian], since the finite
+    /// differences evaluate the right hand side once per component. The dense output is stored
+    /// as for [Solver::adams]. The position is required to implement [Components]. Returns
+    /// self.
+    #[allow(clippy::type_complexity)]
+    pub fn bdf(
+        self,
+    ) -> Solver<
+        T,
+        P,
+        0,
+        0,
+        Equation,
+        Initial,
+        Interval,
+        Stepsize,
+        EventsOnStep,
+        EventsOnStart,
+        EventsOnStop,
+        EventsOnLoc,
+    >
+    where
+        P: Components<T>,
+    {
+        self.multistep(Multistep::bdf())
+    }
+
+    /// Sets the multistep method, e.g. `Multistep::bdf().with_max_order(3)`, see
+    /// [Solver::adams] and [Solver::bdf]. The position is required to implement [Components]
+    /// for the Newton iterations of the BDF method. Returns self.
+    #[allow(clippy::type_complexity)]
+    pub fn multistep(
+        mut self,
+        multistep: Multistep<T, P>,
+    ) -> Solver<
+        T,
+        P,
+        0,
+        0,
+        Equation,
+        Initial,
+        Interval,
+        Stepsize,
+        EventsOnStep,
+        EventsOnStart,
+        EventsOnStop,
+        EventsOnLoc,
+    >
+    where
+        P: Components<T>,
+    {
+        if multistep.formula() == Formula::Bdf {
+            self.newton = Some(self.newton.take().unwrap_or_default());
+        }
+        self.set_multistep(multistep)
+    }
+
+    /// Sets the multistep method with the tableau without stages, see [Multistep::tableau].
+    #[allow(clippy::type_complexity)]
+    fn set_multistep(
+        self,
+        multistep: Multistep<T, P>,
+    ) -> Solver<
+        T,
+        P,
+        0,
+        0,
+        Equation,
+        Initial,
+        Interval,
+        Stepsize,
+        EventsOnStep,
+        EventsOnStart,
+        EventsOnStop,
+        EventsOnLoc,
+    > {
+        let solver = solver_set!(self, rk: multistep.tableau(), rk_switch: None);
+        Solver {
+            multistep: Some(multistep),
+            ..solver
+        }
+    }
+
+    /// Sets [Newton::tolerance] (used if the stepsize controller has no tolerances, see
+    /// [Newton::kappa]) and [Newton::max_iterations] of the Newton iterations of implicit
+    /// methods, see [Solver::implicit_rk]. Returns self.
+    pub fn newton(mut self, tolerance: T, max_iterations: usize) -> Self
+    where
+        P: Components<T>,
+    {
+        let newton = self.newton.get_or_insert_default();
+        newton.tolerance = tolerance;
+        newton.max_iterations = max_iterations;
+        self
+    }
+
+    /// Sets the function of time and position, which computes the Jacobian of the right hand
+    /// side with respect to the position for implicit methods (see [Solver::implicit_rk]),
+    /// instead of finite differences. Returns self.
+    pub fn jacobian(
+        mut self,
+        jacobian: impl Fn(T, &P) -> DMatrix<T> + Send + Sync + 'static,
+    ) -> Self
+    where
+        P: Components<T>,
+    {
+        self.newton.get_or_insert_default().jacobian_fn = Some(Arc::new(jacobian));
+        self
+    }
+
+    /// Sets the Jacobian of implicit methods (see [Solver::jacobian]), which is computed by
+    /// automatic differentiation (see [crate::dual::jacobian]) of `rhs`, the right hand side of
+    /// the equation as a function of time and position with [crate::Dual] scalars (e.g. a
+    /// generic function, which also implements the equation for `T`). Returns self.
+    ///
+    /// # Panics
+    /// The Jacobian evaluation panics if the position does not have `N` components.
+    pub fn dual_jacobian<const N: usize>(
+        self,
+        rhs: impl Fn(crate::Dual<T, N>, SVector<crate::Dual<T, N>, N>) -> SVector<crate::Dual<T, N>, N>
+        + Send
+        + Sync
+        + 'static,
+    ) -> Self
+    where
+        P: Components<T>,
+    {
+        self.jacobian(move |t, p| {
+            let p = SVector::from_column_slice(p.to_components().as_slice());
+            let (_, jacobian) = crate::dual::jacobian(|p| rhs(crate::Dual::constant(t), p), p);
+            DMatrix::from_column_slice(N, N, jacobian.as_slice())
+        })
+    }
+
+    /// Enables the stiffness detection (see [Stiffness]), which is reported in
+    /// [crate::SolveStats::stiff_steps] and [crate::SolveStats::stiffness_detections]. The
+    /// position is required to implement [Components]. Returns self.
+    pub fn stiffness_detection(mut self) -> Self
+    where
+        P: Components<T>,
+    {
+        self.stiffness.get_or_insert_default();
+        self
     }
 
+    /// Enables the stiffness detection (see [Solver::stiffness_detection]) and the automatic
+    /// switching from the explicit method to the implicit or Rosenbrock method `stiff_rk` when
+    /// the equation is detected as stiff, and back when it is not. The explicit method is set
+    /// before with [Solver::rk] (setting the method afterwards disables the switching), and
+    /// `stiff_rk` is padded to its numbers of stages and interpolation coefficients (see
+    /// [ButcherTableu::padded]), e.g. [ButcherTableu::sdirk4] or [ButcherTableu::rodas4] for the
+    /// default [ButcherTableu::rktp64]. The stepsize controller uses the order of the error
+    /// estimate of the current method (see [StepsizeController::update_with_order]), and the
+    /// history is evaluated with the method of each step. Returns self.
+    ///
+    /// # Panics
+    /// Panics if `stiff_rk` has more stages or interpolation coefficients than the explicit
+    /// method.
+    pub fn auto_switch<const S_: usize, const I_: usize>(
+        mut self,
+        stiff_rk: ButcherTableu<T, S_, I_>,
+    ) -> Self
+    where
+        P: Components<T>,
+    {
+        self.stiffness.get_or_insert_default();
+        self.newton.get_or_insert_default();
+        self.rk_switch = Some(stiff_rk.padded());
+        self
+    }
+
+    /// Adds an event, evaluated after every step. The `callback` may return [Control] to stop
+    /// the integration or restart the stepsize controller.
     #[allow(unused_parens)]
-    pub fn on_step<C: FnMut(&crate::StateRef<T, P, S, I, Initial>)>(
+    pub fn on_step<R: Into<Control>, C: FnMut(&crate::StateRef<T, P, S, I, Initial>) -> R>(
         self,
         callback: C,
-    ) -> SolverType!(EventsOnStep => EventsOnStep::Output::<(crate::state::StateFn<T, P, (), C>)>)
+    ) -> SolverType!(EventsOnStep => EventsOnStep::Output::<(ControlFn<crate::state::StateFn<T, P, R, C>>)>)
     {
-        solver_set!(self, events_on_step: events_on_step.append(crate::StateFn::new(callback)))
+        solver_set!(self, events_on_step: events_on_step.append(ControlFn(crate::StateFn::new(callback))))
     }
 
     #[allow(unused_parens)]
@@ -195,42 +634,174 @@ impl<
         solver_set!(self, events_on_start: events_on_start.append(crate::StateFn::new_mut(callback)))
     }
 
+    /// Adds an event, which calls the `callback` when `loc` is triggered. The `callback` may
+    /// return [Control] to stop the integration or restart the stepsize controller.
     #[allow(unused_parens)]
     pub fn on<
         LocF: Locate<T, P, S, I, Initial>,
-        CallbackF: FnMut(&crate::StateRef<T, P, S, I, Initial>),
+        R: Into<Control>,
+        CallbackF: FnMut(&crate::StateRef<T, P, S, I, Initial>) -> R,
     >(
         self,
         loc: LocF,
         callback: CallbackF,
-    ) -> SolverType!(EventsOnLoc => (EventsOnLoc::Output::<DedupLocF<T, LocCallback<LocF, (crate::state::StateFn<T, P, (), CallbackF>)>>>))
+    ) -> SolverType!(EventsOnLoc => (EventsOnLoc::Output::<DedupLocF<T, LocCallback<LocF, (ControlFn<crate::state::StateFn<T, P, R, CallbackF>>)>>>))
     where
         Initial: InitialCondition<T, P>,
     {
         solver_set!(self, events_on_loc: events_on_loc.append(
             DedupLocF {
                 last_call: None,
-                loc_f: LocCallback(loc, crate::StateFn::new(callback))
+                loc_f: LocCallback(loc, ControlFn(crate::StateFn::new(callback)))
             }
         ))
     }
 
+    /// Adds an event, which changes the state with the `callback` when `loc` is triggered. The
+    /// `callback` may return [Control] to stop the integration or restart the stepsize
+    /// controller.
     #[allow(unused_parens)]
     pub fn on_mut<
+        LocF: Locate<T, P, S, I, Initial>,
+        R: Into<Control>,
+        C: FnMut(&mut crate::StateRefMut<T, P, S, I, Initial>) -> R,
+    >(
+        self,
+        loc: LocF,
+        callback: C,
+    ) -> SolverType!(EventsOnLoc => (EventsOnLoc::Output::<DedupLocF<T, LocCallback<LocF, (ControlFn<crate::state::StateFn<T, P, R, C, true>>)>>>))
+    where
+        Initial: InitialCondition<T, P>,
+    {
+        solver_set!(self, events_on_loc: events_on_loc.append(
+            DedupLocF {
+                last_call: None,
+                loc_f: LocCallback(loc, ControlFn(crate::StateFn::new_mut(callback))),
+            })
+        )
+    }
+
+    /// Adds a state-mutating event like [Solver::on_mut], which detects [Zeno] behavior (e.g.
+    /// accumulation of bounces of a ball) and responds to it with `response` in place of the
+    /// `callback`. See also [Solver::on_mut_zeno_with].
+    #[allow(unused_parens)]
+    pub fn on_mut_zeno<
+        LocF: Locate<T, P, S, I, Initial>,
+        C: FnMut(&mut crate::StateRefMut<T, P, S, I, Initial>),
+    >(
+        self,
+        loc: LocF,
+        callback: C,
+        zeno: Zeno<T>,
+        response: ZenoResponse,
+    ) -> SolverType!(EventsOnLoc => (EventsOnLoc::Output::<ZenoLocF<T, LocCallback<LocF, crate::state::StateFn<T, P, (), C, true>>, ZenoResponse>>))
+    where
+        Initial: InitialCondition<T, P>,
+    {
+        solver_set!(self, events_on_loc: events_on_loc.append(
+            ZenoLocF::new(zeno, LocCallback(loc, crate::StateFn::new_mut(callback)), response)
+        ))
+    }
+
+    /// Adds a state-mutating event like [Solver::on_mut], which detects [Zeno] behavior and
+    /// calls `zeno_callback` in place of the `callback`, for instance, to switch to a sliding or
+    /// resting mode.
+    #[allow(unused_parens)]
+    pub fn on_mut_zeno_with<
         LocF: Locate<T, P, S, I, Initial>,
         C: FnMut(&mut crate::StateRefMut<T, P, S, I, Initial>),
+        ZenoC: FnMut(&mut crate::StateRefMut<T, P, S, I, Initial>),
     >(
         self,
         loc: LocF,
         callback: C,
-    ) -> SolverType!(EventsOnLoc => (EventsOnLoc::Output::<DedupLocF<T, LocCallback<LocF, (crate::state::StateFn<T, P, (), C, true>)>>>))
+        zeno: Zeno<T>,
+        zeno_callback: ZenoC,
+    ) -> SolverType!(EventsOnLoc => (EventsOnLoc::Output::<ZenoLocF<T, LocCallback<LocF, crate::state::StateFn<T, P, (), C, true>>, crate::state::StateFn<T, P, (), ZenoC, true>>>))
+    where
+        Initial: InitialCondition<T, P>,
+    {
+        solver_set!(self, events_on_loc: events_on_loc.append(
+            ZenoLocF::new(zeno, LocCallback(loc, crate::StateFn::new_mut(callback)), crate::StateFn::new_mut(zeno_callback))
+        ))
+    }
+
+    /// Adds a [GridEvent], which calls the `callback` with the state evaluated at each of the
+    /// `times` by the dense output, as the integration passes it. The steps are not adjusted to
+    /// the `times`, which are to be ordered in the direction of integration. At the time of a
+    /// located event, the state after the event is passed.
+    #[allow(unused_parens)]
+    pub fn on_grid<
+        Times: IntoIterator<Item = T>,
+        C: FnMut(&crate::StateRef<T, P, S, I, Initial>),
+    >(
+        self,
+        times: Times,
+        callback: C,
+    ) -> SolverType!(EventsOnStep => EventsOnStep::Output::<(GridEvent<T, Times::IntoIter, crate::state::StateFn<T, P, (), C>>)>)
+    {
+        solver_set!(self, events_on_step: events_on_step.append(GridEvent::new(times.into_iter(), crate::StateFn::new(callback))))
+    }
+
+    /// Adds a [GridEvent] on the [UniformGrid] with the step `dt` from the start of the run in
+    /// its direction, which are taken when the integration starts, see [Solver::on_grid].
+    #[allow(unused_parens)]
+    pub fn on_uniform<C: FnMut(&crate::StateRef<T, P, S, I, Initial>)>(
+        self,
+        dt: T,
+        callback: C,
+    ) -> SolverType!(EventsOnStep => EventsOnStep::Output::<(GridEvent<T, UniformGrid<T>, crate::state::StateFn<T, P, (), C>>)>)
+    {
+        solver_set!(self, events_on_step: events_on_step.append(GridEvent::new(UniformGrid::new(dt), crate::StateFn::new(callback))))
+    }
+
+    /// Adds a [ProgressEvent], which passes [Progress] (the fraction of the integration interval
+    /// covered, the current stepsize, etc.) to the `callback` after a step, at most once in
+    /// `every` wall-clock time, and also at the start and at the end of the integration,
+    /// including the end by a stop, a limit or cancellation (by the `on_stop` event). The
+    /// fraction is computed for the interval of the run at the moment of the report, see
+    /// [crate::Stepper::t_end].
+    #[allow(unused_parens)]
+    pub fn on_progress<C: FnMut(Progress<T>)>(
+        self,
+        every: std::time::Duration,
+        callback: C,
+    ) -> SolverType!(EventsOnStep => EventsOnStep::Output::<(ProgressEvent<C>)>, EventsOnStop => EventsOnStop::Output::<(ProgressEvent<C>)>)
+    {
+        let (on_step, on_stop) = ProgressEvent::new(every, callback);
+        let solver = solver_set!(self, events_on_step: events_on_step.append(on_step));
+        solver_set!(solver, events_on_stop: events_on_stop.append(on_stop))
+    }
+
+    /// Adds a [Checkpoint] event, which passes the state to the `callback` at the start of the
+    /// integration and after every `every` steps.
+    #[allow(unused_parens)]
+    pub fn checkpoint_every<C: FnMut(&crate::state::State<T, P, S, I, Initial>)>(
+        self,
+        every: usize,
+        callback: C,
+    ) -> SolverType!(EventsOnStep => EventsOnStep::Output::<(Checkpoint<C>)>) {
+        solver_set!(self, events_on_step: events_on_step.append(Checkpoint::new(every, callback)))
+    }
+
+    /// Adds a [Checkpoint] event, which passes the state to the `callback` whenever `loc` is
+    /// triggered, for instance, on [crate::Periodic] schedule.
+    #[allow(unused_parens)]
+    pub fn checkpoint_on<
+        LocF: Locate<T, P, S, I, Initial>,
+        C: FnMut(&crate::state::State<T, P, S, I, Initial>),
+    >(
+        self,
+        loc: LocF,
+        callback: C,
+    ) -> SolverType!(EventsOnLoc => (EventsOnLoc::Output::<DedupLocF<T, LocCallback<LocF, (Checkpoint<C>)>>>))
     where
         Initial: InitialCondition<T, P>,
     {
         solver_set!(self, events_on_loc: events_on_loc.append(
             DedupLocF {
                 last_call: None,
-                loc_f: LocCallback(loc, crate::StateFn::new_mut(callback)),
+                loc_f: LocCallback(loc, Checkpoint::new(1, callback)),
             })
         )
     }
@@ -260,7 +831,10 @@ impl<
         self.with_delayed_argument(move |s| s.t - delay, smoothing_order)
     }
 
-    pub fn run(mut self) -> crate::state::State<T, P, S, I, Initial>
+    /// Runs the integration. Panics on any [SolverError], see [Solver::try_run]. The reason of
+    /// the end of the integration is recorded in [crate::state::State::termination] of the
+    /// returned state.
+    pub fn run(self) -> crate::state::State<T, P, S, I, Initial>
     where
         Equation: crate::state::EvalState<T, P, S, I, Initial, P>,
         Interval: crate::interval::IntegrationInterval<T>,
@@ -272,44 +846,251 @@ impl<
         EventsOnLoc: crate::loc::loc_hlist::HListLocateEarliest<T, P, S, I, Initial>
             + crate::state::EvalMutStateFnHList<T, P, S, I, Initial, ()>,
     {
-        let t_init = self.interval.start_bound();
-        let t_end = self.interval.end_bound();
+        self.try_run().unwrap_or_else(|error| panic!("{error}"))
+    }
+
+    /// Runs the integration, returning the final state, or the first [SolverError] encountered.
+    pub fn try_run(self) -> Result<crate::state::State<T, P, S, I, Initial>, SolverError<T>>
+    where
+        Equation: crate::state::EvalState<T, P, S, I, Initial, P>,
+        Interval: crate::interval::IntegrationInterval<T>,
+        Initial: crate::initial_condition::InitialCondition<T, P>,
+        Stepsize: StepsizeController<T, P>,
+        EventsOnStart: crate::state::EvalMutStateFnHList<T, P, S, I, Initial, ()>,
+        EventsOnStep: crate::state::EvalMutStateFnHList<T, P, S, I, Initial, ()>,
+        EventsOnStop: crate::state::EvalMutStateFnHList<T, P, S, I, Initial, ()>,
+        EventsOnLoc: crate::loc::loc_hlist::HListLocateEarliest<T, P, S, I, Initial>
+            + crate::state::EvalMutStateFnHList<T, P, S, I, Initial, ()>,
+    {
+        self.check_tstops(self.interval.start_bound())?;
+        self.into_stepper().run()
+    }
+
+    /// Runs the integration, keeping the whole history (see [Solver::keep_history]), and returns
+    /// the [crate::Solution], which evaluates the solution at any time of the integration
+    /// interval. Panics on any [SolverError], see [Solver::try_solve].
+    pub fn solve(self) -> crate::Solution<T, P, S, I, Initial>
+    where
+        Equation: crate::state::EvalState<T, P, S, I, Initial, P>,
+        Interval: crate::interval::IntegrationInterval<T>,
+        Initial: crate::initial_condition::InitialCondition<T, P>,
+        Stepsize: StepsizeController<T, P>,
+        EventsOnStart: crate::state::EvalMutStateFnHList<T, P, S, I, Initial, ()>,
+        EventsOnStep: crate::state::EvalMutStateFnHList<T, P, S, I, Initial, ()>,
+        EventsOnStop: crate::state::EvalMutStateFnHList<T, P, S, I, Initial, ()>,
+        EventsOnLoc: crate::loc::loc_hlist::HListLocateEarliest<T, P, S, I, Initial>
+            + crate::state::EvalMutStateFnHList<T, P, S, I, Initial, ()>,
+    {
+        self.try_solve().unwrap_or_else(|error| panic!("{error}"))
+    }
+
+    /// Runs the integration like [Solver::solve], returning the [crate::Solution], or the first
+    /// [SolverError] encountered.
+    pub fn try_solve(self) -> Result<crate::Solution<T, P, S, I, Initial>, SolverError<T>>
+    where
+        Equation: crate::state::EvalState<T, P, S, I, Initial, P>,
+        Interval: crate::interval::IntegrationInterval<T>,
+        Initial: crate::initial_condition::InitialCondition<T, P>,
+        Stepsize: StepsizeController<T, P>,
+        EventsOnStart: crate::state::EvalMutStateFnHList<T, P, S, I, Initial, ()>,
+        EventsOnStep: crate::state::EvalMutStateFnHList<T, P, S, I, Initial, ()>,
+        EventsOnStop: crate::state::EvalMutStateFnHList<T, P, S, I, Initial, ()>,
+        EventsOnLoc: crate::loc::loc_hlist::HListLocateEarliest<T, P, S, I, Initial>
+            + crate::state::EvalMutStateFnHList<T, P, S, I, Initial, ()>,
+    {
+        self.keep_history()
+            .try_run()
+            .map(crate::state::State::into_solution)
+    }
 
-        let mut rhs = self.equation;
+    /// Continues the integration from the `state` returned by a previous run, up to the end of
+    /// the integration interval. Panics on any [SolverError], see [Solver::try_resume].
+    pub fn resume(
+        self,
+        state: crate::state::State<T, P, S, I, Initial>,
+    ) -> crate::state::State<T, P, S, I, Initial>
+    where
+        Equation: crate::state::EvalState<T, P, S, I, Initial, P>,
+        Interval: crate::interval::IntegrationInterval<T>,
+        Initial: crate::initial_condition::InitialCondition<T, P>,
+        Stepsize: StepsizeController<T, P>,
+        EventsOnStart: crate::state::EvalMutStateFnHList<T, P, S, I, Initial, ()>,
+        EventsOnStep: crate::state::EvalMutStateFnHList<T, P, S, I, Initial, ()>,
+        EventsOnStop: crate::state::EvalMutStateFnHList<T, P, S, I, Initial, ()>,
+        EventsOnLoc: crate::loc::loc_hlist::HListLocateEarliest<T, P, S, I, Initial>
+            + crate::state::EvalMutStateFnHList<T, P, S, I, Initial, ()>,
+    {
+        self.try_resume(state)
+            .unwrap_or_else(|error| panic!("{error}"))
+    }
+
+    /// Continues the integration from the `state` returned by a previous run, returning the
+    /// final state, or the first [SolverError] encountered, including
+    /// [SolverError::ReversedInterval] if the end of the interval is before the current time of
+    /// `state` in the direction of its history. See [Solver::into_stepper_from].
+    pub fn try_resume(
+        self,
+        state: crate::state::State<T, P, S, I, Initial>,
+    ) -> Result<crate::state::State<T, P, S, I, Initial>, SolverError<T>>
+    where
+        Equation: crate::state::EvalState<T, P, S, I, Initial, P>,
+        Interval: crate::interval::IntegrationInterval<T>,
+        Initial: crate::initial_condition::InitialCondition<T, P>,
+        Stepsize: StepsizeController<T, P>,
+        EventsOnStart: crate::state::EvalMutStateFnHList<T, P, S, I, Initial, ()>,
+        EventsOnStep: crate::state::EvalMutStateFnHList<T, P, S, I, Initial, ()>,
+        EventsOnStop: crate::state::EvalMutStateFnHList<T, P, S, I, Initial, ()>,
+        EventsOnLoc: crate::loc::loc_hlist::HListLocateEarliest<T, P, S, I, Initial>
+            + crate::state::EvalMutStateFnHList<T, P, S, I, Initial, ()>,
+    {
+        self.check_tstops(state.t_curr)?;
+        self.check_direction(&state)?;
+        self.into_stepper_from(state).run()
+    }
+
+    /// Initializes the integration, evaluating `on_start` and `on_step` events for the initial
+    /// state, and returns [Stepper], which makes steps on demand. If the end of the integration
+    /// interval is less than its start, the integration goes backward in time.
+    pub fn into_stepper(
+        mut self,
+    ) -> Stepper<T, P, S, I, Equation, Initial, Stepsize, EventsOnStep, EventsOnStop, EventsOnLoc>
+    where
+        Interval: crate::interval::IntegrationInterval<T>,
+        Initial: crate::initial_condition::InitialCondition<T, P>,
+        Stepsize: StepsizeController<T, P>,
+        EventsOnStart: crate::state::EvalMutStateFnHList<T, P, S, I, Initial, ()>,
+        EventsOnStep: crate::state::EvalMutStateFnHList<T, P, S, I, Initial, ()>,
+    {
         let mut state = crate::state::State::new(
-            t_init,
+            self.interval.start_bound(),
             self.max_delay,
             self.initial,
             self.initial_disco.into(),
             self.rk,
         );
+        state.history.backward = self.interval.end_bound() < self.interval.start_bound();
+        state.history.keep_all = self.keep_history;
+        state.newton = self.newton;
+        if let Some(newton) = &mut state.newton {
+            newton.set_tolerances(self.stepsize.tolerances());
+        }
+        state.stiffness = self.stiffness;
+        state.rk_switch = self.rk_switch;
+        state.multistep = self.multistep;
 
-        let mut stepsize = self.stepsize;
-
+        let tstops = Self::upcoming_tstops(self.tstops, state.t_curr, state.history.backward);
+        let rhs_evals = state.stats.rhs_evals;
+        let t_start = state.t_curr;
+        state.run_interval = Some((t_start, self.interval.end_bound()));
+        // the history errors are reported by the first step
+        state.history.record_errors = true;
         self.events_on_start.eval_mut(&mut state);
         self.events_on_step.eval_mut(&mut state);
+        state.history.record_errors = false;
+        state.take_control(None);
 
-        while state.t_curr < t_end {
-            state.make_step(&mut rhs, stepsize.get().min(t_end - state.t_curr));
-            while stepsize.update(&state.e_curr) == StepStatus::Rejected {
-                state.undo_step();
-                state.make_step(&mut rhs, stepsize.get().min(t_end - state.t_curr));
-            }
-
-            if let Some((index, time)) = self.events_on_loc.locate_earliest(&state)
-                && time >= state.t_prev
-            {
-                state.undo_step();
-                state.make_step(&mut rhs, time - state.t_curr);
-                state.commit_step();
-                state.make_zero_step();
-                self.events_on_loc.eval_mut_at_index(&mut state, index);
-            }
-            state.commit_step();
-            self.events_on_step.eval_mut(&mut state);
+        Stepper {
+            state,
+            equation: self.equation,
+            stepsize: self.stepsize,
+            t_start,
+            t_end: self.interval.end_bound(),
+            event_tolerance: self.event_tolerance,
+            events_on_step: self.events_on_step,
+            events_on_stop: self.events_on_stop,
+            events_on_loc: self.events_on_loc,
+            step_count: 0,
+            tstops,
+            tstops_disco: self.tstops_disco,
+            limits: self.limits,
+            cancel_token: self.cancel_token,
+            start_time: std::time::Instant::now(),
+            start_rhs_evals: rhs_evals,
+        }
+    }
+    /// Continues the integration from the `state` returned by a previous run, evaluating
+    /// `on_start` events, and returns [Stepper], which makes steps on demand.
+    ///
+    /// The history of `state` (including tracked discontinuities), its Runge-Kutta method (with
+    /// [crate::state::State::newton], [crate::state::State::stiffness] and
+    /// [crate::state::State::multistep], unless they are lost in serialization, and
+    /// [crate::state::State::rk_switch]) and the stepsize
+    /// proposed at the end of the previous run (see [StepsizeController::resume]) are kept,
+    /// while the equation and events are those of the solver. The initial condition,
+    /// Runge-Kutta method and the start of the interval of the solver are ignored, and the
+    /// history is kept for at least [Solver::max_delay] from now on. The integration continues
+    /// even if the previous one was stopped, see [crate::state::State::termination].
+    ///
+    /// The direction of the integration is that of the history of `state`, unless no steps are
+    /// made yet, in which case it is set by the end of the interval, as for
+    /// [Solver::into_stepper].
+    ///
+    /// # Panics
+    /// Panics if the end of the interval is before the current time of `state` in the direction
+    /// of its history, see [SolverError::ReversedInterval].
+    pub fn into_stepper_from(
+        mut self,
+        mut state: crate::state::State<T, P, S, I, Initial>,
+    ) -> Stepper<T, P, S, I, Equation, Initial, Stepsize, EventsOnStep, EventsOnStop, EventsOnLoc>
+    where
+        Interval: crate::interval::IntegrationInterval<T>,
+        Initial: crate::initial_condition::InitialCondition<T, P>,
+        Stepsize: StepsizeController<T, P>,
+        EventsOnStart: crate::state::EvalMutStateFnHList<T, P, S, I, Initial, ()>,
+    {
+        if let Err(error) = self.check_direction(&state) {
+            panic!("{error}");
         }
-        self.events_on_stop.eval_mut(&mut state);
+        let t_end = self.interval.end_bound();
+        if state.t_curr == state.history.t_init {
+            state.history.backward = t_end < state.t_curr;
+        }
+        state.history.t_span = state.history.t_span.max(self.max_delay);
+        state.history.keep_all |= self.keep_history;
+        if let Some(stepsize) = state.next_stepsize {
+            self.stepsize.resume(stepsize);
+        }
+        state.termination = None;
+        if state.newton.is_none() {
+            state.newton = self.newton;
+        }
+        if let Some(newton) = &mut state.newton {
+            newton.set_tolerances(self.stepsize.tolerances());
+        }
+        if state.stiffness.is_none() {
+            state.stiffness = self.stiffness;
+        }
+        if state.multistep.is_none() {
+            state.multistep = self.multistep;
+        }
+        let tstops = Self::upcoming_tstops(self.tstops, state.t_curr, state.history.backward);
+        let rhs_evals = state.stats.rhs_evals;
+        let t_start = state.t_curr;
+        state.run_interval = Some((t_start, t_end));
+        // the derivative at the current point is recomputed, since the equation may be changed
+        state.make_zero_step();
+        state.history.record_errors = true;
+        self.events_on_start.eval_mut(&mut state);
+        state.history.record_errors = false;
+        state.take_control(None);
 
-        state
+        Stepper {
+            state,
+            equation: self.equation,
+            stepsize: self.stepsize,
+            t_start,
+            t_end,
+            event_tolerance: self.event_tolerance,
+            events_on_step: self.events_on_step,
+            events_on_stop: self.events_on_stop,
+            events_on_loc: self.events_on_loc,
+            step_count: 0,
+            tstops,
+            tstops_disco: self.tstops_disco,
+            limits: self.limits,
+            cancel_token: self.cancel_token,
+            start_time: std::time::Instant::now(),
+            start_rhs_evals: rhs_evals,
+        }
     }
 }
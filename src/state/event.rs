@@ -1,7 +1,18 @@
+use std::{
+    sync::{Arc, Mutex},
+    time::{Duration, Instant},
+};
+
 use nalgebra::RealField;
 
-use crate::{initial_condition::InitialCondition, state::EvalMutState, traits::RealVectorSpace};
+use crate::{
+    control::Control,
+    initial_condition::InitialCondition,
+    state::{EvalMutState, EvalState},
+    traits::RealVectorSpace,
+};
 
+/// Event, which stops the integration, as returning [Control::Stop] with an empty reason does.
 pub struct StopIntegration();
 
 impl<
@@ -13,7 +24,242 @@ impl<
 > EvalMutState<T, Y, S, I, IC, ()> for StopIntegration
 {
     fn eval_mut(&mut self, state: &mut super::State<T, Y, S, I, IC>) {
-        state.make_zero_step();
-        state.t_curr = T::max_value().unwrap_or(T::from_f64(f64::INFINITY).unwrap())
+        state.control.request(Control::Stop(String::new()))
+    }
+}
+
+/// Event, which passes the whole [super::State] to the callback on the first call and every
+/// `every`-th call after it, for instance, to save a snapshot of the integration, which can be continued by
+/// [crate::Solver::resume]. See [crate::Solver::checkpoint_every] and
+/// [crate::Solver::checkpoint_on].
+pub struct Checkpoint<F> {
+    pub every: usize,
+    pub count: usize,
+    pub callback: F,
+}
+
+impl<F> Checkpoint<F> {
+    pub fn new(every: usize, callback: F) -> Self {
+        assert!(every > 0, "Checkpoint period must be positive.");
+        Self {
+            every,
+            count: 0,
+            callback,
+        }
+    }
+}
+
+impl<
+    T: RealField + Copy,
+    Y: RealVectorSpace<T>,
+    const S: usize,
+    const I: usize,
+    IC: InitialCondition<T, Y>,
+    F: FnMut(&super::State<T, Y, S, I, IC>),
+> EvalMutState<T, Y, S, I, IC, ()> for Checkpoint<F>
+{
+    fn eval_mut(&mut self, state: &mut super::State<T, Y, S, I, IC>) {
+        if self.count.is_multiple_of(self.every) {
+            (self.callback)(state)
+        }
+        self.count += 1;
+    }
+}
+
+/// Progress of the integration, passed to the callback of [crate::Solver::on_progress].
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub struct Progress<T> {
+    /// Current time
+    pub t: T,
+    /// Fraction of the integration interval covered, from `0` to `1`
+    pub fraction: T,
+    /// Stepsize proposed by the stepsize controller for the next step (`None` before the first
+    /// step), see [super::State::next_stepsize]
+    pub stepsize: Option<T>,
+    /// Number of accepted steps, see [crate::SolveStats::accepted_steps]
+    pub steps: usize,
+}
+
+/// Reporter of [Progress], which is shared by the [ProgressEvent]s after the steps and at the
+/// end of the integration, see [crate::Solver::on_progress].
+pub struct ProgressReporter<F> {
+    pub every: Duration,
+    pub last_report: Option<Instant>,
+    /// Number of accepted steps at the last report
+    pub last_steps: Option<usize>,
+    pub callback: F,
+}
+
+/// Event, which passes [Progress] to the callback after a step, at most once in `every` time
+/// (but always at the start and at the end of the integration interval), or at the end of the
+/// integration (`on_stop`), unless the last step is reported already. The fraction is computed
+/// for [super::State::run_interval] at the moment of the report. See
+/// [crate::Solver::on_progress].
+pub struct ProgressEvent<F> {
+    pub reporter: Arc<Mutex<ProgressReporter<F>>>,
+    pub on_stop: bool,
+}
+
+impl<F> ProgressEvent<F> {
+    /// Returns the events after the steps and at the end of the integration, which share the
+    /// `callback`.
+    pub fn new(every: Duration, callback: F) -> (Self, Self) {
+        let reporter = Arc::new(Mutex::new(ProgressReporter {
+            every,
+            last_report: None,
+            last_steps: None,
+            callback,
+        }));
+        (
+            Self {
+                reporter: reporter.clone(),
+                on_stop: false,
+            },
+            Self {
+                reporter,
+                on_stop: true,
+            },
+        )
+    }
+}
+
+impl<
+    T: RealField + Copy,
+    Y: RealVectorSpace<T>,
+    const S: usize,
+    const I: usize,
+    IC: InitialCondition<T, Y>,
+    F: FnMut(Progress<T>),
+> EvalMutState<T, Y, S, I, IC, ()> for ProgressEvent<F>
+{
+    fn eval_mut(&mut self, state: &mut super::State<T, Y, S, I, IC>) {
+        let reporter = &mut *self.reporter.lock().unwrap();
+        let steps = state.stats.accepted_steps;
+        let (t_start, t_end) = state.run_interval.unwrap_or((state.t_curr, state.t_curr));
+        let fraction = if t_end == t_start {
+            T::one()
+        } else {
+            ((state.t_curr - t_start) / (t_end - t_start)).clamp(T::zero(), T::one())
+        };
+        let due = if self.on_stop {
+            reporter.last_steps != Some(steps)
+        } else {
+            fraction == T::one()
+                || reporter
+                    .last_report
+                    .is_none_or(|last_report| last_report.elapsed() >= reporter.every)
+        };
+        if due {
+            reporter.last_report = Some(Instant::now());
+            reporter.last_steps = Some(steps);
+            (reporter.callback)(Progress {
+                t: state.t_curr,
+                fraction,
+                stepsize: state.next_stepsize,
+                steps,
+            })
+        }
+    }
+}
+
+/// Times of a [GridEvent], which are to be ordered in the direction of integration. Any
+/// iterator over the times is a grid, and [UniformGrid] places its times from the start of the
+/// run.
+pub trait GridTimes<T> {
+    /// Returns the next time of the grid for the run over the interval from `start` to `end`, see
+    /// [super::State::run_interval].
+    fn next_time(&mut self, start: T, end: T) -> Option<T>;
+}
+
+impl<T, Times: Iterator<Item = T>> GridTimes<T> for Times {
+    fn next_time(&mut self, _start: T, _end: T) -> Option<T> {
+        self.next()
+    }
+}
+
+/// Event, which calls the callback with the state evaluated by the dense output at each of the
+/// `times`, after the step which passes it, see [crate::Solver::on_grid].
+pub struct GridEvent<T, Times, F> {
+    pub times: Times,
+    /// The time of the grid, which is not yet passed by the integration.
+    pub next: Option<T>,
+    pub callback: F,
+}
+
+impl<T, Times: GridTimes<T>, F> GridEvent<T, Times, F> {
+    pub fn new(times: Times, callback: F) -> Self {
+        Self {
+            times,
+            next: None,
+            callback,
+        }
+    }
+}
+
+impl<
+    T: RealField + Copy,
+    Y: RealVectorSpace<T>,
+    const S: usize,
+    const I: usize,
+    IC: InitialCondition<T, Y>,
+    Times: GridTimes<T>,
+    F: EvalState<T, Y, S, I, IC, ()>,
+> EvalMutState<T, Y, S, I, IC, ()> for GridEvent<T, Times, F>
+{
+    fn eval_mut(&mut self, state: &mut super::State<T, Y, S, I, IC>) {
+        let (start, end) = state.run_interval.unwrap_or((state.t_curr, state.t_curr));
+        while let Some(t) = self
+            .next
+            .take()
+            .or_else(|| self.times.next_time(start, end))
+        {
+            if !state.history.precedes(t, state.t_curr) {
+                self.next = Some(t);
+                break;
+            }
+            if t == state.t_curr {
+                self.callback.eval_curr(state)
+            } else {
+                self.callback.eval_at(state, t)
+            }
+        }
+    }
+}
+
+/// Infinite uniform grid `start + k * step` for `k = 0, 1, 2, ...`, where `start` is the start of
+/// the run, and the sign of the `step` is that of the direction of the run, see
+/// [crate::Solver::on_uniform]. The start and the direction are resolved at the first evaluation,
+/// so the grid follows the interval of the run, which is set or resumed after the grid is created.
+#[derive(Clone, Copy, Debug)]
+pub struct UniformGrid<T> {
+    pub step: T,
+    /// The start and the signed step, once resolved.
+    pub origin: Option<(T, T)>,
+    pub index: usize,
+}
+
+impl<T: RealField + Copy> UniformGrid<T> {
+    pub fn new(step: T) -> Self {
+        Self {
+            step,
+            origin: None,
+            index: 0,
+        }
+    }
+}
+
+impl<T: RealField + Copy> GridTimes<T> for UniformGrid<T> {
+    fn next_time(&mut self, start: T, end: T) -> Option<T> {
+        let (start, step) = *self.origin.get_or_insert_with(|| {
+            let step = if end < start {
+                -self.step.abs()
+            } else {
+                self.step.abs()
+            };
+            (start, step)
+        });
+        let t = start + step * T::from_usize(self.index).unwrap();
+        self.index += 1;
+        Some(t)
     }
 }
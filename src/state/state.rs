@@ -2,12 +2,39 @@ use impl_tools::autoimpl;
 use nalgebra::RealField;
 
 use crate::{
-    initial_condition::InitialCondition, state::state_fn::EvalState, traits::RealVectorSpace,
+    control::{Control, Termination},
+    error::{HistoryError, SolverError},
+    implicit::Newton,
+    initial_condition::InitialCondition,
+    multistep::{self, Multistep},
+    rk::ButcherTableu,
+    state::state_fn::EvalState,
+    stats::SolveStats,
+    stiffness::Stiffness,
+    traits::RealVectorSpace,
+};
+use std::{
+    collections::VecDeque,
+    sync::{
+        OnceLock,
+        atomic::{AtomicUsize, Ordering},
+    },
 };
-use std::collections::VecDeque;
 
+/// Values of the state at the past steps, used to evaluate the solution at past times.
+///
+/// With `serde` feature, it can be serialized if the initial condition `IC` can, see
+/// [State::map_initial] otherwise.
 #[derive(Clone)]
 #[autoimpl(Debug ignore self.p_init where T: std::fmt::Debug, Y: std::fmt::Debug)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+#[cfg_attr(
+    feature = "serde",
+    serde(bound(
+        serialize = "T: serde::Serialize, Y: serde::Serialize, IC: serde::Serialize",
+        deserialize = "T: serde::Deserialize<'de>, Y: serde::Deserialize<'de>, IC: serde::Deserialize<'de>"
+    ))
+)]
 pub struct StateHistory<T: RealField, Y: RealVectorSpace<T>, const S: usize, const I: usize, IC> {
     pub t_span: T,
 
@@ -16,13 +43,57 @@ pub struct StateHistory<T: RealField, Y: RealVectorSpace<T>, const S: usize, con
 
     pub t_deque: VecDeque<T>,
     pub p_deque: VecDeque<Y>,
+    #[cfg_attr(
+        feature = "serde",
+        serde(with = "crate::util::serde_array::deque_of_arrays")
+    )]
     pub k_deque: VecDeque<[Y; S]>,
+    /// Dense output of the steps of multistep methods: the coefficients of the polynomial of
+    /// each step, see [crate::multistep::Multistep]. It is empty for the steps of Runge-Kutta
+    /// methods, which are evaluated with [StateHistory::k_deque].
+    pub polynomials: VecDeque<Vec<Y>>,
     pub disco_deque: VecDeque<(T, usize)>,
 
     pub rk: crate::rk::ButcherTableu<T, S, I>,
+
+    /// Switches of the method by [crate::Solver::auto_switch]: the times, from which the steps
+    /// are made by the method, in the direction of integration. The steps before the first
+    /// switch are made by [StateHistory::rk].
+    pub rk_switches: VecDeque<(T, ButcherTableu<T, S, I>)>,
+
+    /// Whether the integration goes backward in time, in which case the times in
+    /// [StateHistory::t_deque] and [StateHistory::disco_deque] are decreasing.
+    pub backward: bool,
+
+    /// Whether the whole history is kept, regardless of [StateHistory::t_span], see
+    /// [crate::Solver::keep_history].
+    pub keep_all: bool,
+
+    /// Whether [StateHistory::eval] records the errors in [StateHistory::error] instead of
+    /// panicking. It is set by [crate::Stepper] during the steps, and during the evaluation of
+    /// `on_start`, `on_stop` events and [crate::Stepper::modify].
+    #[cfg_attr(feature = "serde", serde(skip))]
+    pub(crate) record_errors: bool,
+
+    /// The first error encountered by [StateHistory::eval], which is reported by the solver
+    /// right after the evaluation, in which it occurred, and cleared when the step is undone.
+    #[cfg_attr(feature = "serde", serde(skip))]
+    pub(crate) error: OnceLock<HistoryError<T>>,
 }
 
-#[autoimpl(Debug ignore self.history, self.rk, self.k_curr where T: std::fmt::Debug, Y: std::fmt::Debug)]
+/// State of the integration: the current step and [StateHistory].
+///
+/// With `serde` feature, it can be serialized if the initial condition `IC` can, see
+/// `State::snapshot` and [State::map_initial] otherwise.
+#[autoimpl(Debug ignore self.history, self.rk, self.k_curr, self.newton, self.rk_switch, self.multistep where T: std::fmt::Debug, Y: std::fmt::Debug)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+#[cfg_attr(
+    feature = "serde",
+    serde(bound(
+        serialize = "T: serde::Serialize, Y: serde::Serialize, IC: serde::Serialize",
+        deserialize = "T: serde::Deserialize<'de>, Y: serde::Deserialize<'de>, IC: serde::Deserialize<'de>"
+    ))
+)]
 pub struct State<T: RealField + Copy, Y: RealVectorSpace<T>, const S: usize, const I: usize, IC> {
     pub history: StateHistory<T, Y, S, I, IC>,
 
@@ -38,7 +109,57 @@ pub struct State<T: RealField + Copy, Y: RealVectorSpace<T>, const S: usize, con
     pub e_curr: Y,
 
     pub rk: crate::rk::ButcherTableu<T, S, I>,
+    #[cfg_attr(feature = "serde", serde(with = "crate::util::serde_array::array"))]
     pub k_curr: [Y; S],
+
+    pub stats: SolveStats<T>,
+
+    /// Stepsize proposed by the stepsize controller for the next step, recorded after each
+    /// step for [crate::Solver::resume].
+    pub next_stepsize: Option<T>,
+
+    /// Time at which Zeno behavior was detected by an event with
+    /// [crate::loc::zeno::ZenoResponse::Error] and the index of the event (set by
+    /// [State::take_control]), which are reported by the solver after the step.
+    pub zeno: Option<(T, Option<usize>)>,
+
+    /// Control flow request of the evaluated callbacks, which is handled by the solver after
+    /// them, see [State::take_control].
+    pub control: Control,
+
+    /// Reason of the end of the integration, set by the solver. It is `None` while the
+    /// integration is in progress, or if [crate::Stepper::finish] is called before the end.
+    pub termination: Option<Termination<T>>,
+
+    /// Start and end of the integration interval of the current run, set by [crate::Stepper]
+    /// before the events are evaluated (the end may be changed with [crate::Stepper::t_end]), see
+    /// [super::ProgressEvent].
+    #[cfg_attr(feature = "serde", serde(skip))]
+    pub run_interval: Option<(T, T)>,
+
+    /// Newton iterations of implicit Runge-Kutta methods, see [crate::Solver::implicit_rk]. It
+    /// is not serialized, and it is restored by [crate::Solver::into_stepper_from].
+    #[cfg_attr(feature = "serde", serde(skip))]
+    pub newton: Option<Newton<T, Y>>,
+
+    /// Stiffness detection, see [crate::Solver::stiffness_detection]. It is not serialized, and
+    /// it is restored by [crate::Solver::into_stepper_from].
+    #[cfg_attr(feature = "serde", serde(skip))]
+    pub stiffness: Option<Stiffness<T, Y>>,
+
+    /// Method, to which the integration switches, when the stiffness detection requests it, see
+    /// [crate::Solver::auto_switch].
+    pub rk_switch: Option<ButcherTableu<T, S, I>>,
+
+    /// Multistep method, which replaces [State::rk], see [crate::Solver::adams]. It is not
+    /// serialized, and it is restored by [crate::Solver::into_stepper_from].
+    #[cfg_attr(feature = "serde", serde(skip))]
+    pub multistep: Option<Multistep<T, Y>>,
+
+    /// Iterations of the event location methods since the last committed step, which are
+    /// counted through a shared reference to the state, see [State::count_location_iteration].
+    #[cfg_attr(feature = "serde", serde(skip))]
+    pub(crate) location_iterations: AtomicUsize,
 }
 
 impl<
@@ -67,72 +188,193 @@ impl<
             e_curr: Y::zero(),
             rk,
             k_curr: [Y::zero(); S],
+            stats: SolveStats::default(),
+            next_stepsize: None,
+            zeno: None,
+            control: Control::Continue,
+            termination: None,
+            run_interval: None,
+            newton: None,
+            stiffness: None,
+            rk_switch: None,
+            multistep: None,
+            location_iterations: AtomicUsize::new(0),
             history: StateHistory {
                 rk,
+                rk_switches: VecDeque::new(),
                 t_init,
                 t_span,
                 p_init,
                 t_deque: VecDeque::from([t_init]),
                 p_deque: VecDeque::from([p]),
                 k_deque: VecDeque::new(),
+                polynomials: VecDeque::new(),
                 disco_deque: disco_init,
+                backward: false,
+                keep_all: false,
+                record_errors: false,
+                error: OnceLock::new(),
             },
         }
     }
 
     pub fn eval<const D: usize>(&self, t: T) -> Y {
-        if t >= self.t_prev && t <= self.t_curr {
+        self.try_eval::<D>(t)
+            .unwrap_or_else(|error| self.history.recover::<D>(error))
+    }
+
+    pub fn try_eval<const D: usize>(&self, t: T) -> Result<Y, HistoryError<T>> {
+        if !self.in_step(t) {
+            self.history.try_eval::<D>(t)
+        } else if !self.has_derivative(D) {
+            Err(HistoryError::UnsupportedDerivative { t, order: D })
+        } else {
             let t_step = self.t_curr - self.t_prev;
             let theta = (t - self.t_prev) / t_step;
-            self.rk
-                .dense_output::<D, Y>(&self.p_prev, t_step, theta, &self.k_curr)
-        } else {
-            self.history.eval::<D>(t)
+            Ok(match &self.multistep {
+                Some(multistep) => multistep::dense_output::<D, T, Y>(
+                    &self.p_prev,
+                    t_step,
+                    theta,
+                    &multistep.polynomial,
+                ),
+                None => self
+                    .rk
+                    .dense_output::<D, Y>(&self.p_prev, t_step, theta, &self.k_curr),
+            })
         }
     }
 
+    /// Returns `true` if `t` lies in the current step.
+    fn in_step(&self, t: T) -> bool {
+        self.history.precedes(self.t_prev, t) && self.history.precedes(t, self.t_curr)
+    }
+
+    /// Returns `true` if the dense output of the current step has the derivative of the given
+    /// `order`, which is at most 1 for Runge-Kutta methods.
+    fn has_derivative(&self, order: usize) -> bool {
+        order <= 1
+            || self
+                .multistep
+                .as_ref()
+                .is_some_and(|multistep| !multistep.polynomial.is_empty())
+    }
+
     pub fn make_step(&mut self, rhs: &mut impl EvalState<T, Y, S, I, IC, Y>, t_step: T) {
+        if self.multistep.is_some() {
+            return self.make_multistep_step(rhs, t_step);
+        }
+        if !self.rk.is_explicit() {
+            return self.make_implicit_step(rhs, t_step);
+        }
         if self.t_prev != self.t_curr {
             self.k_curr[0] = self.d_curr;
         } else {
             self.k_curr[0] = rhs.eval_curr(self);
+            self.stats.rhs_evals += 1;
         }
 
         self.t_prev = self.t_curr;
         self.p_prev = self.p_curr;
         self.d_prev = self.d_curr;
 
-        for i in 1..S {
+        let main = self.rk.main_stages();
+        let mut stages = [self.p_prev; S];
+        for (i, stage) in stages.iter_mut().enumerate().take(main).skip(1) {
             self.t_curr = self.t_prev + self.rk.c[i] * t_step;
             self.p_curr = self.p_prev
                 + (0..i).fold(Y::zero(), |acc, j| acc + self.k_curr[j] * self.rk.a[i][j]) * t_step;
             self.k_curr[i] = rhs.eval_curr(self);
+            *stage = self.p_curr;
+            if self.history.failed() {
+                return;
+            }
         }
 
-        self.p_curr = self.p_prev
-            + (0..S).fold(Y::zero(), |acc, j| acc + self.k_curr[j] * self.rk.b[j]) * t_step;
         self.t_curr = self.t_prev + t_step;
-        self.d_curr = rhs.eval_curr(self);
+        if main > 0 && self.rk.is_end_stage(main - 1) {
+            // the last stage is evaluated at the end of the step (first same as last)
+            self.p_curr = stages[main - 1];
+            self.d_curr = self.k_curr[main - 1];
+            self.stats.rhs_evals += main - 1;
+        } else {
+            self.p_curr = self.p_prev
+                + (0..S).fold(Y::zero(), |acc, j| acc + self.k_curr[j] * self.rk.b[j]) * t_step;
+            self.d_curr = rhs.eval_curr(self);
+            self.stats.rhs_evals += main;
+        }
 
         self.e_curr = (0..S).fold(Y::zero(), |acc, j| {
             acc + self.k_curr[j] * (self.rk.b2[j] - self.rk.b[j])
         }) * t_step;
+        self.estimate_stiffness(stages);
+    }
+
+    /// Evaluates the stages of the explicit method, which are used only by the dense output (see
+    /// [ButcherTableu::main_stages]), after the step is accepted. The stage, which is the
+    /// derivative at the end of the step, is not evaluated again.
+    pub fn make_dense_stages(&mut self, rhs: &mut impl EvalState<T, Y, S, I, IC, Y>) {
+        let main = self.rk.main_stages();
+        if self.multistep.is_some() || !self.rk.is_explicit() || main == S {
+            return;
+        }
+        let (t_curr, p_curr) = (self.t_curr, self.p_curr);
+        let t_step = t_curr - self.t_prev;
+        for i in main..S {
+            if self.rk.is_end_stage(i) {
+                self.k_curr[i] = self.d_curr;
+                continue;
+            }
+            self.t_curr = self.t_prev + self.rk.c[i] * t_step;
+            self.p_curr = self.p_prev
+                + (0..i).fold(Y::zero(), |acc, j| acc + self.k_curr[j] * self.rk.a[i][j]) * t_step;
+            self.k_curr[i] = rhs.eval_curr(self);
+            self.stats.rhs_evals += 1;
+            if self.history.failed() {
+                break;
+            }
+        }
+        (self.t_curr, self.p_curr) = (t_curr, p_curr);
+    }
+
+    /// Records an iteration of an event location method. The iterations are added to
+    /// [SolveStats::location_iterations] when the step is committed.
+    pub fn count_location_iteration(&self) {
+        self.location_iterations.fetch_add(1, Ordering::Relaxed);
     }
 
     pub fn commit_step(&mut self) {
+        if self.t_curr != self.t_prev {
+            self.stats.count_step((self.t_curr - self.t_prev).abs());
+        }
+        self.stats.location_iterations += std::mem::take(self.location_iterations.get_mut());
         self.history.t_deque.push_back(self.t_curr);
         self.history.p_deque.push_back(self.p_curr);
         self.history.k_deque.push_back(self.k_curr);
-        let t_tail = self.t_prev - self.history.t_span;
-        while let Some(second_t) = self.history.t_deque.get(1)
-            && *second_t < t_tail
+        let polynomial = self.multistep.as_ref().map(|m| m.polynomial.clone());
+        self.history
+            .polynomials
+            .push_back(polynomial.unwrap_or_default());
+        if self.history.keep_all {
+            return;
+        }
+        let t_tail = self.t_prev - self.history.direction() * self.history.t_span;
+        while let Some(&second_t) = self.history.t_deque.get(1)
+            && !self.history.precedes(t_tail, second_t)
         {
             self.history.t_deque.pop_front();
             self.history.p_deque.pop_front();
             self.history.k_deque.pop_front();
+            self.history.polynomials.pop_front();
+        }
+        while let Some(&(t_switch, rk)) = self.history.rk_switches.front()
+            && self.history.precedes(t_switch, self.history.t_deque[0])
+        {
+            self.history.rk = rk;
+            self.history.rk_switches.pop_front();
         }
-        while let Some((t, _order)) = self.history.disco_deque.front()
-            && t < &t_tail
+        while let Some(&(t, _order)) = self.history.disco_deque.front()
+            && !self.history.precedes(t_tail, t)
         {
             self.history.disco_deque.pop_front();
         }
@@ -147,6 +389,196 @@ impl<
         self.t_curr = self.t_prev;
         self.p_curr = self.p_prev;
         self.d_curr = self.d_prev;
+        self.history.error.take();
+    }
+
+    /// Returns the error recorded by [StateHistory::eval] during the step (clearing it),
+    /// [SolverError::Zeno] if [State::zeno] is set, or [SolverError::NonFinite] if the current
+    /// position is not finite. `step` is the number of committed steps, which is reported in the
+    /// error.
+    pub fn check(&mut self, step: usize) -> Result<(), SolverError<T>> {
+        self.check_history(step)?;
+        if let Some((t, event)) = self.zeno {
+            return Err(SolverError::Zeno { t, event, step });
+        }
+        // each component multiplied by zero is zero if it is finite, and NaN otherwise
+        if !(self.p_curr * T::zero()).is_zero() {
+            return Err(SolverError::NonFinite {
+                t: self.t_curr,
+                step,
+            });
+        }
+        Ok(())
+    }
+
+    /// Returns the error recorded by [StateHistory::eval] (clearing it). The solver calls it
+    /// after each stage evaluation, event location and callback of the step, so that the step
+    /// is aborted before anything else is computed from the fallback value.
+    pub fn check_history(&mut self, step: usize) -> Result<(), SolverError<T>> {
+        match self.history.error.take() {
+            Some(error) => Err(SolverError::History {
+                error,
+                t: self.t_curr,
+                step,
+            }),
+            None => Ok(()),
+        }
+    }
+
+    /// Takes the control flow request of the evaluated callbacks, resetting [State::control].
+    /// On [Control::Stop], sets [State::termination] (unless it is set already), attributing it
+    /// to the located event with the index `event`, which is also recorded for the detected
+    /// [State::zeno].
+    pub fn take_control(&mut self, event: Option<usize>) -> Control {
+        if let Some((_, zeno_event @ None)) = &mut self.zeno {
+            *zeno_event = event;
+        }
+        let control = std::mem::take(&mut self.control);
+        if let Control::Stop(reason) = &control
+            && self.termination.is_none()
+        {
+            self.termination = Some(Termination::Stopped {
+                event,
+                reason: reason.clone(),
+            });
+        }
+        control
+    }
+}
+
+impl<T: RealField + Copy, Y: RealVectorSpace<T>, const S: usize, const I: usize, IC>
+    State<T, Y, S, I, IC>
+{
+    /// Replaces the initial condition of the history. For instance, an initial condition that
+    /// cannot be serialized can be replaced with `()` before serialization, and restored after
+    /// deserialization:
+    /// ```ignore
+    /// let json = serde_json::to_string(&state.map_initial(|_| ()))?;
+    /// let state = serde_json::from_str::<State<_, _, 7, 5, ()>>(&json)?.map_initial(|()| initial);
+    /// ```
+    pub fn map_initial<NewIC>(self, f: impl FnOnce(IC) -> NewIC) -> State<T, Y, S, I, NewIC> {
+        let State {
+            history,
+            t_curr,
+            t_prev,
+            p_curr,
+            p_prev,
+            d_curr,
+            d_prev,
+            e_curr,
+            rk,
+            k_curr,
+            stats,
+            next_stepsize,
+            zeno,
+            control,
+            termination,
+            run_interval,
+            newton,
+            stiffness,
+            rk_switch,
+            multistep,
+            location_iterations,
+        } = self;
+        let StateHistory {
+            t_span,
+            t_init,
+            p_init,
+            t_deque,
+            p_deque,
+            k_deque,
+            polynomials,
+            disco_deque,
+            rk: history_rk,
+            rk_switches,
+            backward,
+            keep_all,
+            record_errors,
+            error,
+        } = history;
+        State {
+            history: StateHistory {
+                t_span,
+                t_init,
+                p_init: f(p_init),
+                t_deque,
+                p_deque,
+                k_deque,
+                polynomials,
+                disco_deque,
+                rk: history_rk,
+                rk_switches,
+                backward,
+                keep_all,
+                record_errors,
+                error,
+            },
+            t_curr,
+            t_prev,
+            p_curr,
+            p_prev,
+            d_curr,
+            d_prev,
+            e_curr,
+            rk,
+            k_curr,
+            stats,
+            next_stepsize,
+            zeno,
+            control,
+            termination,
+            run_interval,
+            newton,
+            stiffness,
+            rk_switch,
+            multistep,
+            location_iterations,
+        }
+    }
+
+    /// Returns the borrowed view of the state, which is serialized as the state with the
+    /// initial condition replaced by `()`, e.g. in the callbacks of
+    /// [crate::Solver::checkpoint_every], which cannot take the state to call
+    /// [State::map_initial]. It is deserialized as `State<T, Y, S, I, ()>`:
+    /// ```ignore
+    /// .checkpoint_every(100, |state| json = serde_json::to_string(&state.snapshot()).unwrap())
+    /// // ...
+    /// let state = serde_json::from_str::<State<_, _, 7, 5, ()>>(&json)?.map_initial(|()| initial);
+    /// ```
+    #[cfg(feature = "serde")]
+    pub fn snapshot(&self) -> Snapshot<'_, T, Y, S, I> {
+        let history = &self.history;
+        Snapshot {
+            history: HistorySnapshot {
+                t_span: &history.t_span,
+                t_init: &history.t_init,
+                p_init: (),
+                t_deque: &history.t_deque,
+                p_deque: &history.p_deque,
+                k_deque: &history.k_deque,
+                polynomials: &history.polynomials,
+                disco_deque: &history.disco_deque,
+                rk: &history.rk,
+                rk_switches: &history.rk_switches,
+                backward: history.backward,
+                keep_all: history.keep_all,
+            },
+            t_curr: &self.t_curr,
+            t_prev: &self.t_prev,
+            p_curr: &self.p_curr,
+            p_prev: &self.p_prev,
+            d_curr: &self.d_curr,
+            d_prev: &self.d_prev,
+            e_curr: &self.e_curr,
+            rk: &self.rk,
+            k_curr: &self.k_curr,
+            stats: &self.stats,
+            next_stepsize: &self.next_stepsize,
+            zeno: &self.zeno,
+            control: &self.control,
+            termination: &self.termination,
+            rk_switch: &self.rk_switch,
+        }
     }
 }
 
@@ -158,31 +590,159 @@ impl<
     IC: InitialCondition<T, Y>,
 > StateHistory<T, Y, S, I, IC>
 {
+    /// Evaluates the derivative of order `D` of the state at time `t`.
+    ///
+    /// # Panics
+    ///
+    /// Panics on failure, see [StateHistory::try_eval], except during the steps of the solver.
+    ///
+    /// # Fallback values
+    ///
+    /// During the steps of the solver, a failed evaluation returns a fallback value instead: the
+    /// state at the nearest available time, or zero if there is no such state. The error is
+    /// recorded (unless there is one already) and reported by [crate::Solver::try_run], which
+    /// aborts the step right after the evaluation of the stage, event or callback, in which it
+    /// occurred. Thus, once an error is recorded, the values returned by the failed evaluations
+    /// are not the state, and are discarded with the step. Use [StateHistory::try_eval] to
+    /// handle the errors directly.
     pub fn eval<const D: usize>(&self, t: T) -> Y {
-        if t <= self.t_init {
-            self.p_init.eval::<D>(t)
+        self.try_eval::<D>(t)
+            .unwrap_or_else(|error| self.recover::<D>(error))
+    }
+
+    /// Panics on the `error` of the evaluation of the derivative of order `D`, or records it and
+    /// returns the fallback value, see [StateHistory::eval].
+    fn recover<const D: usize>(&self, error: HistoryError<T>) -> Y {
+        if !self.record_errors {
+            panic!("{error}");
+        }
+        let fallback = match error {
+            HistoryError::Deleted { t_earliest, .. } => self.try_eval::<D>(t_earliest).ok(),
+            HistoryError::NotComputed { .. } => self.p_deque.back().filter(|_| D == 0).copied(),
+            HistoryError::UnsupportedDerivative { .. } => None,
+        };
+        let _ = self.error.set(error);
+        fallback.unwrap_or(Y::zero())
+    }
+
+    /// Returns `true` if an error is recorded by [StateHistory::eval] during the step, in which
+    /// case the evaluations of the step are stopped.
+    pub(crate) fn failed(&self) -> bool {
+        self.error.get().is_some()
+    }
+
+    /// Returns `1` for forward integration, and `-1` for backward integration.
+    pub fn direction(&self) -> T {
+        if self.backward { -T::one() } else { T::one() }
+    }
+
+    /// Returns `true` if `t1` is not after `t2` in the direction of integration.
+    pub fn precedes(&self, t1: T, t2: T) -> bool {
+        if self.backward { t1 >= t2 } else { t1 <= t2 }
+    }
+
+    pub fn try_eval<const D: usize>(&self, t: T) -> Result<Y, HistoryError<T>> {
+        if self.precedes(t, self.t_init) {
+            self.p_init
+                .try_eval::<D>(t)
+                .ok_or(HistoryError::UnsupportedDerivative { t, order: D })
         } else {
-            let i = self.t_deque.partition_point(|t_i| t_i <= &t); // first i : t_seq[i] > t
+            let i = self.t_deque.partition_point(|&t_i| self.precedes(t_i, t)); // first i : t_seq[i] > t
             if i == 0 {
-                panic!(
-                    "Evaluation of state at {:?} in deleted time range (before {:?})",
-                    maybe_debug::maybe_debug(&t),
-                    self.t_deque.front().map(maybe_debug::maybe_debug),
-                );
+                return Err(HistoryError::Deleted {
+                    t,
+                    t_earliest: self.t_deque[0],
+                });
             } else if i == self.t_deque.len() {
-                panic!(
-                    "Evaluation of state in a not yet computed time range at {:?} while most recent time in history is {:?}.",
-                    maybe_debug::maybe_debug(&t),
-                    self.t_deque.back().map(maybe_debug::maybe_debug)
-                );
+                return Err(HistoryError::NotComputed {
+                    t,
+                    t_latest: self.t_deque[i - 1],
+                });
+            } else if D > 1 && self.polynomials.get(i - 1).is_none_or(|p| p.is_empty()) {
+                // only the dense output of multistep methods has higher derivatives
+                return Err(HistoryError::UnsupportedDerivative { t, order: D });
             }
-            let y_prev = &self.p_deque[i - 1];
-            let k = &self.k_deque[i - 1];
             let t_prev = self.t_deque[i - 1];
-            let t_next = self.t_deque[i];
-            let t_step = t_next - t_prev;
-            let theta = (t - t_prev) / t_step;
-            self.rk.dense_output::<D, Y>(y_prev, t_step, theta, k)
+            let theta = (t - t_prev) / (self.t_deque[i] - t_prev);
+            Ok(self.eval_step::<D>(i, theta))
         }
     }
+
+    /// Evaluates the derivative of order `D` (at most 1 for Runge-Kutta methods) of the dense
+    /// output of the step, which ends at `t_deque[i]`, at `theta` in the step.
+    pub(crate) fn eval_step<const D: usize>(&self, i: usize, theta: T) -> Y {
+        let y_prev = &self.p_deque[i - 1];
+        let t_prev = self.t_deque[i - 1];
+        let t_step = self.t_deque[i] - t_prev;
+        match self.polynomials.get(i - 1).filter(|p| !p.is_empty()) {
+            Some(polynomial) => {
+                multistep::dense_output::<D, T, Y>(y_prev, t_step, theta, polynomial)
+            }
+            None => {
+                self.rk_at(t_prev)
+                    .dense_output::<D, Y>(y_prev, t_step, theta, &self.k_deque[i - 1])
+            }
+        }
+    }
+
+    /// Returns the method of the step starting at `t`, see [StateHistory::rk_switches].
+    pub fn rk_at(&self, t: T) -> &ButcherTableu<T, S, I> {
+        self.rk_switches
+            .iter()
+            .rev()
+            .find(|(t_switch, _)| self.precedes(*t_switch, t))
+            .map_or(&self.rk, |(_, rk)| rk)
+    }
+}
+
+/// Borrowed view of a [State] without the initial condition, see [State::snapshot]. Its fields
+/// are serialized as those of the state, with `()` for [StateHistory::p_init].
+#[cfg(feature = "serde")]
+#[derive(serde::Serialize)]
+#[serde(
+    rename = "State",
+    bound(serialize = "T: serde::Serialize, Y: serde::Serialize")
+)]
+pub struct Snapshot<'a, T: RealField + Copy, Y: RealVectorSpace<T>, const S: usize, const I: usize>
+{
+    history: HistorySnapshot<'a, T, Y, S, I>,
+    t_curr: &'a T,
+    t_prev: &'a T,
+    p_curr: &'a Y,
+    p_prev: &'a Y,
+    d_curr: &'a Y,
+    d_prev: &'a Y,
+    e_curr: &'a Y,
+    rk: &'a ButcherTableu<T, S, I>,
+    #[serde(serialize_with = "crate::util::serde_array::array::serialize")]
+    k_curr: &'a [Y; S],
+    stats: &'a SolveStats<T>,
+    next_stepsize: &'a Option<T>,
+    zeno: &'a Option<(T, Option<usize>)>,
+    control: &'a Control,
+    termination: &'a Option<Termination<T>>,
+    rk_switch: &'a Option<ButcherTableu<T, S, I>>,
+}
+
+/// Borrowed view of a [StateHistory] without the initial condition, see [Snapshot].
+#[cfg(feature = "serde")]
+#[derive(serde::Serialize)]
+#[serde(
+    rename = "StateHistory",
+    bound(serialize = "T: serde::Serialize, Y: serde::Serialize")
+)]
+struct HistorySnapshot<'a, T: RealField, Y: RealVectorSpace<T>, const S: usize, const I: usize> {
+    t_span: &'a T,
+    t_init: &'a T,
+    p_init: (),
+    t_deque: &'a VecDeque<T>,
+    p_deque: &'a VecDeque<Y>,
+    #[serde(serialize_with = "crate::util::serde_array::deque_of_arrays::serialize")]
+    k_deque: &'a VecDeque<[Y; S]>,
+    polynomials: &'a VecDeque<Vec<Y>>,
+    disco_deque: &'a VecDeque<(T, usize)>,
+    rk: &'a ButcherTableu<T, S, I>,
+    rk_switches: &'a VecDeque<(T, ButcherTableu<T, S, I>)>,
+    backward: bool,
+    keep_all: bool,
 }
@@ -1,6 +1,8 @@
 use nalgebra::RealField;
 
 use crate::{
+    control::Control,
+    error::HistoryError,
     initial_condition::InitialCondition,
     state::{StateHistory, state::State},
     traits::RealVectorSpace,
@@ -31,7 +33,61 @@ pub struct StateRef<
 
     pub p_prev: &'s P,
 
-    history: &'s StateHistory<T, P, S, I, IC>,
+    pub(crate) history: HistoryRef<'s, T, P, S, I, IC>,
+}
+
+/// History, through which [StateRef] evaluates the past states: the history of the state itself,
+/// or the history of a state with another scalar type, lifted into it (see [LiftedHistory]).
+pub(crate) enum HistoryRef<
+    's,
+    T: RealField,
+    P: RealVectorSpace<T>,
+    const S: usize,
+    const I: usize,
+    IC,
+> {
+    State(&'s StateHistory<T, P, S, I, IC>),
+    Lifted(&'s dyn LiftedHistory<T, P>),
+}
+
+impl<T: RealField, P: RealVectorSpace<T>, const S: usize, const I: usize, IC> Clone
+    for HistoryRef<'_, T, P, S, I, IC>
+{
+    fn clone(&self) -> Self {
+        *self
+    }
+}
+
+impl<T: RealField, P: RealVectorSpace<T>, const S: usize, const I: usize, IC> Copy
+    for HistoryRef<'_, T, P, S, I, IC>
+{
+}
+
+impl<
+    T: RealField + std::fmt::Debug,
+    P: RealVectorSpace<T> + std::fmt::Debug,
+    const S: usize,
+    const I: usize,
+    IC,
+> std::fmt::Debug for HistoryRef<'_, T, P, S, I, IC>
+{
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            HistoryRef::State(history) => history.fmt(f),
+            HistoryRef::Lifted(_) => f.write_str("LiftedHistory"),
+        }
+    }
+}
+
+/// History of a state with another scalar type, whose evaluations are lifted into the scalar
+/// type `T` (e.g. into [crate::Dual] numbers, see [crate::dual]).
+pub(crate) trait LiftedHistory<T: RealField, P> {
+    /// Evaluates the position (with `derivative == false`) or the derivative at `t`, recording
+    /// the error as [StateHistory::eval] does.
+    fn eval(&self, t: T, derivative: bool) -> P;
+    /// Evaluates the position (with `derivative == false`) or the derivative at `t`, see
+    /// [StateHistory::try_eval].
+    fn try_eval(&self, t: T, derivative: bool) -> Result<P, HistoryError<T>>;
 }
 
 impl<
@@ -43,11 +99,49 @@ impl<
     IC: InitialCondition<T, P>,
 > StateRef<'s, T, P, S, I, IC>
 {
+    /// Returns the state, whose history is evaluated through the history of another state, see
+    /// [LiftedHistory].
+    pub(crate) fn lifted(
+        t: T,
+        p: &'s P,
+        d: &'s P,
+        t_prev: T,
+        p_prev: &'s P,
+        history: &'s dyn LiftedHistory<T, P>,
+    ) -> Self {
+        Self {
+            t,
+            p,
+            d,
+            t_prev,
+            p_prev,
+            history: HistoryRef::Lifted(history),
+        }
+    }
+
     pub fn p(&self, t: T) -> P {
-        self.history.eval::<0>(t)
+        match self.history {
+            HistoryRef::State(history) => history.eval::<0>(t),
+            HistoryRef::Lifted(history) => history.eval(t, false),
+        }
     }
     pub fn d(&self, t: T) -> P {
-        self.history.eval::<1>(t)
+        match self.history {
+            HistoryRef::State(history) => history.eval::<1>(t),
+            HistoryRef::Lifted(history) => history.eval(t, true),
+        }
+    }
+    pub fn try_p(&self, t: T) -> Result<P, HistoryError<T>> {
+        match self.history {
+            HistoryRef::State(history) => history.try_eval::<0>(t),
+            HistoryRef::Lifted(history) => history.try_eval(t, false),
+        }
+    }
+    pub fn try_d(&self, t: T) -> Result<P, HistoryError<T>> {
+        match self.history {
+            HistoryRef::State(history) => history.try_eval::<1>(t),
+            HistoryRef::Lifted(history) => history.try_eval(t, true),
+        }
     }
 }
 
@@ -72,6 +166,8 @@ pub struct StateRefMut<
     pub p_prev: &'s P,
 
     history: &'s mut StateHistory<T, P, S, I, IC>,
+
+    control: &'s mut Control,
 }
 
 impl<
@@ -89,11 +185,20 @@ impl<
     pub fn d(&self, t: T) -> P {
         self.history.eval::<1>(t)
     }
-    pub fn stop_integration(&mut self)
-    where
-        T: num_traits::Float,
-    {
-        *self.t = T::infinity();
+    pub fn try_p(&self, t: T) -> Result<P, HistoryError<T>> {
+        self.history.try_eval::<0>(t)
+    }
+    pub fn try_d(&self, t: T) -> Result<P, HistoryError<T>> {
+        self.history.try_eval::<1>(t)
+    }
+    /// Stops the integration after the callback, as returning [Control::Stop] with an empty
+    /// reason does.
+    pub fn stop_integration(&mut self) {
+        self.request(Control::Stop(String::new()));
+    }
+    /// Requests the control flow change after the callback, see [Control::request].
+    pub fn request(&mut self, control: Control) {
+        self.control.request(control);
     }
 }
 
@@ -174,7 +279,7 @@ impl<
             p: &state.p_curr,
             p_prev: &state.p_prev,
             d: &state.d_curr,
-            history: &state.history,
+            history: HistoryRef::State(&state.history),
         })
     }
     fn eval_prev(&mut self, state: &State<T, P, S, I, IC>) -> Output {
@@ -184,7 +289,7 @@ impl<
             p: &state.p_prev,
             p_prev: &state.p_prev,
             d: &state.d_prev,
-            history: &state.history,
+            history: HistoryRef::State(&state.history),
         })
     }
     fn eval_at(&mut self, state: &State<T, P, S, I, IC>, t: T) -> Output {
@@ -196,7 +301,7 @@ impl<
             p: y,
             p_prev: y,
             d: dy,
-            history: &state.history,
+            history: HistoryRef::State(&state.history),
         })
     }
 }
@@ -241,6 +346,7 @@ impl<
             p_prev: &state.p_prev,
             d: &state.d_curr,
             history: &mut state.history,
+            control: &mut state.control,
         })
     }
 }
@@ -260,6 +366,29 @@ impl<
     }
 }
 
+/// Callback, whose output is converted to [Control] and requested with [Control::request] as
+/// [State::control].
+pub struct ControlFn<F>(pub F);
+
+impl<
+    T: RealField + Copy,
+    P: RealVectorSpace<T>,
+    const S: usize,
+    const I: usize,
+    IC: InitialCondition<T, P>,
+    Output: Into<Control>,
+    F,
+    const MUT: bool,
+> EvalMutState<T, P, S, I, IC, ()> for ControlFn<StateFn<T, P, Output, F, MUT>>
+where
+    StateFn<T, P, Output, F, MUT>: EvalMutState<T, P, S, I, IC, Output>,
+{
+    fn eval_mut(&mut self, state: &mut State<T, P, S, I, IC>) {
+        let control = self.0.eval_mut(state).into();
+        state.control.request(control);
+    }
+}
+
 hlist2_trait_macro::TraitHList! {
     pub EvalStateFnHList for
         trait EvalState<
@@ -0,0 +1,77 @@
+//! Defines [SolveStats].
+
+/// Statistics of integration, available as [crate::state::State::stats] after
+/// [crate::Solver::run].
+#[derive(Clone, Debug, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct SolveStats<T> {
+    /// Number of steps accepted by [crate::stepsize::StepsizeController::update].
+    pub accepted_steps: usize,
+    /// Number of steps rejected by [crate::stepsize::StepsizeController::update].
+    pub rejected_steps: usize,
+    /// Number of evaluations of the right hand side of the equation.
+    pub rhs_evals: usize,
+    /// Number of evaluations of the Jacobian by implicit methods, see
+    /// [crate::implicit::Newton]. The evaluations of the right hand side for it are counted in
+    /// [SolveStats::rhs_evals].
+    pub jacobian_evals: usize,
+    /// Number of Newton iterations made by implicit methods, see [crate::implicit::Newton].
+    pub newton_iterations: usize,
+    /// Number of accepted steps, which are stiff for the explicit method, see
+    /// [crate::stiffness::Stiffness].
+    pub stiff_steps: usize,
+    /// Number of times the equation is detected as stiff, see [crate::stiffness::Stiffness].
+    pub stiffness_detections: usize,
+    /// Number of switches of the method, see [crate::Solver::auto_switch].
+    pub method_switches: usize,
+    /// Number of times each located event fired, by its index in the order of addition to the
+    /// solver (including delay propagation events). Events after the last fired one may be
+    /// missing.
+    pub event_counts: Vec<usize>,
+    /// Total number of iterations performed by event location methods, see
+    /// [crate::state::State::count_location_iteration].
+    pub location_iterations: usize,
+    /// Smallest length of a committed step.
+    pub min_step: Option<T>,
+    /// Largest length of a committed step.
+    pub max_step: Option<T>,
+}
+
+impl<T> Default for SolveStats<T> {
+    fn default() -> Self {
+        Self {
+            accepted_steps: 0,
+            rejected_steps: 0,
+            rhs_evals: 0,
+            jacobian_evals: 0,
+            newton_iterations: 0,
+            stiff_steps: 0,
+            stiffness_detections: 0,
+            method_switches: 0,
+            event_counts: Vec::new(),
+            location_iterations: 0,
+            min_step: None,
+            max_step: None,
+        }
+    }
+}
+
+impl<T: PartialOrd + Copy> SolveStats<T> {
+    /// Records the firing of the located event with the given index.
+    pub fn count_event(&mut self, index: usize) {
+        if self.event_counts.len() <= index {
+            self.event_counts.resize(index + 1, 0);
+        }
+        self.event_counts[index] += 1;
+    }
+
+    /// Records the length of a committed step.
+    pub fn count_step(&mut self, t_step: T) {
+        if self.min_step.is_none_or(|min| t_step < min) {
+            self.min_step = Some(t_step);
+        }
+        if self.max_step.is_none_or(|max| t_step > max) {
+            self.max_step = Some(t_step);
+        }
+    }
+}
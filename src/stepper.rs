@@ -0,0 +1,430 @@
+//! Defines [Stepper], the step-by-step interface to the integration loop.
+
+use std::{
+    collections::VecDeque,
+    sync::{
+        Arc,
+        atomic::{AtomicBool, Ordering},
+    },
+    time::Instant,
+};
+
+use nalgebra::RealField;
+
+use crate::{
+    StateFn, StateRefMut,
+    control::{Control, Limits, Termination},
+    error::SolverError,
+    initial_condition::InitialCondition,
+    loc::loc_hlist::HListLocateEarliest,
+    state::{EvalMutState, EvalMutStateFnHList, EvalState, State},
+    stepsize::{StepStatus, StepsizeController},
+    traits::RealVectorSpace,
+};
+
+/// Information about a committed step, yielded by [Stepper::step].
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub struct StepInfo<T, P> {
+    /// Time at the end of the step
+    pub t: T,
+    /// Position at the end of the step
+    pub p: P,
+    /// Index of the located event, which fired at the end of the step, if any. If several events
+    /// fired (see [crate::Solver::event_tolerance]), the index of the first one.
+    pub event: Option<usize>,
+}
+
+/// Integration in progress, created by [crate::Solver::into_stepper] or
+/// [crate::Solver::into_stepper_from].
+///
+/// Each call of [Stepper::step] (or [Iterator::next]) makes one committed step, evaluating the
+/// located and `on_step` events. Between steps, the end time can be changed by setting
+/// [Stepper::t_end], and the state can be changed with [Stepper::modify]. The integration is
+/// completed by [Stepper::finish], which evaluates `on_stop` events. Stepping ends when the end
+/// of the integration interval is reached, or [State::termination] is set (by a callback
+/// requesting [Control::Stop], by a failure, by reaching one of the [Limits], or by
+/// cancellation).
+pub struct Stepper<
+    T: RealField + Copy,
+    P: RealVectorSpace<T>,
+    const S: usize,
+    const I: usize,
+    Equation,
+    Initial,
+    Stepsize,
+    EventsOnStep,
+    EventsOnStop,
+    EventsOnLoc,
+> {
+    pub state: State<T, P, S, I, Initial>,
+    pub equation: Equation,
+    pub stepsize: Stepsize,
+    /// Time of the start of the run, see [State::run_interval]
+    pub t_start: T,
+    pub t_end: T,
+    /// See [crate::Solver::event_tolerance]
+    pub event_tolerance: Option<T>,
+    pub events_on_step: EventsOnStep,
+    pub events_on_stop: EventsOnStop,
+    pub events_on_loc: EventsOnLoc,
+    /// Number of committed steps
+    pub step_count: usize,
+    /// Times ahead, on which the steps end, ordered in the direction of integration, see
+    /// [crate::Solver::tstops]
+    pub tstops: VecDeque<T>,
+    /// Whether [Stepper::tstops] are registered as discontinuities when reached, see
+    /// [crate::Solver::tstops_disco]
+    pub tstops_disco: bool,
+    /// See [Limits]
+    pub limits: Limits,
+    /// See [crate::Solver::cancel_token]
+    pub cancel_token: Option<Arc<AtomicBool>>,
+    /// Time of the start of the run, from which [Limits::time_limit] counts
+    pub start_time: Instant,
+    /// Value of [crate::SolveStats::rhs_evals] at the start of the run, from which
+    /// [Limits::max_rhs_evals] counts
+    pub start_rhs_evals: usize,
+}
+
+impl<
+    T: RealField + Copy,
+    P: RealVectorSpace<T>,
+    const S: usize,
+    const I: usize,
+    Equation: EvalState<T, P, S, I, Initial, P>,
+    Initial: InitialCondition<T, P>,
+    Stepsize: StepsizeController<T, P>,
+    EventsOnStep: EvalMutStateFnHList<T, P, S, I, Initial, ()>,
+    EventsOnStop: EvalMutStateFnHList<T, P, S, I, Initial, ()>,
+    EventsOnLoc: HListLocateEarliest<T, P, S, I, Initial> + EvalMutStateFnHList<T, P, S, I, Initial, ()>,
+> Stepper<T, P, S, I, Equation, Initial, Stepsize, EventsOnStep, EventsOnStop, EventsOnLoc>
+{
+    /// Returns `true` if the end of the integration interval is reached, or the integration is
+    /// stopped or failed.
+    pub fn is_done(&self) -> bool {
+        self.state.termination.is_some()
+            || self.state.history.precedes(self.t_end, self.state.t_curr)
+    }
+
+    /// Makes one committed step. Returns `None` if the end of the integration interval is
+    /// reached, the integration is stopped or failed (see [Stepper::is_done]), one of the
+    /// [Limits] is reached, or the integration is cancelled.
+    pub fn step(&mut self) -> Option<Result<StepInfo<T, P>, SolverError<T>>> {
+        if self.is_done() {
+            return None;
+        }
+        if let Some(cancel_token) = &self.cancel_token
+            && cancel_token.load(Ordering::Relaxed)
+        {
+            self.state.termination = Some(Termination::Cancelled);
+            return None;
+        }
+        if let Some(limit) = self.limits.reached(
+            self.step_count,
+            self.state.stats.rhs_evals - self.start_rhs_evals,
+            self.start_time.elapsed(),
+        ) {
+            self.state.termination = Some(Termination::LimitReached(limit));
+            return None;
+        }
+        self.state.run_interval = Some((self.t_start, self.t_end));
+        // the history errors are reported after the step instead of panicking
+        self.state.history.record_errors = true;
+        let result = self.try_step();
+        self.state.history.record_errors = false;
+        if let Err(error) = &result {
+            self.state.termination = Some(Termination::Failed(*error));
+        }
+        result.transpose()
+    }
+
+    /// Makes one committed step, or returns `None` if one of the [Limits] is reached during the
+    /// rejected attempts of the step.
+    fn try_step(&mut self) -> Result<Option<StepInfo<T, P>>, SolverError<T>> {
+        let state = &mut self.state;
+        let stepsize = &mut self.stepsize;
+        let rhs = &mut self.equation;
+        let direction = state.history.direction();
+        let t_end = match self.tstops.front() {
+            Some(&t_stop) if state.history.precedes(t_stop, self.t_end) => t_stop,
+            _ => self.t_end,
+        };
+
+        state.check(self.step_count)?;
+        state.switch_method();
+        if state.multistep.is_none() && state.newton.is_none() && !state.rk.is_explicit() {
+            return Err(SolverError::ImplicitMethod {
+                t: state.t_curr,
+                step: self.step_count,
+            });
+        }
+
+        // the stepsize halved after a failure of the Newton iterations is not clamped
+        let below_min = |stepsize: &Stepsize| {
+            stepsize
+                .min_stepsize()
+                .is_some_and(|min| stepsize.get() < min)
+        };
+
+        let mut landing;
+        let located = loop {
+            landing = stepsize.get() >= (t_end - state.t_curr).abs();
+            state.make_step(
+                rhs,
+                direction * stepsize.get().min((t_end - state.t_curr).abs()),
+            );
+            state.check_history(self.step_count)?;
+            loop {
+                let stepsize_prev = stepsize.get();
+                if state.newton_failed() {
+                    // the stage equations of the implicit method are not solved, so the error
+                    // estimate is meaningless
+                    let t_step = (state.t_curr - state.t_prev).abs();
+                    stepsize.set(t_step / (T::one() + T::one()));
+                } else if state.update_stepsize(stepsize) == StepStatus::Accepted {
+                    state.stats.accepted_steps += 1;
+                    state.update_stiffness();
+                    break;
+                }
+                state.stats.rejected_steps += 1;
+                state.undo_step();
+                if let Some(limit) = self.limits.reached(
+                    self.step_count,
+                    state.stats.rhs_evals - self.start_rhs_evals,
+                    self.start_time.elapsed(),
+                ) {
+                    state.termination = Some(Termination::LimitReached(limit));
+                    return Ok(None);
+                }
+                if stepsize.get() >= stepsize_prev || below_min(stepsize) {
+                    return Err(SolverError::StepsizeUnderflow {
+                        stepsize: stepsize.get(),
+                        t: state.t_curr,
+                        step: self.step_count,
+                    });
+                }
+                landing = stepsize.get() >= (t_end - state.t_curr).abs();
+                state.make_step(
+                    rhs,
+                    direction * stepsize.get().min((t_end - state.t_curr).abs()),
+                );
+                state.check_history(self.step_count)?;
+            }
+            state.make_dense_stages(rhs);
+            state.check_history(self.step_count)?;
+            state.next_stepsize = Some(stepsize.get());
+
+            let located = self
+                .events_on_loc
+                .locate_simultaneous(state, self.event_tolerance);
+            state.check_history(self.step_count)?;
+            let Some((time, indices)) =
+                located.filter(|&(time, _)| state.history.precedes(state.t_prev, time))
+            else {
+                break None;
+            };
+            state.undo_step();
+            state.make_step(rhs, time - state.t_curr);
+            state.check_history(self.step_count)?;
+            if !state.newton_failed() {
+                break Some((time, indices));
+            }
+            // the stage equations of the implicit method are not solved on the step to the
+            // event, so the step is rejected and retried with the stepsize halved
+            state.stats.accepted_steps -= 1;
+            state.stats.rejected_steps += 1;
+            state.undo_step();
+            stepsize.set((time - state.t_curr).abs() / (T::one() + T::one()));
+            if below_min(stepsize) {
+                return Err(SolverError::StepsizeUnderflow {
+                    stepsize: stepsize.get(),
+                    t: state.t_curr,
+                    step: self.step_count,
+                });
+            }
+        };
+
+        let mut event = None;
+        let mut restart = false;
+        if let Some((time, indices)) = located {
+            state.make_dense_stages(rhs);
+            state.check_history(self.step_count)?;
+            state.commit_step();
+            let (t_begin, p_begin) = (state.t_prev, state.p_prev);
+            let mut changed = false;
+            for index in indices {
+                if changed {
+                    if state.t_curr != time {
+                        break;
+                    }
+                    // evaluate the detection on the located step ending in the changed state
+                    (state.t_prev, state.p_prev) = (t_begin, p_begin);
+                    let detected = self.events_on_loc.detect_at_index(state, index);
+                    state.check_history(self.step_count)?;
+                    if !detected {
+                        continue;
+                    }
+                }
+                state.make_zero_step();
+                state.stats.count_event(index);
+                self.events_on_loc.eval_mut_at_index(state, index);
+                state.check_history(self.step_count)?;
+                changed |= state.t_curr != time || !(state.p_curr - state.p_prev).is_zero();
+                event = event.or(Some(index));
+                match state.take_control(Some(index)) {
+                    Control::Continue => {}
+                    Control::RestartStepsize => restart = true,
+                    Control::Stop(_) => break,
+                }
+            }
+            state.make_zero_step();
+        } else if landing {
+            // land exactly on the end of the interval or on the stop time, avoiding the rounding
+            // error of `t_prev + t_step`
+            state.t_curr = t_end;
+        }
+        state.commit_step();
+        self.step_count += 1;
+        if let Some(&t_stop) = self.tstops.front()
+            && state.t_curr == t_stop
+        {
+            self.tstops.pop_front();
+            if self.tstops_disco {
+                state.history.disco_deque.push_back((t_stop, 0));
+            }
+            // the derivative is evaluated again at the start of the next step
+            state.make_zero_step();
+        }
+        self.events_on_step.eval_mut(state);
+        if state.take_control(None) == Control::RestartStepsize || restart {
+            stepsize.init();
+            state.next_stepsize = Some(stepsize.get());
+        }
+        state.check(self.step_count)?;
+
+        Ok(Some(StepInfo {
+            t: state.t_curr,
+            p: state.p_curr,
+            event,
+        }))
+    }
+
+    /// Changes the current state with a callback, as a state-mutating event does. Control flow
+    /// requests of the callback (see [StateRefMut::request]) are handled as those of `on_step`
+    /// events, and a failed evaluation of the history in it is reported by the next
+    /// [Stepper::step] or [Stepper::finish].
+    pub fn modify<R>(
+        &mut self,
+        callback: impl FnMut(&mut StateRefMut<T, P, S, I, Initial>) -> R,
+    ) -> R {
+        self.state.make_zero_step();
+        // the history error is reported by the next step
+        self.state.history.record_errors = true;
+        let result = StateFn::new_mut(callback).eval_mut(&mut self.state);
+        self.state.history.record_errors = false;
+        self.state.commit_step();
+        if self.state.take_control(None) == Control::RestartStepsize {
+            self.stepsize.init();
+        }
+        result
+    }
+
+    /// Makes steps until the end of the integration interval, and finishes the integration.
+    pub fn run(mut self) -> Result<State<T, P, S, I, Initial>, SolverError<T>> {
+        for step in &mut self {
+            step?;
+        }
+        self.finish()
+    }
+
+    /// Evaluates `on_stop` events and returns the final state, setting [State::termination] to
+    /// [Termination::ReachedEnd] if the end of the integration interval is reached.
+    pub fn finish(mut self) -> Result<State<T, P, S, I, Initial>, SolverError<T>> {
+        if self.is_done() {
+            self.state
+                .termination
+                .get_or_insert(Termination::ReachedEnd);
+        }
+        self.state.next_stepsize = Some(self.stepsize.get());
+        self.state.run_interval = Some((self.t_start, self.t_end));
+        self.state.history.record_errors = true;
+        self.events_on_stop.eval_mut(&mut self.state);
+        self.state.history.record_errors = false;
+        self.state.check(self.step_count)?;
+        Ok(self.state)
+    }
+}
+
+impl<
+    T: RealField + Copy,
+    P: RealVectorSpace<T>,
+    const S: usize,
+    const I: usize,
+    Equation: EvalState<T, P, S, I, Initial, P>,
+    Initial: InitialCondition<T, P>,
+    Stepsize: StepsizeController<T, P>,
+    EventsOnStep: EvalMutStateFnHList<T, P, S, I, Initial, ()>,
+    EventsOnStop: EvalMutStateFnHList<T, P, S, I, Initial, ()>,
+    EventsOnLoc: HListLocateEarliest<T, P, S, I, Initial> + EvalMutStateFnHList<T, P, S, I, Initial, ()>,
+> Iterator
+    for Stepper<T, P, S, I, Equation, Initial, Stepsize, EventsOnStep, EventsOnStop, EventsOnLoc>
+{
+    type Item = Result<StepInfo<T, P>, SolverError<T>>;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        self.step()
+    }
+}
+
+impl<
+    T: RealField + Copy,
+    P: RealVectorSpace<T>,
+    const S: usize,
+    const I: usize,
+    IC: InitialCondition<T, P>,
+> State<T, P, S, I, IC>
+{
+    /// Updates the stepsize controller with the error estimate of the step, and returns whether
+    /// the step is accepted. With [crate::Solver::auto_switch], the controller uses the order of
+    /// the error estimate of the current method. For multistep methods, the controller uses the
+    /// order of the step, and the order of the next step is selected, see
+    /// [crate::multistep::Multistep]. The order of the controller itself is left unchanged.
+    fn update_stepsize(&mut self, stepsize: &mut impl StepsizeController<T, P>) -> StepStatus {
+        let t_step = (self.t_curr - self.t_prev).abs();
+        if let Some(status) = self
+            .multistep
+            .as_mut()
+            .and_then(|multistep| multistep.update_stepsize(stepsize, &self.e_curr, t_step))
+        {
+            return status;
+        }
+        let error = self.error_estimate(stepsize);
+        if self.rk_switch.is_some() {
+            // the method changes with the stiffness, see [crate::Solver::auto_switch]
+            let order = self.rk.order.min(self.rk.order_embedded);
+            return stepsize.update_with_order(&error, order as u32);
+        }
+        stepsize.update(&error)
+    }
+
+    /// Returns the error estimate of the Runge-Kutta step. With the second embedded method
+    /// [crate::ButcherTableu::b3], the estimate is scaled by `err5 / sqrt(err5² + 0.01 err3²)`
+    /// for the norms `err5` and `err3` of the estimates of the embedded methods, as in the DOP853
+    /// code.
+    fn error_estimate(&self, stepsize: &impl StepsizeController<T, P>) -> P {
+        let Some(b3) = &self.rk.b3 else {
+            return self.e_curr;
+        };
+        let t_step = self.t_curr - self.t_prev;
+        let e3 = (0..S).fold(P::zero(), |acc, j| {
+            acc + self.k_curr[j] * (b3[j] - self.rk.b[j])
+        }) * t_step;
+        match (stepsize.error_norm(&self.e_curr), stepsize.error_norm(&e3)) {
+            (Some(err5), Some(err3)) if err5 > T::zero() => {
+                let hundredth = T::from_f64(0.01).unwrap();
+                self.e_curr * (err5 / (err5 * err5 + hundredth * err3 * err3).sqrt())
+            }
+            _ => self.e_curr,
+        }
+    }
+}
@@ -7,6 +7,40 @@ pub trait StepsizeController<T, P> {
     fn get(&self) -> T;
     fn set(&mut self, new_stepsize: T);
     fn update(&mut self, error: &P) -> StepStatus;
+
+    /// Restores the stepsize proposed at the end of a previous integration, see
+    /// [crate::Solver::resume].
+    fn resume(&mut self, stepsize: T) {
+        self.set(stepsize);
+    }
+
+    /// Returns the norm of the error estimate, with which the step is accepted if it is less
+    /// than one, or `None` if the controller does not measure errors. It is used by the order
+    /// selection of [crate::multistep::Multistep].
+    fn error_norm(&self, _error: &P) -> Option<T> {
+        None
+    }
+
+    /// Returns the absolute and relative tolerances of the error estimate, or `None` if the
+    /// controller does not measure errors. They scale the stopping criterion of the Newton
+    /// iterations of implicit methods, see [crate::implicit::Newton::kappa].
+    fn tolerances(&self) -> Option<(P, P)> {
+        None
+    }
+
+    /// Returns the smallest stepsize of the controller, if any. The solver fails with
+    /// [crate::SolverError::StepsizeUnderflow] when the stepsize is reduced below it after a
+    /// failure of the Newton iterations of an implicit method.
+    fn min_stepsize(&self) -> Option<T> {
+        None
+    }
+
+    /// Updates the stepsize as [StepsizeController::update] does, for the error estimate of the
+    /// given `order` instead of the configured one, which changes from step to step for
+    /// [crate::Solver::auto_switch] and the variable-order methods of [crate::multistep].
+    fn update_with_order(&mut self, error: &P, _order: u32) -> StepStatus {
+        self.update(error)
+    }
 }
 
 #[derive(Clone, Copy, PartialEq)]
@@ -29,9 +63,13 @@ impl<T: Copy, P> StepsizeController<T, P> for T {
     fn update(&mut self, _: &P) -> StepStatus {
         StepStatus::Accepted
     }
+
+    /// Fixed stepsize is kept as set for the resumed integration.
+    fn resume(&mut self, _: T) {}
 }
 
 #[derive(Clone, Default, Debug)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub struct AutomaticStepsize<T, P> {
     pub stepsize: T,
     pub stepsize_range: std::ops::Range<T>,
@@ -61,17 +99,22 @@ where
     fn set(&mut self, new_stepsize: T) {
         self.stepsize = new_stepsize
     }
+
+    fn min_stepsize(&self) -> Option<T> {
+        Some(self.stepsize_range.start)
+    }
+
+    fn tolerances(&self) -> Option<(P, P)> {
+        Some((self.atol, self.rtol))
+    }
     fn update(&mut self, error: &P) -> StepStatus {
-        let err = error
-            .into_iter()
-            .zip(&self.atol)
-            .zip(&self.rtol)
-            .map(|((&err, &atol), &rtol)| err.abs() / (atol + err.abs() * rtol))
-            .reduce(T::max)
-            .unwrap_or(T::zero());
+        self.update_with_order(error, self.order)
+    }
 
-        let factor =
-            self.fac * (T::one() / err).powf(T::one() / T::from_u32(self.order + 1).unwrap());
+    fn update_with_order(&mut self, error: &P, order: u32) -> StepStatus {
+        let err = self.error_norm(error).unwrap();
+
+        let factor = self.fac * (T::one() / err).powf(T::one() / T::from_u32(order + 1).unwrap());
         let factor = factor.clamp(self.fac_range.start, self.fac_range.end);
         self.stepsize *= factor;
         self.stepsize = self
@@ -83,4 +126,15 @@ where
             false => StepStatus::Accepted,
         }
     }
+
+    fn error_norm(&self, error: &P) -> Option<T> {
+        let err = error
+            .into_iter()
+            .zip(&self.atol)
+            .zip(&self.rtol)
+            .map(|((&err, &atol), &rtol)| err.abs() / (atol + err.abs() * rtol))
+            .reduce(T::max)
+            .unwrap_or(T::zero());
+        Some(err)
+    }
 }
@@ -0,0 +1,187 @@
+//! Defines [Stiffness], which detects stiffness of the equation during the integration, and
+//! switches between explicit and implicit methods, see [crate::Solver::stiffness_detection] and
+//! [crate::Solver::auto_switch].
+
+use std::fmt;
+
+use nalgebra::{DVector, RealField};
+
+use crate::{
+    initial_condition::InitialCondition,
+    state::State,
+    traits::{Components, RealVectorSpace},
+};
+
+/// Stiffness detection, stored in [State::stiffness].
+///
+/// During explicit steps, the dominant eigenvalue `ρ` of the Jacobian is estimated by the ratio
+/// of the differences of the derivatives and positions of two stages at the same time (E.
+/// Hairer, G. Wanner - Solving Ordinary Differential Equations II, Section IV.2), and during implicit
+/// steps, by the norm of the Jacobian used by [crate::implicit::Newton]. An accepted step is
+/// stiff, if `h ρ` exceeds [Stiffness::boundary] of the explicit method. The equation is
+/// detected as stiff after [Stiffness::stiff_steps] consecutive stiff steps, and as non-stiff
+/// after [Stiffness::nonstiff_steps] consecutive non-stiff steps.
+#[derive(Clone)]
+pub struct Stiffness<T, Y> {
+    /// Bound of `h ρ` for non-stiff steps. If `None`, it is set at the first step to `0.8` of
+    /// the stability boundary of the explicit method (see
+    /// [crate::ButcherTableu::stability_boundary]), since the stepsize controller keeps the
+    /// steps of stiff equations just below the stability boundary.
+    pub boundary: Option<T>,
+    /// Number of consecutive stiff steps, after which the equation is detected as stiff.
+    pub stiff_steps: usize,
+    /// Number of consecutive non-stiff steps, after which the equation is no longer detected
+    /// as stiff.
+    pub nonstiff_steps: usize,
+    /// Estimate of `h ρ` in the last step, if available.
+    pub estimate: Option<T>,
+    /// Whether the equation is detected as stiff.
+    pub is_stiff: bool,
+    stiff_count: usize,
+    nonstiff_count: usize,
+    to_components: fn(&Y) -> DVector<T>,
+}
+
+impl<T: RealField + Copy, Y: Components<T>> Default for Stiffness<T, Y> {
+    fn default() -> Self {
+        Self {
+            boundary: None,
+            stiff_steps: 15,
+            nonstiff_steps: 6,
+            estimate: None,
+            is_stiff: false,
+            stiff_count: 0,
+            nonstiff_count: 0,
+            to_components: Y::to_components,
+        }
+    }
+}
+
+impl<T: fmt::Debug, Y> fmt::Debug for Stiffness<T, Y> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("Stiffness")
+            .field("boundary", &self.boundary)
+            .field("stiff_steps", &self.stiff_steps)
+            .field("nonstiff_steps", &self.nonstiff_steps)
+            .field("estimate", &self.estimate)
+            .field("is_stiff", &self.is_stiff)
+            .finish_non_exhaustive()
+    }
+}
+
+impl<T: RealField + Copy, Y> Stiffness<T, Y> {
+    /// Returns the estimate of `ρ` by the ratio `|Δk| / |Δy|` of the differences of the
+    /// derivatives and the positions, or `None` if the positions coincide.
+    fn ratio(&self, dy: Y, dk: Y) -> Option<T> {
+        let dy = (self.to_components)(&dy).amax();
+        let dk = (self.to_components)(&dk).amax();
+        (dy > T::zero()).then(|| dk / dy)
+    }
+}
+
+impl<
+    T: RealField + Copy,
+    Y: RealVectorSpace<T>,
+    const S: usize,
+    const I: usize,
+    IC: InitialCondition<T, Y>,
+> State<T, Y, S, I, IC>
+{
+    /// Estimates `h ρ` in the explicit step, which is just made, by the latest pair of
+    /// different stage positions at the same time, including the end of the step. For methods,
+    /// which have no such pair (e.g. the Euler method), the estimate is not available.
+    /// `stages` are the positions of the stages.
+    pub(crate) fn estimate_stiffness(&mut self, stages: [Y; S]) {
+        let Some(stiffness) = &mut self.stiffness else {
+            return;
+        };
+        let t_step = (self.t_curr - self.t_prev).abs();
+        // the stages used only by the dense output are not evaluated yet
+        let main = self.rk.main_stages();
+        let stage = |i: usize| {
+            if i < main {
+                (self.rk.c[i], stages[i], self.k_curr[i])
+            } else {
+                (T::one(), self.p_curr, self.d_curr)
+            }
+        };
+        let rho = (1..=main).rev().find_map(|j| {
+            let (c_j, y_j, k_j) = stage(j);
+            (0..j).rev().find_map(|i| {
+                let (c_i, y_i, k_i) = stage(i);
+                (c_i == c_j)
+                    .then(|| stiffness.ratio(y_j - y_i, k_j - k_i))
+                    .flatten()
+            })
+        });
+        stiffness.estimate = rho.map(|rho| rho * t_step);
+    }
+
+    /// Updates the stiffness detection after the step is accepted.
+    pub(crate) fn update_stiffness(&mut self) {
+        let Some(stiffness) = &mut self.stiffness else {
+            return;
+        };
+        if !self.rk.is_explicit() {
+            // the norm of the Jacobian bounds its eigenvalues
+            let t_step = (self.t_curr - self.t_prev).abs();
+            stiffness.estimate = self
+                .newton
+                .as_ref()
+                .and_then(|newton| newton.jacobian.as_ref())
+                .map(|jacobian| {
+                    jacobian
+                        .row_iter()
+                        .map(|row| row.iter().fold(T::zero(), |acc, x| acc + x.abs()))
+                        .fold(T::zero(), T::max)
+                        * t_step
+                });
+        }
+        let explicit_rk = [Some(&self.rk), self.rk_switch.as_ref()]
+            .into_iter()
+            .flatten()
+            .find(|rk| rk.is_explicit());
+        if stiffness.boundary.is_none() {
+            stiffness.boundary =
+                explicit_rk.map(|rk| rk.stability_boundary() * T::from_f64(0.8).unwrap());
+        }
+        let (Some(estimate), Some(boundary)) = (stiffness.estimate, stiffness.boundary) else {
+            return;
+        };
+
+        if estimate > boundary {
+            self.stats.stiff_steps += 1;
+            stiffness.nonstiff_count = 0;
+            stiffness.stiff_count += 1;
+            if stiffness.stiff_count >= stiffness.stiff_steps && !stiffness.is_stiff {
+                stiffness.is_stiff = true;
+                self.stats.stiffness_detections += 1;
+            }
+        } else {
+            stiffness.nonstiff_count += 1;
+            if stiffness.nonstiff_count >= stiffness.nonstiff_steps {
+                stiffness.stiff_count = 0;
+                stiffness.is_stiff = false;
+            }
+        }
+    }
+
+    /// Switches to [State::rk_switch], if the stiffness detection requests a method of the
+    /// other kind: implicit for stiff equations, and explicit for non-stiff ones. The switch is
+    /// recorded in [crate::state::StateHistory::rk_switches], so that the history is evaluated
+    /// with the method of each step.
+    pub(crate) fn switch_method(&mut self) {
+        let (Some(stiffness), Some(rk_switch)) = (&self.stiffness, &mut self.rk_switch) else {
+            return;
+        };
+        if stiffness.is_stiff != self.rk.is_explicit() {
+            return;
+        }
+        std::mem::swap(&mut self.rk, rk_switch);
+        self.history.rk_switches.push_back((self.t_curr, self.rk));
+        if let Some(newton) = &mut self.newton {
+            newton.reset();
+        }
+        self.stats.method_switches += 1;
+    }
+}
@@ -29,3 +29,36 @@ impl<T: nalgebra::RealField + Copy, Y> RealVectorSpace<T> for Y where
         + num_traits::Zero
 {
 }
+
+/// Conversion of the position to its components and back, used by the linear algebra of
+/// implicit Runge-Kutta methods, see [crate::Solver::implicit_rk].
+pub trait Components<T: nalgebra::RealField>: RealVectorSpace<T> {
+    fn to_components(&self) -> nalgebra::DVector<T>;
+    fn from_components(components: &nalgebra::DVector<T>) -> Self;
+}
+
+macro_rules! impl_components_for_scalar {
+    ($($t:ty),*) => {$(
+        impl Components<$t> for $t {
+            fn to_components(&self) -> nalgebra::DVector<$t> {
+                nalgebra::DVector::from_element(1, *self)
+            }
+
+            fn from_components(components: &nalgebra::DVector<$t>) -> Self {
+                components[0]
+            }
+        }
+    )*};
+}
+
+impl_components_for_scalar!(f32, f64);
+
+impl<T: nalgebra::RealField + Copy, const N: usize> Components<T> for nalgebra::SVector<T, N> {
+    fn to_components(&self) -> nalgebra::DVector<T> {
+        nalgebra::DVector::from_column_slice(self.as_slice())
+    }
+
+    fn from_components(components: &nalgebra::DVector<T>) -> Self {
+        Self::from_column_slice(components.as_slice())
+    }
+}
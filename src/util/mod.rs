@@ -1,2 +1,4 @@
 mod partition_point;
+#[cfg(feature = "serde")]
+pub mod serde_array;
 pub use partition_point::partition_point_linear;
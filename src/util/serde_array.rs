@@ -0,0 +1,133 @@
+//! Serialization of arrays with const generic length, which is not supported by `serde` derive.
+//! Submodules are to be used with `#[serde(with = "...")]` field attribute.
+
+use std::{collections::VecDeque, marker::PhantomData};
+
+use serde::{
+    Deserialize, Deserializer, Serialize, Serializer,
+    de::{Error, SeqAccess, Visitor},
+    ser::SerializeTuple,
+};
+
+/// Array, which is serialized as a tuple of its elements.
+struct Array<T, const N: usize>([T; N]);
+
+/// Borrowed array, which is serialized as a tuple of its elements.
+struct ArrayRef<'a, T, const N: usize>(&'a [T; N]);
+
+impl<T: Serialize, const N: usize> Serialize for ArrayRef<'_, T, N> {
+    fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        let mut tuple = serializer.serialize_tuple(N)?;
+        for element in self.0 {
+            tuple.serialize_element(element)?;
+        }
+        tuple.end()
+    }
+}
+
+struct ArrayVisitor<T, const N: usize>(PhantomData<T>);
+
+impl<'de, T: Deserialize<'de>, const N: usize> Visitor<'de> for ArrayVisitor<T, N> {
+    type Value = Array<T, N>;
+
+    fn expecting(&self, formatter: &mut std::fmt::Formatter) -> std::fmt::Result {
+        write!(formatter, "an array of length {N}")
+    }
+
+    fn visit_seq<A: SeqAccess<'de>>(self, mut seq: A) -> Result<Self::Value, A::Error> {
+        let mut elements = Vec::with_capacity(N);
+        while let Some(element) = seq.next_element()? {
+            elements.push(element);
+        }
+        let len = elements.len();
+        elements
+            .try_into()
+            .map(Array)
+            .map_err(|_| A::Error::invalid_length(len, &self))
+    }
+}
+
+impl<'de, T: Deserialize<'de>, const N: usize> Deserialize<'de> for Array<T, N> {
+    fn deserialize<D: Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+        deserializer.deserialize_tuple(N, ArrayVisitor(PhantomData))
+    }
+}
+
+/// For fields of type `[T; N]`.
+pub mod array {
+    use super::*;
+
+    pub fn serialize<S: Serializer, T: Serialize, const N: usize>(
+        array: &[T; N],
+        serializer: S,
+    ) -> Result<S::Ok, S::Error> {
+        ArrayRef(array).serialize(serializer)
+    }
+
+    pub fn deserialize<'de, D: Deserializer<'de>, T: Deserialize<'de>, const N: usize>(
+        deserializer: D,
+    ) -> Result<[T; N], D::Error> {
+        Array::deserialize(deserializer).map(|array| array.0)
+    }
+}
+
+/// For fields of type `Option<[T; N]>`.
+pub mod option_array {
+    use super::*;
+
+    pub fn serialize<S: Serializer, T: Serialize, const N: usize>(
+        array: &Option<[T; N]>,
+        serializer: S,
+    ) -> Result<S::Ok, S::Error> {
+        array.as_ref().map(ArrayRef).serialize(serializer)
+    }
+
+    pub fn deserialize<'de, D: Deserializer<'de>, T: Deserialize<'de>, const N: usize>(
+        deserializer: D,
+    ) -> Result<Option<[T; N]>, D::Error> {
+        Option::<Array<T, N>>::deserialize(deserializer).map(|array| array.map(|array| array.0))
+    }
+}
+
+/// For fields of type `[[T; N]; M]`.
+pub mod array2 {
+    use super::*;
+
+    pub fn serialize<S: Serializer, T: Serialize, const N: usize, const M: usize>(
+        array: &[[T; N]; M],
+        serializer: S,
+    ) -> Result<S::Ok, S::Error> {
+        ArrayRef(&array.each_ref().map(ArrayRef)).serialize(serializer)
+    }
+
+    pub fn deserialize<
+        'de,
+        D: Deserializer<'de>,
+        T: Deserialize<'de>,
+        const N: usize,
+        const M: usize,
+    >(
+        deserializer: D,
+    ) -> Result<[[T; N]; M], D::Error> {
+        Array::<Array<T, N>, M>::deserialize(deserializer).map(|array| array.0.map(|row| row.0))
+    }
+}
+
+/// For fields of type `VecDeque<[T; N]>`.
+pub mod deque_of_arrays {
+    use super::*;
+
+    pub fn serialize<S: Serializer, T: Serialize, const N: usize>(
+        deque: &VecDeque<[T; N]>,
+        serializer: S,
+    ) -> Result<S::Ok, S::Error> {
+        serializer.collect_seq(deque.iter().map(ArrayRef))
+    }
+
+    pub fn deserialize<'de, D: Deserializer<'de>, T: Deserialize<'de>, const N: usize>(
+        deserializer: D,
+    ) -> Result<VecDeque<[T; N]>, D::Error> {
+        Vec::<Array<T, N>>::deserialize(deserializer)
+            .map(|arrays| arrays.into_iter().map(|array| array.0).collect())
+    }
+}
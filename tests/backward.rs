@@ -0,0 +1,96 @@
+use diffurch::*;
+use nalgebra::vector;
+
+#[test]
+fn exponential() {
+    let state = Solver::new::<f64, f64>()
+        .initial(1.)
+        .interval(0. ..-1.)
+        .max_delay(1.)
+        .stepsize(0.01)
+        .equation(|s| *s.p)
+        .run();
+
+    assert_eq!(state.t_curr, -1.);
+    assert!((state.p_curr - (-1f64).exp()).abs() < 1e-12);
+    assert!((state.eval::<0>(-0.5) - (-0.5f64).exp()).abs() < 1e-12);
+    assert!(state.stats.min_step.unwrap() > 0.);
+    assert!((state.stats.max_step.unwrap() - 0.01).abs() < 1e-12);
+}
+
+#[test]
+fn adaptive_stepsize() {
+    let state = Solver::new::<f64, nalgebra::Vector1<f64>>()
+        .initial(vector![1.])
+        .interval(0. ..-1.)
+        .stepsize(AutomaticStepsize {
+            stepsize: 0.1,
+            stepsize_range: 1e-6..1.,
+            atol: vector![1e-10],
+            rtol: vector![1e-10],
+            order: 6,
+            fac: 0.9,
+            fac_range: 0.2..5.,
+            initial_stepsize: None,
+        })
+        .equation(|s| *s.p)
+        .run();
+
+    assert_eq!(state.t_curr, -1.);
+    assert!((state.p_curr[0] - (-1f64).exp()).abs() < 1e-9);
+    assert!(state.stats.accepted_steps > 1);
+}
+
+#[test]
+fn events() {
+    type Loc = Locator<f64, f64>;
+
+    let mut periodic = vec![];
+    let mut crossing = vec![];
+    Solver::new::<f64, f64>()
+        .initial(1.)
+        .interval(0. ..-1.)
+        .stepsize(0.1)
+        .equation(|s| *s.p)
+        .on(Periodic::new(0.25), |s| periodic.push(s.t))
+        .on(Loc::zero(|s| s.p - 0.5), |s| crossing.push(s.t))
+        .run();
+
+    assert_eq!(periodic, vec![-0.25, -0.5, -0.75, -1.]);
+    assert_eq!(crossing.len(), 1);
+    assert!((crossing[0] - 0.5f64.ln()).abs() < 1e-8);
+}
+
+#[test]
+fn advanced_argument() {
+    // x'(t) = x(t + 1) for t < 0, x(t) = 1 for t >= 0
+    let state = Solver::new::<f64, f64>()
+        .initial(1.)
+        .initial_disco([(0., 1)])
+        .interval(0. ..-2.)
+        .max_delay(1.)
+        .stepsize(0.1)
+        .equation(|s| s.p(s.t + 1.))
+        .with_delayed_argument(|s| s.t + 1., 1)
+        .run();
+
+    let solution = |t: f64| {
+        if t >= -1. {
+            1. + t
+        } else {
+            t * t / 2. + 2. * t + 1.5
+        }
+    };
+
+    assert!((state.p_curr - solution(-2.)).abs() < 1e-12);
+    for t in [-1.75, -1.5, -1.25, -1.] {
+        assert!((state.eval::<0>(t) - solution(t)).abs() < 1e-12);
+    }
+    assert!(
+        state
+            .history
+            .disco_deque
+            .iter()
+            .any(|&(t, _)| (t + 1.).abs() < 1e-12)
+    );
+}
@@ -0,0 +1,100 @@
+use diffurch::*;
+
+#[test]
+fn checkpoint_times() {
+    let mut every = vec![];
+    let mut periodic = vec![];
+    Solver::new::<f64, f64>()
+        .initial(0.)
+        .interval(0. ..2.)
+        .stepsize(0.25)
+        .equation(|_| 1.)
+        .checkpoint_every(4, |s| every.push(s.t_curr))
+        .checkpoint_on(Periodic::new(0.5), |s| periodic.push(s.t_curr))
+        .run();
+
+    assert_eq!(every, vec![0., 1., 2.]);
+    assert_eq!(periodic, vec![0.5, 1., 1.5, 2.]);
+}
+
+#[cfg(feature = "serde")]
+#[test]
+fn serialize_and_resume() {
+    let solver = |interval: std::ops::Range<f64>| {
+        Solver::new::<f64, f64>()
+            .initial(1.)
+            .interval(interval)
+            .stepsize(0.25)
+            .equation(|s| -s.p(s.t - 1.))
+            .with_const_delay(1., 1)
+    };
+
+    let mut snapshot = String::new();
+    let state = solver(0. ..4.)
+        .checkpoint_every(3, |s| snapshot = serde_json::to_string(s).unwrap())
+        .run();
+
+    let restored: state::State<f64, f64, 7, 5, f64> = serde_json::from_str(&snapshot).unwrap();
+    assert_eq!(restored.t_curr, 3.75);
+    let resumed = solver(0. ..4.).resume(restored);
+
+    assert_eq!(resumed.t_curr, 4.);
+    assert!((resumed.p_curr - state.p_curr).abs() < 1e-12);
+}
+
+#[cfg(feature = "serde")]
+#[test]
+fn map_initial() {
+    let initial = |t: f64| 1. + t;
+    let solver = |interval: std::ops::Range<f64>| {
+        Solver::new::<f64, f64>()
+            .initial(InitFn(initial, ()))
+            .interval(interval)
+            .stepsize(0.25)
+            .equation(|s| -s.p(s.t - 1.))
+            .with_const_delay(1., 1)
+    };
+
+    let state = solver(0. ..2.).run();
+    let half = solver(0. ..0.5).run();
+
+    let json = serde_json::to_string(&half.map_initial(|_| ())).unwrap();
+    let restored = serde_json::from_str::<state::State<f64, f64, 7, 5, ()>>(&json)
+        .unwrap()
+        .map_initial(|()| InitFn(initial, ()));
+    let resumed = solver(0. ..2.).resume(restored);
+
+    assert!((resumed.p_curr - state.p_curr).abs() < 1e-12);
+}
+
+#[cfg(feature = "serde")]
+#[test]
+fn snapshot_without_initial() {
+    let initial = |t: f64| 1. + t;
+    let solver = |interval: std::ops::Range<f64>| {
+        Solver::new::<f64, f64>()
+            .initial(InitFn(initial, ()))
+            .interval(interval)
+            .stepsize(0.25)
+            .equation(|s| -s.p(s.t - 1.))
+            .with_const_delay(1., 1)
+    };
+
+    let mut snapshot = String::new();
+    let state = solver(0. ..2.)
+        .checkpoint_every(3, |s| {
+            snapshot = serde_json::to_string(&s.snapshot()).unwrap()
+        })
+        .run();
+
+    let restored = serde_json::from_str::<state::State<f64, f64, 7, 5, ()>>(&snapshot)
+        .unwrap()
+        .map_initial(|()| InitFn(initial, ()));
+    assert_eq!(restored.t_curr, 1.5);
+    // the history before the initial time is evaluated through the restored initial condition
+    assert_eq!(restored.history.eval::<0>(-0.5), 0.5);
+    let resumed = solver(0. ..2.).resume(restored);
+
+    assert_eq!(resumed.t_curr, 2.);
+    assert!((resumed.p_curr - state.p_curr).abs() < 1e-12);
+}
@@ -0,0 +1,162 @@
+use diffurch::*;
+use nalgebra::{Vector1, vector};
+
+type Loc = Locator<f64, f64>;
+
+#[test]
+fn reached_end() {
+    let state = Solver::new::<f64, f64>()
+        .initial(0.)
+        .interval(0. ..2.)
+        .stepsize(0.3)
+        .equation(|_| 1.)
+        .run();
+
+    assert_eq!(state.termination, Some(Termination::ReachedEnd));
+}
+
+#[test]
+fn stop_keeps_time() {
+    let mut stops = 0;
+    let state = Solver::new::<f64, f64>()
+        .initial(0.)
+        .interval(0. ..10.)
+        .stepsize(0.3)
+        .equation(|_| 1.)
+        .on(Periodic::new(1.), |_| {})
+        .on(Loc::zero(|s| s.p - 2.5), |_| Control::Stop("target".into()))
+        .on_stop(|_| stops += 1)
+        .run();
+
+    assert!((state.t_curr - 2.5).abs() < 1e-12);
+    assert!((state.p_curr - 2.5).abs() < 1e-12);
+    assert_eq!(*state.history.t_deque.back().unwrap(), state.t_curr);
+    assert_eq!(
+        state.termination,
+        Some(Termination::Stopped {
+            event: Some(1),
+            reason: "target".into()
+        })
+    );
+    assert_eq!(stops, 1);
+}
+
+#[test]
+fn stop_integration() {
+    let state = Solver::new::<f64, f64>()
+        .initial(0.)
+        .interval(0. ..-10.)
+        .stepsize(0.3)
+        .equation(|_| 1.)
+        .on_mut(Loc::zero(|s| s.p + 2.5), |s| {
+            *s.p = 0.;
+            s.stop_integration();
+        })
+        .run();
+
+    assert!((state.t_curr + 2.5).abs() < 1e-12);
+    assert_eq!(state.p_curr, 0.);
+    assert_eq!(
+        state.termination,
+        Some(Termination::Stopped {
+            event: Some(0),
+            reason: String::new()
+        })
+    );
+}
+
+#[test]
+fn stop_on_step() {
+    let state = Solver::new::<f64, f64>()
+        .initial(0.)
+        .interval(0. ..10.)
+        .stepsize(0.5)
+        .equation(|_| 1.)
+        .on_step(|s| {
+            if s.t >= 2. {
+                Control::Stop("enough".into())
+            } else {
+                Control::Continue
+            }
+        })
+        .run();
+
+    assert_eq!(state.t_curr, 2.);
+    assert_eq!(
+        state.termination,
+        Some(Termination::Stopped {
+            event: None,
+            reason: "enough".into()
+        })
+    );
+}
+
+#[test]
+fn failed() {
+    let mut stepper = Solver::new::<f64, f64>()
+        .initial(0.)
+        .interval(0. ..10.)
+        .stepsize(0.5)
+        .equation(|s| if s.t > 1. { f64::NAN } else { 1. })
+        .into_stepper();
+
+    let error = stepper.find_map(Result::err).unwrap();
+    assert!(matches!(error, SolverError::NonFinite { .. }));
+    assert_eq!(stepper.state.termination, Some(Termination::Failed(error)));
+    assert!(stepper.is_done());
+    assert!(stepper.step().is_none());
+}
+
+#[test]
+fn restart_stepsize() {
+    let mut stepper = Solver::new::<f64, Vector1<f64>>()
+        .initial(vector![0.])
+        .interval(0. ..2.)
+        .stepsize(AutomaticStepsize {
+            stepsize: 0.5,
+            stepsize_range: 1e-6..1.,
+            atol: vector![1e-6],
+            rtol: vector![1e-6],
+            order: 6,
+            fac: 0.9,
+            fac_range: 0.2..5.,
+            initial_stepsize: Some(1e-3),
+        })
+        .equation(|_| vector![1.])
+        .on_mut(Periodic::new(1.), |_| Control::RestartStepsize)
+        .into_stepper();
+
+    let mut t_event: Option<f64> = None;
+    while let Some(step) = stepper.step() {
+        let step = step.unwrap();
+        if let Some(t_event) = t_event {
+            assert!((step.t - t_event - 1e-3).abs() < 1e-12);
+            break;
+        }
+        if step.event.is_some() {
+            t_event = Some(step.t);
+        }
+    }
+    assert_eq!(t_event, Some(1.));
+}
+
+#[test]
+fn resume_after_stop() {
+    let solver = || {
+        Solver::new::<f64, f64>()
+            .initial(0.)
+            .interval(0. ..4.)
+            .stepsize(0.3)
+            .equation(|_| 1.)
+    };
+
+    let stopped = solver()
+        .on(Loc::zero(|s| s.p - 1.5), |_| Control::Stop("pause".into()))
+        .run();
+    assert!((stopped.t_curr - 1.5).abs() < 1e-12);
+
+    let state = solver().resume(stopped);
+    assert_eq!(state.t_curr, 4.);
+    assert!((state.p_curr - 4.).abs() < 1e-12);
+    assert_eq!(state.termination, Some(Termination::ReachedEnd));
+}
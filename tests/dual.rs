@@ -0,0 +1,210 @@
+use diffurch::{dual::jacobian, *};
+use nalgebra::{ComplexField, RealField, Vector1, Vector3, matrix, vector};
+
+fn lorenz<T: RealField + Copy>(p: Vector3<T>) -> Vector3<T> {
+    let [sigma, rho, beta] = [10., 28., 8. / 3.].map(|c| T::from_f64(c).unwrap());
+    let [x, y, z] = p.into();
+    vector![sigma * (y - x), x * (rho - z) - y, x * y - beta * z]
+}
+
+#[test]
+fn elementary_functions() {
+    let x = Dual::<f64, 1>::variable(0.7, 0);
+    let h = 1e-6;
+
+    let functions: [fn(Dual<f64, 1>) -> Dual<f64, 1>; 8] = [
+        |x| x.sin() * x.exp() / (x * x + Dual::constant(1.)),
+        |x| x.sqrt().ln() + x.cbrt(),
+        |x| x.tan().atan() * x.cosh(),
+        |x| x.asin() + x.acos() * Dual::constant(2.),
+        |x| x.powi(3) - x.powf(x),
+        |x| x.hypot(x.tanh()).recip(),
+        |x| x.atan2(Dual::constant(1.) - x) + x.exp2().log2(),
+        |x| RealField::max(x, Dual::constant(0.5)).abs().log10(),
+    ];
+    for f in functions {
+        let expected = (f(Dual::constant(x.re + h)).re - f(Dual::constant(x.re - h)).re) / (2. * h);
+        assert!((f(x).eps[0] - expected).abs() < 1e-8);
+    }
+}
+
+#[test]
+fn lorenz_jacobian() {
+    let [x, y, z] = [1., 2., 3.];
+    let (value, jacobian) = jacobian(lorenz, vector![x, y, z]);
+
+    assert_eq!(value, lorenz(vector![x, y, z]));
+    assert_eq!(
+        jacobian,
+        matrix![
+            -10., 10., 0.;
+            28. - z, -1., -x;
+            y, x, -8. / 3.;
+        ]
+    );
+}
+
+fn lorenz_equation<T: RealField + Copy, const S: usize, const I: usize, IC>(
+    s: &StateRef<T, Vector3<T>, S, I, IC>,
+) -> Vector3<T>
+where
+    IC: initial_condition::InitialCondition<T, Vector3<T>>,
+{
+    lorenz(*s.p)
+}
+
+#[test]
+fn equation_jacobian() {
+    let mut stepper = Solver::new::<f64, Vector3<f64>>()
+        .initial(vector![10., 15., 20.])
+        .interval(0. ..1.)
+        .stepsize(0.01)
+        .equation(lorenz_equation)
+        .into_stepper();
+    stepper.by_ref().take(10).for_each(|step| {
+        step.unwrap();
+    });
+
+    let equation_jacobian = stepper.state.jacobian(lorenz_equation);
+    assert_eq!(equation_jacobian, jacobian(lorenz, stepper.state.p_curr).1);
+}
+
+#[test]
+fn delay_equation_jacobian() {
+    // x' = -x(t - 1 - x), where the delayed argument depends on the current position
+    fn equation<T: RealField + Copy, const S: usize, const I: usize, IC>(
+        s: &StateRef<T, Vector1<T>, S, I, IC>,
+    ) -> Vector1<T>
+    where
+        IC: initial_condition::InitialCondition<T, Vector1<T>>,
+    {
+        -s.p(s.t - T::one() - s.p[0])
+    }
+
+    let mut stepper = Solver::new::<f64, Vector1<f64>>()
+        .initial(InitFn(
+            |t: f64| vector![t.cos()],
+            |t: f64| vector![-t.sin()],
+        ))
+        .interval(0. ..3.)
+        .stepsize(0.01)
+        .max_delay(3.)
+        .equation(equation)
+        .into_stepper();
+    stepper.by_ref().take(200).for_each(|step| {
+        step.unwrap();
+    });
+
+    let state = &stepper.state;
+    let t = state.t_curr - 1. - state.p_curr[0];
+    assert_eq!(state.jacobian(equation)[0], state.history.eval::<1>(t)[0]);
+}
+
+#[test]
+fn parameter_derivative() {
+    // x' = -k x, where the derivative with respect to k is propagated through the solver
+    let k = Dual::<f64, 1>::variable(0.7, 0);
+    let state = Solver::new::<Dual<f64, 1>, Vector1<Dual<f64, 1>>>()
+        .initial(vector![Dual::constant(2.)])
+        .interval(Dual::constant(0.)..Dual::constant(3.))
+        .stepsize(Dual::constant(0.05))
+        .equation(|s| -*s.p * k)
+        .run();
+
+    let t = 3.;
+    let x = state.p_curr[0];
+    assert!((x.re - 2. * (-k.re * t).exp()).abs() < 1e-10);
+    assert!((x.eps[0] + t * 2. * (-k.re * t).exp()).abs() < 1e-10);
+}
+
+#[test]
+fn sensitivity_equation_with_jacobian() {
+    // the Jacobians of the linearization are computed by automatic differentiation
+    let state = Solver::new::<f64, Sensitivity<f64, 3, 0>>()
+        .initial(vector![10., 15., 20.])
+        .interval(0. ..0.5)
+        .stepsize(0.001)
+        .sensitivity_equation(|s| {
+            let (f, dfdx) = jacobian(lorenz, s.p.x);
+            Linearization {
+                f,
+                dfdx,
+                dfdp: Default::default(),
+                delayed: [],
+            }
+        })
+        .run();
+
+    let h = 1e-6;
+    let run = |x0: Vector3<f64>| {
+        Solver::new::<f64, Vector3<f64>>()
+            .initial(x0)
+            .interval(0. ..0.5)
+            .stepsize(0.001)
+            .equation(|s| lorenz(*s.p))
+            .run()
+            .p_curr
+    };
+    for i in 0..3 {
+        let mut x0 = vector![10., 15., 20.];
+        x0[i] += h;
+        let plus = run(x0);
+        x0[i] -= 2. * h;
+        let minus = run(x0);
+        let expected = (plus - minus) / (2. * h);
+        assert!((state.p_curr.dx0.column(i) - expected).amax() < 1e-4 * expected.amax());
+    }
+}
+
+#[test]
+fn implicit_method_with_dual_jacobian() {
+    let run = |dual: bool| {
+        let solver = Solver::new::<f64, Vector3<f64>>()
+            .initial(vector![10., 15., 20.])
+            .interval(0. ..0.5)
+            .stepsize(0.01)
+            .implicit_rk(ButcherTableu::radau5())
+            .equation(|s| lorenz(*s.p));
+        if dual {
+            solver.dual_jacobian(|_, p| lorenz(p)).run()
+        } else {
+            solver
+                .jacobian(|_, p| {
+                    let jacobian = jacobian(lorenz, *p).1;
+                    nalgebra::DMatrix::from_column_slice(3, 3, jacobian.as_slice())
+                })
+                .run()
+        }
+    };
+
+    let state = run(true);
+    assert!(state.stats.jacobian_evals > 0);
+    assert_eq!(state.p_curr, run(false).p_curr);
+}
+
+#[test]
+fn equality_with_derivatives() {
+    use num_traits::Zero;
+
+    let x = Dual::<f64, 1>::variable(0., 0);
+    assert!(!x.is_zero());
+    assert_eq!(x, Dual::constant(0.));
+    assert!(Dual::<f64, 1>::constant(0.).is_zero());
+    assert_eq!((x * Dual::constant(2.)).eps, (x + x).eps);
+}
+
+#[test]
+fn ordering_with_derivatives() {
+    use std::cmp::Ordering;
+
+    let x = Dual::<f64, 1>::variable(1., 0);
+    let y = Dual::constant(1.);
+    assert_eq!(x.partial_cmp(&y), Some(Ordering::Equal));
+    assert!(!x.lt(&y) && !x.gt(&y) && x.le(&y) && x.ge(&y));
+    assert_eq!(x.partial_cmp(&Dual::constant(2.)), Some(Ordering::Less));
+    assert_eq!(Dual::constant(2.).partial_cmp(&x), Some(Ordering::Greater));
+
+    let mut times = [Dual::<f64, 1>::variable(2., 0), x, y];
+    times.sort_by(|a, b| a.partial_cmp(b).unwrap());
+    assert_eq!(times.map(|t| t.eps[0]), [1., 0., 1.]);
+}
@@ -0,0 +1,125 @@
+use std::{
+    sync::atomic::{AtomicUsize, Ordering},
+    time::{Duration, Instant},
+};
+
+use diffurch::*;
+
+#[test]
+fn parameter_sweep() {
+    let rates = (0..100).map(|i| i as f64 / 10.).collect::<Vec<_>>();
+
+    let outputs = Ensemble::new(rates.len(), |member| {
+        let rate = rates[member.index];
+        Solver::new::<f64, f64>()
+            .initial(1.)
+            .interval(0. ..1.)
+            .stepsize(0.01)
+            .equation(move |s| -rate * s.p)
+    })
+    .run(|member, state| (member.index, state.p_curr));
+
+    assert_eq!(outputs.len(), rates.len());
+    for (i, (rate, output)) in rates.into_iter().zip(outputs).enumerate() {
+        let (index, p) = output.unwrap();
+        assert_eq!(index, i);
+        assert!((p - (-rate).exp()).abs() < 1e-10);
+    }
+}
+
+#[test]
+fn failures_per_member() {
+    let outputs = Ensemble::new(4, |member| {
+        let fail = member.index % 2 == 1;
+        Solver::new::<f64, f64>()
+            .initial(1.)
+            .interval(0. ..1.)
+            .stepsize(0.1)
+            .equation(move |s| if fail && s.t > 0.5 { f64::NAN } else { *s.p })
+    })
+    .run(|_, state| state.t_curr);
+
+    assert_eq!(outputs[0], Ok(1.));
+    assert!(matches!(
+        outputs[1],
+        Err(MemberError::Solver(SolverError::NonFinite { .. }))
+    ));
+    assert_eq!(outputs[2], Ok(1.));
+    assert!(matches!(
+        outputs[3],
+        Err(MemberError::Solver(SolverError::NonFinite { .. }))
+    ));
+}
+
+#[test]
+fn deterministic_seeds() {
+    // the initial condition is drawn from a generator seeded by the member
+    let uniform = |seed: u64| (seed >> 11) as f64 / (1u64 << 53) as f64;
+    let ensemble = |seed| {
+        Ensemble::new(50, move |member| {
+            Solver::new::<f64, f64>()
+                .initial(uniform(member.seed))
+                .interval(0. ..1.)
+                .stepsize(0.1)
+                .equation(|s| -s.p)
+        })
+        .seed(seed)
+    };
+
+    let outputs = ensemble(42).run(|_, state| state.p_curr);
+    assert_eq!(outputs, ensemble(42).run(|_, state| state.p_curr));
+    assert_ne!(outputs, ensemble(43).run(|_, state| state.p_curr));
+
+    let seeds = (0..50)
+        .map(|i| ensemble(42).member(i).seed)
+        .collect::<Vec<_>>();
+    for (i, seed) in seeds.iter().enumerate() {
+        assert!(!seeds[..i].contains(seed));
+    }
+}
+
+#[test]
+fn panics_per_member() {
+    let outputs = Ensemble::new(3, |member| {
+        assert!(member.index != 1, "member 1 is broken");
+        Solver::new::<f64, f64>()
+            .initial(1.)
+            .interval(0. ..1.)
+            .stepsize(0.1)
+            .equation(|s| *s.p)
+    })
+    .run(|_, state| state.t_curr);
+
+    assert_eq!(outputs[0], Ok(1.));
+    assert_eq!(
+        outputs[1],
+        Err(MemberError::Panic(String::from("member 1 is broken")))
+    );
+    assert_eq!(outputs[2], Ok(1.));
+}
+
+#[test]
+fn parallel_members() {
+    if std::thread::available_parallelism().map_or(1, usize::from) < 2 {
+        return;
+    }
+    // each member waits for the other one, which stops waiting only if they run concurrently
+    let running = AtomicUsize::new(0);
+    let outputs = Ensemble::new(2, |_| {
+        Solver::new::<f64, f64>()
+            .initial(1.)
+            .interval(0. ..1.)
+            .stepsize(0.1)
+            .equation(|s| -s.p)
+    })
+    .run(|_, _| {
+        running.fetch_add(1, Ordering::SeqCst);
+        let start = Instant::now();
+        while running.load(Ordering::SeqCst) < 2 && start.elapsed() < Duration::from_secs(10) {
+            std::thread::yield_now();
+        }
+        running.load(Ordering::SeqCst)
+    });
+
+    assert_eq!(outputs, [Ok(2), Ok(2)]);
+}
@@ -0,0 +1,94 @@
+use diffurch::*;
+
+#[test]
+fn grid() {
+    let times = (0..=8).map(|i| i as f64 * 0.25).collect::<Vec<_>>();
+    let mut sampled = vec![];
+    let state = Solver::new::<f64, f64>()
+        .initial(1.)
+        .interval(0. ..2.)
+        .stepsize(0.3)
+        .equation(|s| -s.p)
+        .on_grid(times.clone(), |s| sampled.push((s.t, *s.p, *s.d)))
+        .run();
+
+    assert_eq!(sampled.iter().map(|s| s.0).collect::<Vec<_>>(), times);
+    for (t, p, d) in sampled {
+        assert!((p - (-t).exp()).abs() < 1e-5);
+        // the derivative is not computed before the first step
+        assert!(t == 0. || (d + (-t).exp()).abs() < 1e-4);
+    }
+    // the steps do not land on the grid
+    assert_eq!(state.stats.accepted_steps, 7);
+}
+
+#[test]
+fn uniform() {
+    let mut ts = vec![];
+    Solver::new::<f64, f64>()
+        .initial(1.)
+        .interval(0. ..2.)
+        .stepsize(0.3)
+        .equation(|s| -s.p)
+        .on_uniform(0.25, |s| ts.push(s.t))
+        .run();
+    assert_eq!(ts, (0..=8).map(|i| i as f64 * 0.25).collect::<Vec<_>>());
+
+    let mut ts = vec![];
+    Solver::new::<f64, f64>()
+        .initial(1.)
+        .interval(0. ..-2.)
+        .stepsize(0.3)
+        .equation(|s| -s.p)
+        .on_uniform(0.25, |s| ts.push(s.t))
+        .run();
+    assert_eq!(ts, (0..=8).map(|i| i as f64 * -0.25).collect::<Vec<_>>());
+}
+
+#[test]
+fn jump_at_event() {
+    let mut sampled = vec![];
+    Solver::new::<f64, f64>()
+        .initial(0.)
+        .interval(0. ..2.)
+        .stepsize(0.3)
+        .equation(|_| 1.)
+        .on_mut(Locator::zero(|s| s.p - 1.), |s| *s.p = -1.)
+        .on_grid([0.5, 0.95, 1.05, 1.5], |s| sampled.push(*s.p))
+        .run();
+
+    let expected = [0.5, 0.95, -0.95, -0.5];
+    assert_eq!(sampled.len(), expected.len());
+    for (p, expected) in sampled.into_iter().zip(expected) {
+        assert!((p - expected).abs() < 1e-12);
+    }
+}
+
+#[test]
+fn uniform_from_start_of_run() {
+    let mut ts = vec![];
+    Solver::new::<f64, f64>()
+        .initial(1.)
+        .stepsize(0.3)
+        .equation(|s| -s.p)
+        .on_uniform(0.25, |s| ts.push(s.t))
+        .interval(0. ..-1.)
+        .run();
+    assert_eq!(ts, (0..=4).map(|i| i as f64 * -0.25).collect::<Vec<_>>());
+
+    let state = Solver::new::<f64, f64>()
+        .initial(1.)
+        .interval(0. ..1.)
+        .stepsize(0.3)
+        .equation(|s| -s.p)
+        .run();
+    let mut ts = vec![];
+    Solver::new::<f64, f64>()
+        .initial(1.)
+        .interval(0. ..2.)
+        .stepsize(0.3)
+        .equation(|s| -s.p)
+        .on_uniform(0.25, |s| ts.push(s.t))
+        .resume(state);
+    assert_eq!(ts, (4..=8).map(|i| i as f64 * 0.25).collect::<Vec<_>>());
+}
@@ -0,0 +1,237 @@
+use diffurch::*;
+use nalgebra::{Vector1, Vector2, Vector3, vector};
+
+/// Runs `x' = -1000 (x - cos t) - sin t`, x(0) = 1 with the solution `cos t`, and returns the
+/// error at the end and the final state.
+fn stiff_linear<const S: usize, const I: usize>(
+    rk: ButcherTableu<f64, S, I>,
+    stepsize: f64,
+) -> (f64, state::State<f64, Vector1<f64>, S, I, Vector1<f64>>) {
+    let state = Solver::new::<f64, Vector1<f64>>()
+        .initial(vector![1.])
+        .interval(0. ..2.)
+        .stepsize(stepsize)
+        .implicit_rk(rk)
+        .equation(|s| -1000. * (*s.p - Vector1::new(s.t.cos())) - Vector1::new(s.t.sin()))
+        .run();
+    ((state.p_curr[0] - 2f64.cos()).abs(), state)
+}
+
+#[test]
+fn stiff_linear_with_large_steps() {
+    // explicit methods are unstable for steps larger than about 0.003
+    assert!(stiff_linear(ButcherTableu::tr_bdf2(), 0.1).0 < 1e-5);
+    assert!(stiff_linear(ButcherTableu::sdirk4(), 0.1).0 < 1e-4);
+    assert!(stiff_linear(ButcherTableu::radau5(), 0.1).0 < 1e-7);
+    assert!(stiff_linear(ButcherTableu::ros3p(), 0.1).0 < 1e-5);
+    assert!(stiff_linear(ButcherTableu::rodas4(), 0.1).0 < 1e-5);
+}
+
+#[test]
+fn jacobian_reuse() {
+    let (_, state) = stiff_linear(ButcherTableu::radau5(), 0.1);
+    assert_eq!(state.stats.jacobian_evals, 1);
+    assert_eq!(state.stats.accepted_steps, 20);
+    assert_eq!(state.stats.rejected_steps, 0);
+    // the iterations converge in two iterations for the linear equation with the exact Jacobian
+    assert!(state.stats.newton_iterations <= 2 * 20);
+}
+
+#[test]
+fn stiff_oscillation() {
+    // the Jacobian has the eigenvalues -1000 ± 100 i, and the solution is (cos t, sin t)
+    let g = |t: f64| vector![t.cos(), t.sin()];
+    let state = Solver::new::<f64, Vector2<f64>>()
+        .initial(g(0.))
+        .interval(0. ..2.)
+        .stepsize(0.1)
+        .implicit_rk(ButcherTableu::radau5())
+        .equation(|s| {
+            let y = *s.p - g(s.t);
+            vector![-1000. * y[0] + 100. * y[1], -100. * y[0] - 1000. * y[1]]
+                + vector![-s.t.sin(), s.t.cos()]
+        })
+        .run();
+
+    assert!((state.p_curr - g(2.)).norm() < 1e-7);
+    assert_eq!(state.stats.jacobian_evals, 1);
+    assert!(state.stats.newton_iterations <= 2 * 20);
+}
+
+#[test]
+fn convergence_orders() {
+    // x' = x cos t, x(0) = 1 with the solution exp(sin t)
+    fn error<const S: usize, const I: usize>(rk: ButcherTableu<f64, S, I>, stepsize: f64) -> f64 {
+        let state = Solver::new::<f64, Vector1<f64>>()
+            .initial(vector![1.])
+            .interval(0. ..1.)
+            .stepsize(stepsize)
+            .implicit_rk(rk)
+            .newton(1e-14, 20)
+            .equation(|s| *s.p * s.t.cos())
+            .run();
+        (state.p_curr[0] - 1f64.sin().exp()).abs()
+    }
+    fn order<const S: usize, const I: usize>(rk: ButcherTableu<f64, S, I>) -> f64 {
+        (error(rk, 0.1) / error(rk, 0.05)).log2()
+    }
+
+    assert!((order(ButcherTableu::tr_bdf2()) - 2.).abs() < 0.2);
+    assert!((order(ButcherTableu::sdirk4()) - 4.).abs() < 0.3);
+    assert!((order(ButcherTableu::radau5()) - 5.).abs() < 0.3);
+    assert!((order(ButcherTableu::ros3p()) - 3.).abs() < 0.3);
+    assert!((order(ButcherTableu::rodas4()) - 4.).abs() < 0.3);
+}
+
+#[test]
+fn dense_output() {
+    fn max_error<const S: usize, const I: usize>(rk: ButcherTableu<f64, S, I>) -> f64 {
+        let solution = Solver::new::<f64, Vector1<f64>>()
+            .initial(vector![1.])
+            .interval(0. ..1.)
+            .stepsize(0.1)
+            .implicit_rk(rk)
+            .equation(|s| *s.p * s.t.cos())
+            .solve();
+        (0..100)
+            .map(|i| {
+                let t = i as f64 * 0.01 + 0.003;
+                (solution.eval(t)[0] - t.sin().exp()).abs()
+            })
+            .fold(0., f64::max)
+    }
+
+    assert!(max_error(ButcherTableu::tr_bdf2()) < 5e-3);
+    assert!(max_error(ButcherTableu::sdirk4()) < 1e-4);
+    assert!(max_error(ButcherTableu::radau5()) < 1e-4);
+    assert!(max_error(ButcherTableu::ros3p()) < 1e-3);
+    assert!(max_error(ButcherTableu::rodas4()) < 1e-4);
+}
+
+#[test]
+fn robertson() {
+    // chemical kinetics problem with the reference solution at t = 40 from E. Hairer, G. Wanner -
+    // Solving Ordinary Differential Equations II
+    let state = Solver::new::<f64, Vector3<f64>>()
+        .initial(vector![1., 0., 0.])
+        .interval(0. ..40.)
+        .stepsize(AutomaticStepsize {
+            stepsize: 1e-6,
+            stepsize_range: 1e-12..10.,
+            atol: vector![1e-6, 1e-10, 1e-6],
+            rtol: vector![1e-6, 1e-6, 1e-6],
+            order: 3,
+            fac: 0.9,
+            fac_range: 0.2..5.,
+            initial_stepsize: None,
+        })
+        .implicit_rk(ButcherTableu::radau5())
+        .equation(|s| {
+            let [y1, y2, y3] = (*s.p).into();
+            vector![
+                -0.04 * y1 + 1e4 * y2 * y3,
+                0.04 * y1 - 1e4 * y2 * y3 - 3e7 * y2 * y2,
+                3e7 * y2 * y2
+            ]
+        })
+        .run();
+
+    let expected = vector![0.7158270687, 9.185534765e-6, 0.2841637457];
+    assert!((state.p_curr - expected).component_div(&expected).amax() < 1e-6);
+    assert!(state.stats.accepted_steps < 200);
+    assert!(state.stats.jacobian_evals < state.stats.accepted_steps);
+}
+
+#[test]
+fn newton_tolerance_from_stepsize_controller() {
+    // the iterations stop at a fraction of the tolerances of the controller, so that the
+    // unattainable fixed tolerance is not used
+    let run = |tolerance: f64| {
+        let state = Solver::new::<f64, Vector1<f64>>()
+            .initial(vector![1.])
+            .interval(0. ..2.)
+            .stepsize(AutomaticStepsize {
+                stepsize: 1e-3,
+                stepsize_range: 1e-10..1.,
+                atol: vector![tolerance],
+                rtol: vector![tolerance],
+                order: 5,
+                fac: 0.9,
+                fac_range: 0.2..5.,
+                initial_stepsize: None,
+            })
+            .implicit_rk(ButcherTableu::radau5())
+            .newton(0., 10)
+            .equation(|s| vector![-1000. * (s.p[0] - s.t.cos()).powi(3) - s.t.sin()])
+            .run();
+        let error = (state.p_curr[0] - 2f64.cos()).abs();
+        let iterations = state.stats.newton_iterations as f64 / state.stats.accepted_steps as f64;
+        (error, iterations)
+    };
+    let (loose, tight) = (run(1e-3), run(1e-9));
+    assert!(loose.0 < 1e-2 && tight.0 < 1e-6);
+    assert!(loose.1 < 3. && tight.1 < 3.);
+}
+
+#[test]
+fn van_der_pol() {
+    // relaxation oscillator with mu = 1000, for which explicit methods need millions of steps
+    fn run<const S: usize, const I: usize>(
+        rk: ButcherTableu<f64, S, I>,
+    ) -> state::State<f64, Vector2<f64>, S, I, Vector2<f64>> {
+        Solver::new::<f64, Vector2<f64>>()
+            .initial(vector![2., 0.])
+            .interval(0. ..1000.)
+            .stepsize(AutomaticStepsize {
+                stepsize: 1e-6,
+                stepsize_range: 1e-12..100.,
+                atol: vector![1e-8, 1e-8],
+                rtol: vector![1e-8, 1e-8],
+                order: 3,
+                fac: 0.9,
+                fac_range: 0.2..5.,
+                initial_stepsize: None,
+            })
+            .implicit_rk(rk)
+            .equation(|s| {
+                let [x, y] = (*s.p).into();
+                vector![y, 1000. * (1. - x * x) * y - x]
+            })
+            .run()
+    }
+
+    let sdirk = run(ButcherTableu::sdirk4());
+    let radau = run(ButcherTableu::radau5());
+    assert!((sdirk.p_curr[0] - radau.p_curr[0]).abs() < 1e-5);
+    assert!(sdirk.stats.accepted_steps < 10000);
+    assert!(radau.stats.accepted_steps < 10000);
+}
+
+#[test]
+fn delay_differential_equation() {
+    // x'(t) = -x(t - 1) with the constant initial function 1, whose solution is a polynomial of
+    // degree at most 3 on [0, 3], which the collocation polynomial of Radau IIA reproduces
+    let solution = Solver::new::<f64, f64>()
+        .initial(1.)
+        .interval(0. ..3.)
+        .stepsize(0.1)
+        .implicit_rk(ButcherTableu::radau5())
+        .equation(|s| -s.p(s.t - 1.))
+        .with_const_delay(1., 1)
+        .solve();
+
+    let exact = |t: f64| {
+        let mut x = 1. - t;
+        if t > 1. {
+            x += (t - 1.).powi(2) / 2.;
+        }
+        if t > 2. {
+            x -= (t - 2.).powi(3) / 6.;
+        }
+        x
+    };
+    for i in 0..=300 {
+        let t = i as f64 * 0.01;
+        assert!((solution.eval(t) - exact(t)).abs() < 1e-10);
+    }
+}
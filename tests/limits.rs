@@ -0,0 +1,124 @@
+use std::time::Duration;
+
+use diffurch::*;
+
+#[test]
+fn max_steps() {
+    let state = Solver::new::<f64, f64>()
+        .initial(0.)
+        .interval(0. ..10.)
+        .stepsize(0.5)
+        .equation(|_| 1.)
+        .max_steps(3)
+        .run();
+
+    assert_eq!(state.t_curr, 1.5);
+    assert_eq!(state.stats.accepted_steps, 3);
+    assert_eq!(
+        state.termination,
+        Some(Termination::LimitReached(Limit::MaxSteps))
+    );
+}
+
+#[test]
+fn max_rhs_evals() {
+    let state = Solver::new::<f64, f64>()
+        .initial(0.)
+        .interval(0. ..10.)
+        .stepsize(0.5)
+        .equation(|_| 1.)
+        .max_rhs_evals(20)
+        .try_run()
+        .unwrap();
+
+    assert!(state.stats.rhs_evals >= 20);
+    assert!(state.t_curr < 10.);
+    assert_eq!(
+        state.termination,
+        Some(Termination::LimitReached(Limit::MaxRhsEvals))
+    );
+}
+
+#[test]
+fn time_limit() {
+    let state = Solver::new::<f64, f64>()
+        .initial(0.)
+        .interval(0. ..1e9)
+        .stepsize(1e-3)
+        .equation(|_| 1.)
+        .time_limit(Duration::from_millis(50))
+        .run();
+
+    assert!(state.t_curr < 1e9);
+    assert_eq!(
+        state.termination,
+        Some(Termination::LimitReached(Limit::TimeLimit))
+    );
+}
+
+#[test]
+fn not_reached() {
+    let state = Solver::new::<f64, f64>()
+        .initial(0.)
+        .interval(0. ..10.)
+        .stepsize(0.5)
+        .equation(|_| 1.)
+        .max_steps(20)
+        .max_rhs_evals(1000)
+        .time_limit(Duration::from_secs(60))
+        .run();
+
+    assert_eq!(state.t_curr, 10.);
+    assert_eq!(state.termination, Some(Termination::ReachedEnd));
+}
+
+#[test]
+fn limits_count_from_resume() {
+    let solver = || {
+        Solver::new::<f64, f64>()
+            .initial(0.)
+            .interval(0. ..10.)
+            .stepsize(0.5)
+            .equation(|_| 1.)
+            .max_steps(4)
+    };
+
+    let state = solver().run();
+    assert_eq!(state.t_curr, 2.);
+    let state = solver().resume(state);
+    assert_eq!(state.t_curr, 4.);
+    assert_eq!(
+        state.termination,
+        Some(Termination::LimitReached(Limit::MaxSteps))
+    );
+}
+
+#[test]
+fn limit_during_rejections() {
+    // the first step of the size 1 is rejected until it is decreased below 1e-6
+    let state = Solver::new::<f64, nalgebra::Vector1<f64>>()
+        .initial([1.])
+        .interval(0. ..10.)
+        .stepsize(AutomaticStepsize {
+            stepsize: 1.,
+            stepsize_range: 1e-12..1.,
+            atol: nalgebra::vector![1e-14],
+            rtol: nalgebra::vector![1e-14],
+            order: 4,
+            fac: 0.9,
+            fac_range: 0.9..1.,
+            initial_stepsize: None,
+        })
+        .equation(|s| -s.p)
+        .max_rhs_evals(50)
+        .run();
+
+    assert_eq!(state.stats.accepted_steps, 0);
+    assert!(state.stats.rejected_steps > 0);
+    assert!(state.stats.rhs_evals < 60);
+    assert_eq!(state.t_curr, 0.);
+    assert_eq!(
+        state.termination,
+        Some(Termination::LimitReached(Limit::MaxRhsEvals))
+    );
+}
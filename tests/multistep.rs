@@ -0,0 +1,310 @@
+use diffurch::{multistep::Multistep, *};
+use nalgebra::{DMatrix, SVector, Vector1, Vector3, vector};
+
+type Loc = Locator<f64, Vector1<f64>>;
+
+fn stepsize(tolerance: f64) -> AutomaticStepsize<f64, Vector1<f64>> {
+    AutomaticStepsize {
+        stepsize: 1e-3,
+        stepsize_range: 1e-10..10.,
+        atol: vector![tolerance],
+        rtol: vector![tolerance],
+        order: 5,
+        fac: 0.9,
+        fac_range: 0.2..5.,
+        initial_stepsize: None,
+    }
+}
+
+#[test]
+fn smooth_equation() {
+    // x' = x cos t, x(0) = 1 with the solution exp(sin t)
+    let adams = Solver::new::<f64, Vector1<f64>>()
+        .initial(vector![1.])
+        .interval(0. ..20.)
+        .stepsize(stepsize(1e-10))
+        .adams()
+        .equation(|s| *s.p * s.t.cos())
+        .run();
+    let rk = Solver::new::<f64, Vector1<f64>>()
+        .initial(vector![1.])
+        .interval(0. ..20.)
+        .stepsize(stepsize(1e-10))
+        .equation(|s| *s.p * s.t.cos())
+        .run();
+
+    assert!((adams.p_curr[0] - 20f64.sin().exp()).abs() < 1e-7);
+    assert!((rk.p_curr[0] - 20f64.sin().exp()).abs() < 1e-7);
+    assert!(adams.stats.rhs_evals < rk.stats.rhs_evals / 2);
+    assert!(adams.multistep.unwrap().order() > 4);
+}
+
+#[test]
+fn dense_output_and_max_order() {
+    let state = Solver::new::<f64, Vector1<f64>>()
+        .initial(vector![1.])
+        .interval(0. ..2.)
+        .stepsize(stepsize(1e-8))
+        .multistep(Multistep::adams().with_max_order(4))
+        .keep_history()
+        .equation(|s| *s.p * s.t.cos())
+        .run();
+    assert!(state.multistep.as_ref().unwrap().order() <= 4);
+    // the dense output of each step is the polynomial of the degree up to 5
+    let polynomials = &state.history.polynomials;
+    assert_eq!(polynomials.len(), state.history.t_deque.len() - 1);
+    assert!(polynomials.iter().all(|p| (1..=5).contains(&p.len())));
+    assert!(polynomials.iter().any(|p| p.len() == 5));
+
+    // x'' = (cos² t - sin t) exp(sin t)
+    let max_error = (0..200)
+        .map(|i| {
+            let t = i as f64 * 0.01 + 0.003;
+            let exact = (t.cos().powi(2) - t.sin()) * t.sin().exp();
+            (state.history.eval::<2>(t)[0] - exact).abs()
+        })
+        .fold(0., f64::max);
+    assert!(max_error < 1e-3);
+
+    let solution = state.into_solution();
+
+    let max_error = (0..200)
+        .map(|i| {
+            let t = i as f64 * 0.01 + 0.003;
+            (solution.eval(t)[0] - t.sin().exp()).abs()
+        })
+        .fold(0., f64::max);
+    assert!(max_error < 1e-6);
+}
+
+#[test]
+fn delay_equation() {
+    // x' = -x(t - 1), x(t) = 1 for t <= 0, whose solution is a polynomial between the
+    // propagated discontinuities, where the method restarts
+    let state = Solver::new::<f64, Vector1<f64>>()
+        .initial(vector![1.])
+        .interval(0. ..3.)
+        .stepsize(AutomaticStepsize {
+            stepsize_range: 1e-10..0.5,
+            ..stepsize(1e-10)
+        })
+        .adams()
+        .equation(|s| -s.p(s.t - 1.))
+        .with_const_delay(1., 3)
+        .run();
+
+    assert!((state.p_curr[0] + 1. / 6.).abs() < 1e-9);
+}
+
+#[test]
+fn restart_at_events() {
+    // x' = -x, x(0) = 1, which is reset to 1 whenever it decreases to 1/2
+    let mut times = vec![];
+    let state = Solver::new::<f64, Vector1<f64>>()
+        .initial(vector![1.])
+        .interval(0. ..3.)
+        .stepsize(stepsize(1e-10))
+        .adams()
+        .equation(|s| -*s.p)
+        .on_mut(Loc::zero(|s| s.p[0] - 0.5), |s| {
+            times.push(*s.t);
+            *s.p = vector![1.];
+        })
+        .run();
+
+    assert_eq!(times.len(), 4);
+    for (i, t) in times.into_iter().enumerate() {
+        assert!((t - (i + 1) as f64 * 2f64.ln()).abs() < 1e-8);
+    }
+    assert!((state.p_curr[0] - (4. * 2f64.ln() - 3.).exp()).abs() < 1e-8);
+}
+
+/// Method of lines discretization of `u_t = u_xx` on `(0, 1)` with zero boundary values.
+fn heat<const N: usize>(u: &SVector<f64, N>) -> SVector<f64, N> {
+    let dx = 1. / (N + 1) as f64;
+    SVector::from_fn(|i, _| {
+        let left = if i > 0 { u[i - 1] } else { 0. };
+        let right = if i + 1 < N { u[i + 1] } else { 0. };
+        (left - 2. * u[i] + right) / (dx * dx)
+    })
+}
+
+#[test]
+fn bdf_heat_equation() {
+    const N: usize = 40;
+    let dx = 1. / (N + 1) as f64;
+    let initial =
+        SVector::<f64, N>::from_fn(|i, _| (std::f64::consts::PI * (i + 1) as f64 * dx).sin());
+    // the initial condition is the eigenvector of the discretization with the eigenvalue
+    let eigenvalue = (2. / dx * (std::f64::consts::PI * dx / 2.).sin()).powi(2);
+    let stepsize = AutomaticStepsize {
+        stepsize: 1e-4,
+        stepsize_range: 1e-10..1.,
+        atol: SVector::repeat(1e-6),
+        rtol: SVector::repeat(1e-6),
+        order: 3,
+        fac: 0.9,
+        fac_range: 0.2..5.,
+        initial_stepsize: None,
+    };
+
+    let bdf = Solver::new::<f64, SVector<f64, N>>()
+        .initial(initial)
+        .interval(0. ..0.5)
+        .stepsize(stepsize.clone())
+        .bdf()
+        .equation(|s| heat(s.p))
+        .run();
+    let explicit = Solver::new::<f64, SVector<f64, N>>()
+        .initial(initial)
+        .interval(0. ..0.5)
+        .stepsize(stepsize)
+        .rk(ButcherTableu::rk43())
+        .equation(|s| heat(s.p))
+        .run();
+
+    assert!((bdf.p_curr - initial * (-eigenvalue * 0.5).exp()).amax() < 1e-5);
+    assert!(bdf.stats.rhs_evals < explicit.stats.rhs_evals / 10);
+    // the Jacobian of the linear equation is reused in all the steps
+    assert_eq!(bdf.stats.jacobian_evals, 1);
+    assert_eq!(bdf.multistep.unwrap().order(), 5);
+}
+
+#[test]
+fn bdf_large_heat_equation() {
+    // the positions with many components are copied on the stack
+    std::thread::Builder::new()
+        .stack_size(256 << 20)
+        .spawn(|| {
+            const N: usize = 1000;
+            let dx = 1. / (N + 1) as f64;
+            let initial = SVector::<f64, N>::from_fn(|i, _| {
+                (std::f64::consts::PI * (i + 1) as f64 * dx).sin()
+            });
+            let eigenvalue = (2. / dx * (std::f64::consts::PI * dx / 2.).sin()).powi(2);
+
+            let state = Solver::new::<f64, SVector<f64, N>>()
+                .initial(initial)
+                .interval(0. ..0.5)
+                .stepsize(AutomaticStepsize {
+                    stepsize: 1e-4,
+                    stepsize_range: 1e-10..1.,
+                    atol: SVector::repeat(1e-6),
+                    rtol: SVector::repeat(1e-6),
+                    order: 3,
+                    fac: 0.9,
+                    fac_range: 0.2..5.,
+                    initial_stepsize: None,
+                })
+                .bdf()
+                .jacobian(move |_, _| {
+                    DMatrix::from_fn(N, N, |i, j| match i.abs_diff(j) {
+                        0 => -2. / (dx * dx),
+                        1 => 1. / (dx * dx),
+                        _ => 0.,
+                    })
+                })
+                .equation(|s| heat(s.p))
+                .run();
+
+            assert!((state.p_curr - initial * (-eigenvalue * 0.5).exp()).amax() < 1e-5);
+            // the Jacobian is evaluated once, without finite differences, and its factorizations
+            // are reused across steps
+            assert_eq!(state.stats.jacobian_evals, 1);
+            assert!(state.stats.rhs_evals < 4 * state.stats.accepted_steps + 10);
+        })
+        .unwrap()
+        .join()
+        .unwrap();
+}
+
+#[test]
+fn bdf_robertson() {
+    // chemical kinetics problem with the reference solution at t = 40 from E. Hairer, G. Wanner -
+    // Solving Ordinary Differential Equations II
+    let expected = vector![0.7158270687, 9.185534765e-6, 0.2841637457];
+    let state = Solver::new::<f64, Vector3<f64>>()
+        .initial(vector![1., 0., 0.])
+        .interval(0. ..40.)
+        .stepsize(AutomaticStepsize {
+            stepsize: 1e-6,
+            stepsize_range: 1e-12..10.,
+            atol: vector![1e-8, 1e-12, 1e-8],
+            rtol: vector![1e-7, 1e-7, 1e-7],
+            order: 3,
+            fac: 0.9,
+            fac_range: 0.2..5.,
+            initial_stepsize: None,
+        })
+        .bdf()
+        .equation(|s| {
+            let [y1, y2, y3] = (*s.p).into();
+            vector![
+                -0.04 * y1 + 1e4 * y2 * y3,
+                0.04 * y1 - 1e4 * y2 * y3 - 3e7 * y2 * y2,
+                3e7 * y2 * y2
+            ]
+        })
+        .run();
+
+    assert!((state.p_curr - expected).component_div(&expected).amax() < 1e-5);
+    assert!(state.stats.accepted_steps < 500);
+    assert!(state.stats.jacobian_evals < 10);
+}
+
+#[test]
+fn bdf_dense_output_and_delay() {
+    let state = Solver::new::<f64, Vector1<f64>>()
+        .initial(vector![1.])
+        .interval(0. ..2.)
+        .stepsize(stepsize(1e-8))
+        .bdf()
+        .keep_history()
+        .equation(|s| *s.p * s.t.cos())
+        .run();
+    let solution = state.into_solution();
+    for i in 0..200 {
+        let t = i as f64 * 0.01 + 0.003;
+        assert!((solution.eval(t)[0] - t.sin().exp()).abs() < 1e-6);
+    }
+
+    let state = Solver::new::<f64, Vector1<f64>>()
+        .initial(vector![1.])
+        .interval(0. ..3.)
+        .stepsize(AutomaticStepsize {
+            stepsize_range: 1e-10..0.5,
+            ..stepsize(1e-8)
+        })
+        .multistep(Multistep::bdf().with_max_order(3))
+        .equation(|s| -s.p(s.t - 1.))
+        .with_const_delay(1., 3)
+        .run();
+    assert!((state.p_curr[0] + 1. / 6.).abs() < 1e-6);
+    assert!(state.multistep.unwrap().order() <= 3);
+}
+
+#[test]
+fn stepsize_order_kept() {
+    // the order of each step is passed to the controller, which keeps its own order
+    let mut stepper = Solver::new::<f64, Vector1<f64>>()
+        .initial(vector![1.])
+        .interval(0. ..5.)
+        .stepsize(stepsize(1e-10))
+        .adams()
+        .equation(|s| *s.p * s.t.cos())
+        .into_stepper();
+    for result in stepper.by_ref() {
+        result.unwrap();
+    }
+    assert!(stepper.state.multistep.as_ref().unwrap().order() > 4);
+    assert_eq!(stepper.stepsize.order, 5);
+}
+
+#[test]
+fn max_order_clamped() {
+    let adams = Multistep::<f64, Vector1<f64>>::adams();
+    assert_eq!(adams.clone().with_max_order(0).max_order(), 1);
+    assert_eq!(adams.with_max_order(20).max_order(), 12);
+    let bdf = Multistep::<f64, Vector1<f64>>::bdf();
+    assert_eq!(bdf.with_max_order(9).max_order(), 5);
+}
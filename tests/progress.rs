@@ -0,0 +1,155 @@
+use std::{
+    sync::{
+        Arc,
+        atomic::{AtomicBool, Ordering},
+    },
+    thread,
+    time::Duration,
+};
+
+use diffurch::*;
+
+#[test]
+fn cancel_from_callback() {
+    let cancel_token = Arc::new(AtomicBool::new(false));
+    let state = Solver::new::<f64, f64>()
+        .initial(0.)
+        .interval(0. ..10.)
+        .stepsize(0.5)
+        .equation(|_| 1.)
+        .cancel_token(cancel_token.clone())
+        .on_step(|s| {
+            if s.t >= 1. {
+                cancel_token.store(true, Ordering::Relaxed)
+            }
+        })
+        .run();
+
+    assert_eq!(state.t_curr, 1.);
+    assert_eq!(state.termination, Some(Termination::Cancelled));
+}
+
+#[test]
+fn cancel_from_thread() {
+    let cancel_token = Arc::new(AtomicBool::new(false));
+    let handle = {
+        let cancel_token = cancel_token.clone();
+        thread::spawn(move || {
+            Solver::new::<f64, f64>()
+                .initial(0.)
+                .interval(0. ..1e12)
+                .stepsize(1e-3)
+                .equation(|_| 1.)
+                .cancel_token(cancel_token)
+                .run()
+        })
+    };
+    thread::sleep(Duration::from_millis(20));
+    cancel_token.store(true, Ordering::Relaxed);
+    let state = handle.join().unwrap();
+
+    assert!(state.t_curr < 1e12);
+    assert_eq!(state.termination, Some(Termination::Cancelled));
+}
+
+#[test]
+fn progress_every_step() {
+    let mut reports = vec![];
+    Solver::new::<f64, f64>()
+        .initial(0.)
+        .interval(1. ..3.)
+        .stepsize(0.5)
+        .equation(|_| 1.)
+        .on_progress(Duration::ZERO, |progress| reports.push(progress))
+        .run();
+
+    let fractions = reports.iter().map(|p| p.fraction).collect::<Vec<_>>();
+    assert_eq!(fractions, vec![0., 0.25, 0.5, 0.75, 1.]);
+    assert_eq!(reports[0].stepsize, None);
+    assert_eq!(reports[4].stepsize, Some(0.5));
+    assert_eq!(reports[4].steps, 4);
+    assert_eq!(reports[4].t, 3.);
+}
+
+#[test]
+fn progress_throttled() {
+    let mut fractions = vec![];
+    Solver::new::<f64, f64>()
+        .initial(0.)
+        .interval(0. ..-10.)
+        .stepsize(0.01)
+        .equation(|_| 1.)
+        .on_progress(Duration::from_secs(3600), |progress| {
+            fractions.push(progress.fraction)
+        })
+        .run();
+
+    assert_eq!(fractions, vec![0., 1.]);
+}
+
+#[test]
+fn progress_on_stop() {
+    let mut reports = vec![];
+    let state = Solver::new::<f64, f64>()
+        .initial(0.)
+        .interval(0. ..10.)
+        .stepsize(1.)
+        .equation(|_| 1.)
+        .on_progress(Duration::from_secs(3600), |progress| reports.push(progress))
+        .on_step(|s| {
+            if s.t >= 3. {
+                Control::Stop("done".into())
+            } else {
+                Control::Continue
+            }
+        })
+        .run();
+
+    assert_eq!(state.t_curr, 3.);
+    let fractions = reports.iter().map(|p| p.fraction).collect::<Vec<_>>();
+    assert_eq!(fractions, vec![0., 0.3]);
+    assert_eq!(reports[1].steps, 3);
+}
+
+#[test]
+fn progress_extended_interval() {
+    let mut fractions = vec![];
+    let mut stepper = Solver::new::<f64, f64>()
+        .initial(0.)
+        .interval(0. ..2.)
+        .stepsize(0.5)
+        .equation(|_| 1.)
+        .on_progress(Duration::ZERO, |progress| fractions.push(progress.fraction))
+        .into_stepper();
+    stepper.step();
+    stepper.step();
+    stepper.t_end = 4.;
+    stepper.run().unwrap();
+
+    assert_eq!(
+        fractions,
+        vec![0., 0.25, 0.5, 0.375, 0.5, 0.625, 0.75, 0.875, 1.]
+    );
+}
+
+#[test]
+fn progress_in_thread() {
+    let (sender, receiver) = std::sync::mpsc::channel();
+    let stepper = Solver::new::<f64, f64>()
+        .initial(0.)
+        .interval(0. ..2.)
+        .stepsize(0.5)
+        .equation(|_| 1.)
+        .on_progress(Duration::ZERO, move |progress| {
+            sender.send(progress.fraction).unwrap()
+        })
+        .into_stepper();
+    thread::spawn(move || stepper.run().unwrap())
+        .join()
+        .unwrap();
+
+    assert_eq!(
+        receiver.iter().collect::<Vec<_>>(),
+        vec![0., 0.25, 0.5, 0.75, 1.]
+    );
+}
@@ -0,0 +1,112 @@
+use diffurch::*;
+
+#[test]
+fn same_as_single_run() {
+    let solver = |interval: std::ops::Range<f64>| {
+        Solver::new::<f64, f64>()
+            .initial(1.)
+            .interval(interval)
+            .stepsize(0.25)
+            .equation(|s| -s.p(s.t - 1.))
+            .with_const_delay(1., 1)
+    };
+
+    let state = solver(0. ..4.).run();
+    let half = solver(0. ..2.).run();
+    assert_eq!(half.next_stepsize, Some(0.25));
+    let resumed = solver(0. ..4.).resume(half);
+
+    assert_eq!(resumed.t_curr, 4.);
+    assert!((resumed.p_curr - state.p_curr).abs() < 1e-12);
+    assert_eq!(
+        resumed.history.disco_deque.len(),
+        state.history.disco_deque.len()
+    );
+    for t in [3., 3.5, 3.75] {
+        assert!((resumed.eval::<0>(t) - state.eval::<0>(t)).abs() < 1e-12);
+    }
+}
+
+#[test]
+fn changed_equation_and_stepsize() {
+    let state = Solver::new::<f64, f64>()
+        .initial(0.)
+        .interval(0. ..1.)
+        .stepsize(0.25)
+        .equation(|_| 1.)
+        .run();
+
+    let mut ts = vec![];
+    let state = Solver::new::<f64, f64>()
+        .initial(0.)
+        .interval(..2.)
+        .max_delay(2.)
+        .stepsize(0.5)
+        .equation(|_| -1.)
+        .on_step(|s| ts.push(s.t))
+        .resume(state);
+
+    assert_eq!(ts, vec![1.5, 2.]);
+    assert!(state.p_curr.abs() < 1e-14);
+    assert!((state.eval::<0>(0.5) - 0.5).abs() < 1e-14);
+    assert!((state.eval::<0>(1.5) - 0.5).abs() < 1e-14);
+}
+
+#[test]
+fn direction_without_steps() {
+    let state = Solver::new::<f64, f64>()
+        .initial(1.)
+        .interval(0. ..0.)
+        .equation(|s| *s.p)
+        .run();
+
+    let state = Solver::new::<f64, f64>()
+        .initial(1.)
+        .interval(..-1.)
+        .stepsize(0.125)
+        .equation(|s| *s.p)
+        .resume(state);
+
+    assert_eq!(state.t_curr, -1.);
+    assert!(state.history.backward);
+    assert!((state.p_curr - (-1f64).exp()).abs() < 1e-6);
+}
+
+#[test]
+#[should_panic(expected = "direction of the history")]
+fn reversed_direction() {
+    let state = Solver::new::<f64, f64>()
+        .initial(1.)
+        .interval(0. ..1.)
+        .equation(|s| *s.p)
+        .run();
+
+    Solver::new::<f64, f64>()
+        .initial(1.)
+        .interval(..0.)
+        .equation(|s| *s.p)
+        .resume(state);
+}
+
+#[test]
+fn reversed_direction_error() {
+    let state = Solver::new::<f64, f64>()
+        .initial(1.)
+        .interval(0. ..1.)
+        .equation(|s| *s.p)
+        .run();
+
+    let result = Solver::new::<f64, f64>()
+        .initial(1.)
+        .interval(..0.)
+        .equation(|s| *s.p)
+        .try_resume(state);
+    assert!(matches!(
+        result,
+        Err(SolverError::ReversedInterval {
+            t_end: 0.,
+            t: 1.,
+            step: 0
+        })
+    ));
+}
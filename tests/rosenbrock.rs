@@ -0,0 +1,95 @@
+use diffurch::*;
+use nalgebra::{DMatrix, Vector1, Vector3, dmatrix, vector};
+
+fn robertson(p: Vector3<f64>) -> Vector3<f64> {
+    let [y1, y2, y3] = p.into();
+    vector![
+        -0.04 * y1 + 1e4 * y2 * y3,
+        0.04 * y1 - 1e4 * y2 * y3 - 3e7 * y2 * y2,
+        3e7 * y2 * y2
+    ]
+}
+
+fn robertson_jacobian(_t: f64, p: &Vector3<f64>) -> DMatrix<f64> {
+    let [_, y2, y3] = (*p).into();
+    dmatrix![
+        -0.04, 1e4 * y3, 1e4 * y2;
+        0.04, -1e4 * y3 - 6e7 * y2, -1e4 * y2;
+        0., 6e7 * y2, 0.;
+    ]
+}
+
+fn robertson_stepsize() -> AutomaticStepsize<f64, Vector3<f64>> {
+    AutomaticStepsize {
+        stepsize: 1e-6,
+        stepsize_range: 1e-12..10.,
+        atol: vector![1e-6, 1e-10, 1e-6],
+        rtol: vector![1e-6, 1e-6, 1e-6],
+        order: 3,
+        fac: 0.9,
+        fac_range: 0.2..5.,
+        initial_stepsize: None,
+    }
+}
+
+#[test]
+fn robertson_with_user_jacobian() {
+    // chemical kinetics problem with the reference solution at t = 40 from E. Hairer, G. Wanner -
+    // Solving Ordinary Differential Equations II
+    let expected = vector![0.7158270687, 9.185534765e-6, 0.2841637457];
+
+    let finite_differences = Solver::new::<f64, Vector3<f64>>()
+        .initial(vector![1., 0., 0.])
+        .interval(0. ..40.)
+        .stepsize(robertson_stepsize())
+        .implicit_rk(ButcherTableu::rodas4())
+        .equation(|s| robertson(*s.p))
+        .run();
+    assert!(
+        (finite_differences.p_curr - expected)
+            .component_div(&expected)
+            .amax()
+            < 1e-5
+    );
+    assert!(finite_differences.stats.accepted_steps < 500);
+
+    let user = Solver::new::<f64, Vector3<f64>>()
+        .initial(vector![1., 0., 0.])
+        .interval(0. ..40.)
+        .stepsize(robertson_stepsize())
+        .implicit_rk(ButcherTableu::rodas4())
+        .jacobian(robertson_jacobian)
+        .equation(|s| robertson(*s.p))
+        .run();
+    assert!((user.p_curr - expected).component_div(&expected).amax() < 1e-5);
+
+    // the Jacobian is computed in every step without evaluations of the right hand side, which
+    // is evaluated for the time derivative, five stages and the derivative at the end of the
+    // step, and again at the start of the step after each rejection
+    let steps = user.stats.accepted_steps + user.stats.rejected_steps;
+    assert_eq!(user.stats.jacobian_evals, steps);
+    assert_eq!(
+        user.stats.rhs_evals,
+        1 + 7 * steps + user.stats.rejected_steps
+    );
+}
+
+#[test]
+fn rodas4_embedded_solution() {
+    // the embedded solution of order 3 is the argument of the last stage, so the error estimate
+    // of one step is of order 4
+    fn estimate(stepsize: f64) -> f64 {
+        let state = Solver::new::<f64, Vector1<f64>>()
+            .initial(vector![1.])
+            .interval(0. ..stepsize)
+            .stepsize(stepsize)
+            .implicit_rk(ButcherTableu::rodas4())
+            .equation(|s| *s.p * s.t.cos())
+            .run();
+        state.e_curr[0].abs()
+    }
+
+    let rodas4 = ButcherTableu::<f64, 6, 4>::rodas4();
+    assert_eq!(rodas4.b2, rodas4.a[5]);
+    assert!(((estimate(0.1) / estimate(0.05)).log2() - 4.).abs() < 0.3);
+}
@@ -0,0 +1,200 @@
+use diffurch::*;
+use nalgebra::{SMatrix, Vector1, matrix, vector};
+
+type Loc<const N: usize, const M: usize> = Locator<f64, Sensitivity<f64, N, M>>;
+
+#[test]
+fn exponential() {
+    let k = 0.7;
+    let x0 = 2.;
+
+    let state = Solver::new::<f64, Sensitivity<f64, 1, 1>>()
+        .initial(vector![x0])
+        .interval(0. ..3.)
+        .stepsize(0.05)
+        .sensitivity_equation(|s| Linearization {
+            f: -k * s.p.x,
+            dfdx: matrix![-k],
+            dfdp: -s.p.x,
+            delayed: [],
+        })
+        .run();
+
+    let t = state.t_curr;
+    assert!((state.p_curr.x[0] - x0 * (-k * t).exp()).abs() < 1e-10);
+    assert!((state.p_curr.dx0[0] - (-k * t).exp()).abs() < 1e-10);
+    assert!((state.p_curr.dp[0] + t * x0 * (-k * t).exp()).abs() < 1e-10);
+}
+
+#[test]
+fn delay() {
+    // x'(t) = -k x(t - 1) with the constant initial function x0
+    let run = |k: f64, x0: f64| {
+        Solver::new::<f64, Sensitivity<f64, 1, 1>>()
+            .initial(vector![x0])
+            .interval(0. ..4.)
+            .stepsize(0.01)
+            .sensitivity_equation(move |s| {
+                let delayed = s.p(s.t - 1.);
+                Linearization {
+                    f: -k * delayed.x,
+                    dfdx: matrix![0.],
+                    dfdp: -delayed.x,
+                    delayed: [(s.t - 1., matrix![-k])],
+                }
+            })
+            .with_const_delay(1., 1)
+            .run()
+            .p_curr
+    };
+
+    let (k, x0, h) = (0.8, 1.5, 1e-5);
+    let p = run(k, x0);
+    let dx0 = (run(k, x0 + h).x - run(k, x0 - h).x) / (2. * h);
+    let dp = (run(k + h, x0).x - run(k - h, x0).x) / (2. * h);
+    assert!((p.dx0 - dx0).amax() < 1e-7);
+    assert!((p.dp - dp).amax() < 1e-7);
+    // the solution is linear in the initial function
+    assert!((p.dx0[0] - p.x[0] / x0).abs() < 1e-10);
+}
+
+#[test]
+fn bouncing_ball() {
+    // position and velocity of the ball with the restitution coefficient e as a parameter
+    let run = |e: f64, x0: f64| {
+        Solver::new::<f64, Sensitivity<f64, 2, 1>>()
+            .initial(vector![x0, 0.])
+            .interval(0. ..0.8)
+            .stepsize(0.01)
+            .sensitivity_equation(|s| Linearization {
+                f: vector![s.p.x[1], -9.81],
+                dfdx: matrix![0., 1.; 0., 0.],
+                dfdp: SMatrix::zeros(),
+                delayed: [],
+            })
+            .on_mut(Loc::below_zero(|s| s.p.x[0]), move |s| {
+                let f_before = s.d.x;
+                let g_p = vector![0., -s.p.x[1]];
+                s.p.x[1] *= -e;
+                let f_after = vector![s.p.x[1], -9.81];
+                s.p.jump_at_crossing(
+                    &matrix![1., 0.; 0., -e],
+                    &g_p,
+                    &matrix![1., 0.],
+                    &f_before,
+                    &f_after,
+                );
+            })
+            .run()
+            .p_curr
+    };
+
+    let (e, x0, h) = (0.5, 1., 1e-6);
+    let p = run(e, x0);
+    let dx0 = (run(e, x0 + h).x - run(e, x0 - h).x) / (2. * h);
+    let dp = (run(e + h, x0).x - run(e - h, x0).x) / (2. * h);
+    assert!((p.dx0.column(0) - dx0).amax() < 1e-6);
+    assert!((p.dp - dp).amax() < 1e-6);
+}
+
+#[test]
+fn periodic_jump() {
+    // x' = -x with the kick x -> a x every unit of time
+    let a = 0.5;
+    let state = Solver::new::<f64, Sensitivity<f64, 1, 1>>()
+        .initial(vector![1.])
+        .interval(0. ..2.5)
+        .stepsize(0.05)
+        .sensitivity_equation(|s| Linearization {
+            f: -s.p.x,
+            dfdx: matrix![-1.],
+            dfdp: matrix![0.],
+            delayed: [],
+        })
+        .on_mut(Periodic::new(1.), |s| {
+            let g_p = s.p.x;
+            s.p.x *= a;
+            s.p.jump(&matrix![a], &g_p);
+        })
+        .run();
+
+    // x(t) = a^2 exp(-t) after two kicks
+    let t = state.t_curr;
+    let expected = Vector1::new(a * a * (-t).exp());
+    assert!((state.p_curr.x - expected).amax() < 1e-10);
+    assert!((state.p_curr.dx0[0] - a * a * (-t).exp()).abs() < 1e-10);
+    assert!((state.p_curr.dp[0] - 2. * a * (-t).exp()).abs() < 1e-10);
+}
+
+type D = Dual<f64, 2>;
+
+#[test]
+fn dual_delay() {
+    // x'(t) = -k x(t - 1) with the constant initial function x0, as in `delay`
+    let run = |k: f64, x0: f64| {
+        Solver::new::<f64, Sensitivity<f64, 1, 1>>()
+            .initial(vector![x0])
+            .interval(0. ..4.)
+            .stepsize(0.01)
+            .dual_sensitivity_equation::<2, _>(vector![k], |s, p| {
+                -s.p(s.t - D::constant(1.)) * p[0]
+            })
+            .with_const_delay(1., 1)
+            .run()
+            .p_curr
+    };
+
+    let (k, x0, h) = (0.8, 1.5, 1e-5);
+    let p = run(k, x0);
+    let dx0 = (run(k, x0 + h).x - run(k, x0 - h).x) / (2. * h);
+    let dp = (run(k + h, x0).x - run(k - h, x0).x) / (2. * h);
+    assert!((p.dx0 - dx0).amax() < 1e-7);
+    assert!((p.dp - dp).amax() < 1e-7);
+    assert!((p.dx0[0] - p.x[0] / x0).abs() < 1e-10);
+}
+
+#[test]
+fn dual_bouncing_ball() {
+    // as in `bouncing_ball`, with the jump of the sensitivities computed by the solver
+    let run = |e: f64, x0: f64| {
+        Solver::new::<f64, Sensitivity<f64, 2, 1>>()
+            .initial(vector![x0, 0.])
+            .interval(0. ..0.8)
+            .stepsize(0.01)
+            .dual_sensitivity_equation::<3, _>(vector![e], |s, _| {
+                vector![s.p[1], Dual::constant(-9.81)]
+            })
+            .dual_on_crossing(
+                loc::detection_method::BelowZero,
+                |_, x, _| x[0],
+                |_, x, p| vector![x[0], -p[0] * x[1]],
+            )
+            .run()
+            .p_curr
+    };
+
+    let (e, x0, h) = (0.5, 1., 1e-6);
+    let p = run(e, x0);
+    let dx0 = (run(e, x0 + h).x - run(e, x0 - h).x) / (2. * h);
+    let dp = (run(e + h, x0).x - run(e - h, x0).x) / (2. * h);
+    assert!((p.dx0.column(0) - dx0).amax() < 1e-6);
+    assert!((p.dp - dp).amax() < 1e-6);
+}
+
+#[test]
+fn dual_periodic_jump() {
+    // x' = -x with the kick x -> a x every unit of time, as in `periodic_jump`
+    let a = 0.5;
+    let state = Solver::new::<f64, Sensitivity<f64, 1, 1>>()
+        .initial(vector![1.])
+        .interval(0. ..2.5)
+        .stepsize(0.05)
+        .dual_sensitivity_equation::<2, _>(vector![a], |s, _| -s.p)
+        .dual_on_mut(Periodic::new(1.), |_, x, p| x * p[0])
+        .run();
+
+    let t = state.t_curr;
+    assert!((state.p_curr.x[0] - a * a * (-t).exp()).abs() < 1e-10);
+    assert!((state.p_curr.dx0[0] - a * a * (-t).exp()).abs() < 1e-10);
+    assert!((state.p_curr.dp[0] - 2. * a * (-t).exp()).abs() < 1e-10);
+}
@@ -0,0 +1,90 @@
+use std::cell::RefCell;
+
+use diffurch::*;
+
+#[test]
+fn priority() {
+    let state = Solver::new::<f64, f64>()
+        .initial(0.)
+        .interval(0. ..3.5)
+        .stepsize(0.3)
+        .equation(|_| 1.)
+        .on(Periodic::new(1.), |_| {})
+        .on(Periodic::new(1.), |_| {})
+        .run();
+    assert_eq!(state.stats.event_counts, vec![3]);
+
+    let state = Solver::new::<f64, f64>()
+        .initial(0.)
+        .interval(0. ..3.5)
+        .stepsize(0.3)
+        .equation(|_| 1.)
+        .on(Periodic::new(1.), |_| {})
+        .on(Periodic::new(1.).with_priority(1), |_| {})
+        .run();
+    assert_eq!(state.stats.event_counts, vec![0, 3]);
+}
+
+#[test]
+fn fire_all_in_priority_order() {
+    let log = RefCell::new(vec![]);
+    let mut steps = vec![];
+    let mut stepper = Solver::new::<f64, f64>()
+        .initial(0.)
+        .interval(0. ..2.5)
+        .stepsize(0.3)
+        .equation(|_| 1.)
+        .event_tolerance(1e-12)
+        .on(Periodic::new(1.), |s| log.borrow_mut().push((s.t, 'a')))
+        .on(Periodic::new(1.).with_priority(-1), |s| {
+            log.borrow_mut().push((s.t, 'b'))
+        })
+        .on(Periodic::new(1.).with_priority(1), |s| {
+            log.borrow_mut().push((s.t, 'c'))
+        })
+        .into_stepper();
+    for step in &mut stepper {
+        steps.push(step.unwrap().event);
+    }
+    drop(stepper);
+
+    assert_eq!(
+        log.into_inner(),
+        vec![
+            (1., 'c'),
+            (1., 'a'),
+            (1., 'b'),
+            (2., 'c'),
+            (2., 'a'),
+            (2., 'b')
+        ]
+    );
+    assert_eq!(steps.iter().filter(|&&event| event == Some(2)).count(), 2);
+}
+
+#[test]
+fn reevaluation_after_change() {
+    type Loc = Locator<f64, f64>;
+
+    let state = Solver::new::<f64, f64>()
+        .initial(0.)
+        .interval(0. ..2.2)
+        .stepsize(0.3)
+        .equation(|_| 1.)
+        .event_tolerance(1e-12)
+        .on_mut(Loc::zero(|s| s.p - 1.).with_priority(1), |s| *s.p = 0.5)
+        .on(Loc::zero(|s| s.p - 1.), |_| {})
+        .run();
+    assert_eq!(state.stats.event_counts, vec![3]);
+
+    let state = Solver::new::<f64, f64>()
+        .initial(0.)
+        .interval(0. ..2.2)
+        .stepsize(0.3)
+        .equation(|_| 1.)
+        .event_tolerance(1e-12)
+        .on(Loc::zero(|s| s.p - 1.).with_priority(1), |_| {})
+        .on_mut(Loc::zero(|s| s.p - 1.), |s| *s.p = 0.5)
+        .run();
+    assert_eq!(state.stats.event_counts, vec![3, 3]);
+}
@@ -0,0 +1,89 @@
+use diffurch::{error::HistoryError, *};
+
+#[test]
+fn exponential() {
+    let solution = Solver::new::<f64, f64>()
+        .initial(1.)
+        .interval(0. ..3.)
+        .stepsize(0.05)
+        .equation(|s| -s.p)
+        .solve();
+
+    assert_eq!(solution.start(), 0.);
+    assert_eq!(solution.end(), 3.);
+    for t in [0., 0.123, 1., 1.5, 2.71, 3.] {
+        assert!((solution.eval(t) - (-t).exp()).abs() < 1e-10);
+        assert!((solution.derivative(t) + (-t).exp()).abs() < 1e-7);
+    }
+    // initial condition before the start
+    assert_eq!(solution.eval(-1.), 1.);
+    assert!(solution.try_eval(4.).is_err());
+
+    let times = [0.5, 1.5, 2.5];
+    let sampled = solution.sample(times);
+    for (t, p) in times.into_iter().zip(sampled) {
+        assert!((p - (-t).exp()).abs() < 1e-10);
+    }
+
+    let steps = solution.steps().collect::<Vec<_>>();
+    assert_eq!(steps[0], (0., 1.));
+    assert_eq!(steps[steps.len() - 1].0, 3.);
+    assert!(steps.windows(2).all(|w| w[0].0 < w[1].0));
+    assert!(steps.iter().all(|&(t, p)| (p - (-t).exp()).abs() < 1e-10));
+}
+
+#[test]
+fn history_is_pruned_without_keep_history() {
+    let solver = || {
+        Solver::new::<f64, f64>()
+            .initial(1.)
+            .interval(0. ..3.)
+            .stepsize(0.05)
+            .equation(|s| -s.p)
+    };
+
+    let solution = solver().run().into_solution();
+    assert!(matches!(
+        solution.try_eval(1.),
+        Err(HistoryError::Deleted { .. })
+    ));
+
+    let solution = solver().keep_history().run().into_solution();
+    assert!((solution.eval(1.) - (-1f64).exp()).abs() < 1e-12);
+}
+
+#[test]
+fn jump_at_event() {
+    let solution = Solver::new::<f64, f64>()
+        .initial(0.)
+        .interval(0. ..2.)
+        .stepsize(0.3)
+        .equation(|_| 1.)
+        .on_mut(Locator::zero(|s| s.p - 1.), |s| *s.p = -1.)
+        .solve();
+
+    assert!((solution.eval(0.5) - 0.5).abs() < 1e-12);
+    assert!((solution.eval(1. - 1e-9) - 1.).abs() < 1e-8);
+    assert!((solution.eval(1.5) + 0.5).abs() < 1e-12);
+
+    // the step to the event and the zero step with the jump
+    let (t_event, p_event) = solution.steps().find(|&(_, p)| p < 0.).unwrap();
+    assert!((t_event - 1.).abs() < 1e-12);
+    assert_eq!(p_event, -1.);
+    assert_eq!(solution.eval(t_event), -1.);
+}
+
+#[test]
+fn backward() {
+    let solution = Solver::new::<f64, f64>()
+        .initial(1.)
+        .interval(0. ..-2.)
+        .stepsize(0.05)
+        .equation(|s| -s.p)
+        .solve();
+
+    assert_eq!(solution.end(), -2.);
+    for t in [0., -0.5, -1., -2.] {
+        assert!((solution.eval(t) - (-t).exp()).abs() < 1e-11);
+    }
+}
@@ -0,0 +1,237 @@
+use diffurch::{error::HistoryError, *};
+use nalgebra::*;
+
+#[test]
+fn history_deleted() {
+    let result = Solver::new::<f64, f64>()
+        .initial(1.)
+        .interval(0. ..5.)
+        .stepsize(0.1)
+        .equation(|s| -s.p(s.t - 1.))
+        .try_run();
+
+    let Err(SolverError::History { error, t, .. }) = result else {
+        panic!("expected history error")
+    };
+    assert!(matches!(error, HistoryError::Deleted { .. }));
+    assert!(t > 1.);
+}
+
+#[test]
+fn history_checked_access() {
+    let mut errors = 0;
+    Solver::new::<f64, f64>()
+        .initial(1.)
+        .interval(0. ..5.)
+        .stepsize(0.1)
+        .equation(|s| -s.p)
+        .on_step(|s| {
+            if s.try_p(s.t - 1.).is_err() {
+                errors += 1
+            }
+        })
+        .run();
+    assert!(errors > 0);
+}
+
+#[test]
+fn unsupported_derivative() {
+    let result = Solver::new::<f64, f64>()
+        .initial(InitFn(|t: f64| t.sin(), ()))
+        .interval(0. ..5.)
+        .max_delay(1.)
+        .stepsize(0.1)
+        .equation(|s| s.d(s.t - 1.))
+        .try_run();
+
+    let Err(SolverError::History { error, step, .. }) = result else {
+        panic!("expected history error")
+    };
+    assert!(matches!(
+        error,
+        HistoryError::UnsupportedDerivative { order: 1, .. }
+    ));
+    assert_eq!(step, 0);
+}
+
+#[test]
+fn unsupported_derivative_in_step() {
+    let state = Solver::new::<f64, f64>()
+        .initial(1.)
+        .interval(0. ..1.)
+        .stepsize(0.1)
+        .equation(|s| -s.p)
+        .run();
+
+    // the dense output of Runge-Kutta methods has no second derivative
+    let t = 0.95;
+    assert!(matches!(
+        state.try_eval::<2>(t),
+        Err(HistoryError::UnsupportedDerivative { order: 2, .. })
+    ));
+    assert!((state.eval::<1>(t) + (-t).exp()).abs() < 1e-6);
+}
+
+#[test]
+fn non_finite() {
+    // blows up at t = 1
+    let result = Solver::new::<f64, f64>()
+        .initial(1.)
+        .interval(0. ..2.)
+        .stepsize(0.1)
+        .equation(|s| s.p * s.p)
+        .try_run();
+
+    let Err(SolverError::NonFinite { t, .. }) = result else {
+        panic!("expected non-finite state error")
+    };
+    assert!(t > 1.);
+}
+
+#[test]
+fn stepsize_underflow() {
+    let result = Solver::new::<f64, Vector1<f64>>()
+        .initial([1.])
+        .interval(0. ..1.)
+        .stepsize(AutomaticStepsize {
+            stepsize: 0.1,
+            stepsize_range: 0.1..1.,
+            atol: vector![1e-14],
+            rtol: vector![1e-14],
+            order: 4,
+            fac: 0.9,
+            fac_range: 0.2..5.,
+            initial_stepsize: None,
+        })
+        .equation(|s| -100. * s.p)
+        .try_run();
+
+    assert!(matches!(
+        result,
+        Err(SolverError::StepsizeUnderflow { step: 0, .. })
+    ));
+}
+
+#[test]
+fn invalid_tableau() {
+    assert!(ButcherTableu::<f64, 3, 2>::try_generic_order_3(0.5, 1., None).is_some());
+    assert!(ButcherTableu::<f64, 3, 2>::try_generic_order_3(0., 0., None).is_none());
+}
+
+#[test]
+#[should_panic(expected = "deleted time range")]
+fn history_eval_after_run() {
+    let state = Solver::new::<f64, f64>()
+        .initial(1.)
+        .interval(0. ..5.)
+        .max_delay(1.)
+        .stepsize(0.1)
+        .equation(|s| -s.p(s.t - 1.))
+        .run();
+    // only the history of the last unit of time is kept
+    state.history.eval::<0>(1.);
+}
+
+#[test]
+fn state_is_sync() {
+    fn assert_sync<T: Sync>(_: &T) {}
+    let state = Solver::new::<f64, f64>()
+        .initial(1.)
+        .interval(0. ..1.)
+        .equation(|s| -s.p)
+        .run();
+    assert_sync(&state);
+}
+
+#[test]
+fn implicit_method_without_newton() {
+    let result = Solver::new::<f64, f64>()
+        .initial(1.)
+        .interval(0. ..1.)
+        .stepsize(0.1)
+        .rk(ButcherTableu::radau5())
+        .equation(|s| -s.p)
+        .try_run();
+
+    assert!(matches!(
+        result,
+        Err(SolverError::ImplicitMethod { step: 0, .. })
+    ));
+}
+
+#[test]
+fn stepsize_underflow_after_newton_failure() {
+    // the Newton iterations with the wrong Jacobian diverge for stepsizes above 1e-6
+    let result = Solver::new::<f64, Vector1<f64>>()
+        .initial([1.])
+        .interval(0. ..1.)
+        .stepsize(AutomaticStepsize {
+            stepsize: 0.1,
+            stepsize_range: 1e-3..1.,
+            atol: vector![1e-6],
+            rtol: vector![1e-6],
+            order: 4,
+            fac: 0.9,
+            fac_range: 0.2..5.,
+            initial_stepsize: None,
+        })
+        .rk(ButcherTableu::sdirk4())
+        .jacobian(|_, _| DMatrix::zeros(1, 1))
+        .equation(|s| -1e6 * s.p)
+        .try_run();
+
+    let Err(SolverError::StepsizeUnderflow { stepsize, step, .. }) = result else {
+        panic!("expected stepsize underflow error")
+    };
+    assert_eq!(step, 0);
+    assert!((5e-4..1e-3).contains(&stepsize));
+}
+
+#[test]
+fn history_error_aborts_step() {
+    // the equation is not evaluated again with the fallback value after the failure
+    let mut failures = 0;
+    let result = Solver::new::<f64, f64>()
+        .initial(1.)
+        .interval(0. ..5.)
+        .max_delay(0.5)
+        .stepsize(0.1)
+        .equation(|s| {
+            if s.try_p(s.t - 1.).is_err() {
+                failures += 1;
+            }
+            -s.p(s.t - 1.)
+        })
+        .try_run();
+
+    assert!(matches!(result, Err(SolverError::History { .. })));
+    assert_eq!(failures, 1);
+}
+
+#[test]
+fn history_error_in_callbacks() {
+    let solver = || {
+        Solver::new::<f64, f64>()
+            .initial(1.)
+            .interval(0. ..2.)
+            .max_delay(0.5)
+            .stepsize(0.1)
+            .equation(|s| -s.p)
+    };
+
+    let result = solver().on_stop(|s| println!("{}", s.p(0.5))).try_run();
+    assert!(matches!(result, Err(SolverError::History { .. })));
+
+    let result = solver().on_start(|s| println!("{}", s.p(1.))).try_run();
+    assert!(matches!(result, Err(SolverError::History { step: 0, .. })));
+
+    let mut stepper = solver().into_stepper();
+    stepper.by_ref().take(15).for_each(|step| {
+        step.unwrap();
+    });
+    stepper.modify(|s| *s.p = s.p(0.5));
+    assert!(matches!(
+        stepper.step(),
+        Some(Err(SolverError::History { step: 15, .. }))
+    ));
+}
@@ -0,0 +1,70 @@
+use diffurch::*;
+
+#[test]
+fn fixed_stepsize() {
+    let state = Solver::new::<f64, f64>()
+        .rk(RK::rk4())
+        .initial(1.)
+        .interval(0. ..1.)
+        .stepsize(0.125)
+        .equation(|s| -s.p)
+        .run();
+
+    let stats = &state.stats;
+    assert_eq!(stats.accepted_steps, 8);
+    assert_eq!(stats.rejected_steps, 0);
+    assert_eq!(stats.rhs_evals, 1 + 8 * 4);
+    assert_eq!(stats.min_step, Some(0.125));
+    assert_eq!(stats.max_step, Some(0.125));
+    assert!(stats.event_counts.is_empty());
+    assert_eq!(stats.location_iterations, 0);
+}
+
+#[test]
+fn event_counts() {
+    type Loc = Locator<f64, f64>;
+
+    let state = Solver::new::<f64, f64>()
+        .initial(0.)
+        .interval(0. ..10.)
+        .stepsize(0.3)
+        .equation(|_| 1.)
+        .on(Loc::zero(|s| s.p - 2.5), |_| {})
+        .on(Periodic::new(1.).with_offset(0.5), |_| {})
+        .run();
+
+    let stats = &state.stats;
+    assert_eq!(stats.event_counts, vec![1, 10]);
+    assert!(stats.location_iterations > 0);
+    assert!(stats.min_step.unwrap() < 0.3);
+    assert!((stats.max_step.unwrap() - 0.3).abs() < 1e-12);
+}
+
+#[test]
+fn first_same_as_last() {
+    // the last stage is the derivative at the end of the step, which is the first stage of the
+    // next step
+    let state = Solver::new::<f64, f64>()
+        .rk(RK::tsit5())
+        .initial(1.)
+        .interval(0. ..1.)
+        .stepsize(0.125)
+        .equation(|s| -s.p)
+        .run();
+    assert_eq!(state.stats.rhs_evals, 1 + 8 * 6);
+
+    let state = Solver::new::<f64, f64>()
+        .rk(RK::bs3())
+        .initial(1.)
+        .interval(0. ..1.)
+        .stepsize(0.125)
+        .equation(|s| -s.p)
+        .run();
+    assert_eq!(state.stats.rhs_evals, 1 + 8 * 3);
+}
+
+#[test]
+fn stats_are_sync() {
+    fn assert_sync<T: Sync>() {}
+    assert_sync::<SolveStats<f64>>();
+}
@@ -0,0 +1,79 @@
+use diffurch::*;
+
+#[test]
+fn same_as_run() {
+    let mut ts = vec![];
+    Solver::new::<f64, f64>()
+        .initial(1.)
+        .interval(0. ..2.)
+        .stepsize(0.3)
+        .equation(|s| -s.p)
+        .on_step(|s| ts.push((s.t, *s.p)))
+        .run();
+
+    let mut stepper = Solver::new::<f64, f64>()
+        .initial(1.)
+        .interval(0. ..2.)
+        .stepsize(0.3)
+        .equation(|s| -s.p)
+        .into_stepper();
+
+    let mut ts_stepper = vec![(stepper.state.t_curr, stepper.state.p_curr)];
+    for step in &mut stepper {
+        let StepInfo { t, p, event } = step.unwrap();
+        assert_eq!(event, None);
+        ts_stepper.push((t, p));
+    }
+    assert_eq!(ts, ts_stepper);
+    assert_eq!(stepper.finish().unwrap().t_curr, 2.);
+}
+
+#[test]
+fn events() {
+    let mut stepper = Solver::new::<f64, f64>()
+        .initial(0.)
+        .interval(0. ..3.)
+        .stepsize(0.3)
+        .equation(|_| 1.)
+        .on(Periodic::new(1.), |_| {})
+        .into_stepper();
+
+    let events = stepper
+        .by_ref()
+        .map(Result::unwrap)
+        .filter(|step| step.event == Some(0))
+        .map(|step| step.t)
+        .collect::<Vec<_>>();
+
+    assert_eq!(events, vec![1., 2., 3.]);
+}
+
+#[test]
+fn stop_extend_and_modify() {
+    let mut stepper = Solver::new::<f64, f64>()
+        .initial(0.)
+        .interval(0. ..10.)
+        .max_delay(10.)
+        .stepsize(0.5)
+        .equation(|_| 1.)
+        .into_stepper();
+
+    while let Some(step) = stepper.step() {
+        if step.unwrap().t >= 2. {
+            break;
+        }
+    }
+    assert_eq!(stepper.state.t_curr, 2.);
+
+    stepper.modify(|s| *s.p = 10.);
+    stepper.t_end = 3.;
+    while let Some(step) = stepper.step() {
+        step.unwrap();
+    }
+    let state = stepper.finish().unwrap();
+
+    assert_eq!(state.t_curr, 3.);
+    assert_eq!(state.p_curr, 11.);
+    assert!((state.eval::<0>(1.) - 1.).abs() < 1e-14);
+    assert!((state.eval::<0>(2.5) - 10.5).abs() < 1e-14);
+}
@@ -0,0 +1,115 @@
+use diffurch::*;
+use nalgebra::{Vector1, Vector2, vector};
+
+fn stepsize() -> AutomaticStepsize<f64, Vector1<f64>> {
+    AutomaticStepsize {
+        stepsize: 1e-3,
+        stepsize_range: 1e-10..1.,
+        atol: vector![1e-5],
+        rtol: vector![1e-5],
+        order: 3,
+        fac: 0.9,
+        fac_range: 0.2..5.,
+        initial_stepsize: None,
+    }
+}
+
+/// `x' = -λ(t) (x - cos t) - sin t` with the solution `cos t`, which is stiff while `λ(t)` is
+/// large.
+fn equation<const S: usize, const I: usize>(
+    lambda: impl Fn(f64) -> f64,
+) -> impl Fn(&StateRef<f64, Vector1<f64>, S, I, Vector1<f64>>) -> Vector1<f64> {
+    move |s| -lambda(s.t) * (*s.p - Vector1::new(s.t.cos())) - Vector1::new(s.t.sin())
+}
+
+#[test]
+fn stability_boundary() {
+    assert!((ButcherTableu::<f64, 1, 2>::euler().stability_boundary() - 2.).abs() < 1e-6);
+    assert!((ButcherTableu::<f64, 4, 2>::rk4().stability_boundary() - 2.785293563).abs() < 1e-6);
+    assert!((ButcherTableu::<f64, 5, 4>::rk43().stability_boundary() - 2.785293563).abs() < 1e-6);
+}
+
+#[test]
+fn detection() {
+    let nonstiff = Solver::new::<f64, Vector2<f64>>()
+        .initial(vector![1., 0.])
+        .interval(0. ..20.)
+        .stepsize(0.05)
+        .rk(ButcherTableu::rk43())
+        .stiffness_detection()
+        .equation(|s| vector![s.p[1], -s.p[0]])
+        .run();
+    assert_eq!(nonstiff.stats.stiff_steps, 0);
+    assert_eq!(nonstiff.stats.stiffness_detections, 0);
+    let estimate = nonstiff.stiffness.unwrap().estimate.unwrap();
+    assert!((estimate - 0.05).abs() < 0.01);
+
+    // the stepsize of the explicit method oscillates around its stability boundary
+    let stiff = Solver::new::<f64, Vector1<f64>>()
+        .initial(vector![1.])
+        .interval(0. ..1.)
+        .stepsize(stepsize())
+        .rk(ButcherTableu::rk43())
+        .stiffness_detection()
+        .equation(equation(|_| 1000.))
+        .run();
+    assert!(stiff.stats.stiff_steps > stiff.stats.accepted_steps / 2);
+    assert_eq!(stiff.stats.stiffness_detections, 1);
+    assert_eq!(stiff.stats.method_switches, 0);
+    assert!(stiff.stiffness.unwrap().is_stiff);
+}
+
+#[test]
+fn auto_switch() {
+    // the equation is stiff at the start, and non-stiff at the end
+    let lambda = |t: f64| 1000. / (1. + t * t);
+    let explicit = Solver::new::<f64, Vector1<f64>>()
+        .initial(vector![1.])
+        .interval(0. ..10.)
+        .stepsize(stepsize())
+        .rk(ButcherTableu::rk43())
+        .equation(equation(lambda))
+        .run();
+
+    let state = Solver::new::<f64, Vector1<f64>>()
+        .initial(vector![1.])
+        .interval(0. ..10.)
+        .stepsize(stepsize())
+        .rk(ButcherTableu::rk43())
+        .auto_switch(ButcherTableu::sdirk4())
+        .keep_history()
+        .equation(equation(lambda))
+        .run();
+    assert_eq!(state.stats.stiffness_detections, 1);
+    assert_eq!(state.stats.method_switches, 2);
+    assert!(state.stats.accepted_steps < explicit.stats.accepted_steps / 2);
+    assert!(state.rk.is_explicit());
+
+    // the history is evaluated with the dense output of the method of each step, whose error
+    // is dominated by the interpolant of sdirk4
+    let solution = state.into_solution();
+    for i in 0..=1000 {
+        let t = i as f64 * 0.01;
+        assert!((solution.eval(t)[0] - t.cos()).abs() < 5e-4);
+    }
+    assert_eq!(solution.history.rk_switches.len(), 2);
+    assert!(solution.history.rk_switches[1].1.is_explicit());
+}
+
+#[test]
+fn auto_switch_default_method() {
+    // sdirk4 is padded to the stages of the default rktp64, and the stepsize controller uses
+    // the order of each method
+    let lambda = |t: f64| 1000. / (1. + t * t);
+    let state = Solver::new::<f64, Vector1<f64>>()
+        .initial(vector![1.])
+        .interval(0. ..10.)
+        .stepsize(stepsize())
+        .auto_switch(ButcherTableu::sdirk4())
+        .equation(equation(lambda))
+        .run();
+    assert_eq!(state.stats.stiffness_detections, 1);
+    assert_eq!(state.stats.method_switches, 2);
+    assert!(state.rk.is_explicit());
+    assert!((state.p_curr[0] - 10f64.cos()).abs() < 1e-4);
+}
@@ -0,0 +1,302 @@
+use diffurch::{initial_condition::InitFn, *};
+use nalgebra::{Vector1, vector};
+
+/// Checks that the rows of `a` sum to `c` and the rows of `bi` sum to `b`, so that the dense
+/// output at the end of the step is the step itself (up to the roundoff relative to the magnitude
+/// of the row).
+fn assert_consistent<const S: usize, const I: usize>(rk: ButcherTableu<f64, S, I>) {
+    for i in 0..S {
+        let magnitude = rk.a[i].iter().map(|x| x.abs()).sum::<f64>().max(1.);
+        assert!((rk.a[i].iter().sum::<f64>() - rk.c[i]).abs() < 1e-14 * magnitude);
+        let magnitude = rk.bi[i].iter().map(|x| x.abs()).sum::<f64>().max(1.);
+        assert!((rk.bi[i].iter().sum::<f64>() - rk.b[i]).abs() < 1e-15 * magnitude);
+    }
+    assert!((rk.b.iter().sum::<f64>() - 1.).abs() < 1e-14);
+    assert!((rk.b2.iter().sum::<f64>() - 1.).abs() < 1e-14);
+    if let Some(b3) = rk.b3 {
+        assert!((b3.iter().sum::<f64>() - 1.).abs() < 1e-14);
+    }
+}
+
+/// Returns the errors of the step and the dense output of `x' = x cos t`, x(0) = 1 with the
+/// solution `exp(sin t)` on `[0, 4]` with constant stepsize. The error of the step is the largest
+/// one at `t = 0.4, 0.8, ..., 4`, which are the ends of steps for all the stepsizes `0.4 / n`.
+fn errors<const S: usize, const I: usize>(rk: ButcherTableu<f64, S, I>, stepsize: f64) -> [f64; 2] {
+    let solution = Solver::new::<f64, Vector1<f64>>()
+        .initial(vector![1.])
+        .interval(0. ..4.)
+        .stepsize(stepsize)
+        .rk(rk)
+        .equation(|s| *s.p * s.t.cos())
+        .solve();
+    let history = &solution.history;
+    let step = (history.t_deque.iter().zip(&history.p_deque))
+        .filter(|&(t, _)| (t / 0.4 - (t / 0.4).round()).abs() < 1e-9)
+        .map(|(t, p)| (p[0] - t.sin().exp()).abs())
+        .fold(0., f64::max);
+    let dense = (0..400)
+        .map(|i| {
+            let t = i as f64 * 0.01 + 0.003;
+            (solution.eval(t)[0] - t.sin().exp()).abs()
+        })
+        .fold(0., f64::max);
+    [step, dense]
+}
+
+/// Returns the observed orders of the step and of the local error of the dense output with the
+/// stepsizes `stepsize` and `stepsize / 2`.
+fn orders<const S: usize, const I: usize>(rk: ButcherTableu<f64, S, I>, stepsize: f64) -> [f64; 2] {
+    let [step, dense] = errors(rk, stepsize);
+    let [step_half, dense_half] = errors(rk, stepsize / 2.);
+    [(step / step_half).log2(), (dense / dense_half).log2()]
+}
+
+/// Returns the observed order of the local error of the dense output of a single step of
+/// `x' = x cos t`, x(0) = 1 with the stepsizes `stepsize` and `stepsize / 2`, which exceeds the
+/// order of the dense output by one.
+fn dense_order<const S: usize, const I: usize>(rk: ButcherTableu<f64, S, I>, stepsize: f64) -> f64 {
+    let error = |stepsize: f64| {
+        let solution = Solver::new::<f64, Vector1<f64>>()
+            .initial(vector![1.])
+            .interval(0. ..stepsize)
+            .stepsize(stepsize)
+            .rk(rk)
+            .equation(|s| *s.p * s.t.cos())
+            .solve();
+        (1..100)
+            .map(|i| {
+                let t = i as f64 * stepsize / 100.;
+                (solution.eval(t)[0] - t.sin().exp()).abs()
+            })
+            .fold(0., f64::max)
+    };
+    (error(stepsize) / error(stepsize / 2.)).log2()
+}
+
+/// Returns the error at `t = 10` of `x' = -x(t - π/2)` with the solution `cos t`, whose
+/// delayed argument is evaluated by the dense output.
+fn delay_error<const S: usize, const I: usize>(rk: ButcherTableu<f64, S, I>) -> f64 {
+    let state = Solver::new::<f64, f64>()
+        .initial(InitFn(|t: f64| t.cos(), ()))
+        .interval(0. ..10.)
+        .stepsize(0.2)
+        .rk(rk)
+        .equation(|s| -s.p(s.t - std::f64::consts::FRAC_PI_2))
+        .with_const_delay(std::f64::consts::FRAC_PI_2, 0)
+        .run();
+    (state.p_curr - 10f64.cos()).abs()
+}
+
+#[test]
+fn dormand_prince() {
+    assert_consistent(ButcherTableu::dopri5());
+    assert_consistent(ButcherTableu::dop853());
+
+    let [step, dense] = orders(ButcherTableu::dopri5(), 0.2);
+    assert!((step - 5.).abs() < 0.5);
+    assert!((dense - 5.).abs() < 0.5);
+    let [step, dense] = orders(ButcherTableu::dop853(), 0.2);
+    assert!((step - 8.).abs() < 0.5);
+    assert!((dense - 8.).abs() < 0.5);
+
+    assert!(delay_error(ButcherTableu::dopri5()) < 1e-6);
+    assert!(delay_error(ButcherTableu::dop853()) < 1e-12);
+
+    // the combined error estimate of DOP853 is smaller than the one of the embedded method of
+    // order 5 alone, so that the steps are larger
+    let adaptive = |rk: ButcherTableu<f64, 16, 8>| {
+        let state = Solver::new::<f64, Vector1<f64>>()
+            .initial(vector![1.])
+            .interval(0. ..10.)
+            .stepsize(AutomaticStepsize {
+                stepsize: 0.01,
+                stepsize_range: 1e-10..10.,
+                atol: vector![1e-10],
+                rtol: vector![1e-10],
+                order: 8,
+                fac: 0.9,
+                fac_range: 0.2..5.,
+                initial_stepsize: None,
+            })
+            .rk(rk)
+            .equation(|s| *s.p * s.t.cos())
+            .run();
+        (
+            (state.p_curr[0] - 10f64.sin().exp()).abs(),
+            state.stats.accepted_steps,
+        )
+    };
+    let (error, steps) = adaptive(ButcherTableu::dop853());
+    let (_, steps5) = adaptive(ButcherTableu {
+        b3: None,
+        ..ButcherTableu::dop853()
+    });
+    assert!(error < 1e-8);
+    assert!(steps < steps5);
+}
+
+#[test]
+fn verner() {
+    assert_consistent(ButcherTableu::vern6());
+    assert_consistent(ButcherTableu::vern7());
+    assert_consistent(ButcherTableu::vern8());
+    assert_consistent(ButcherTableu::vern9());
+
+    let [step, _] = orders(ButcherTableu::vern6(), 0.4);
+    assert!((step - 6.).abs() < 0.7);
+    let [step, _] = orders(ButcherTableu::vern7(), 0.4);
+    assert!((step - 7.).abs() < 0.7);
+    let [step, _] = orders(ButcherTableu::vern8(), 0.4);
+    assert!((step - 8.).abs() < 0.7);
+    let [step, _] = orders(ButcherTableu::vern9(), 0.4);
+    assert!((step - 9.).abs() < 0.7);
+
+    assert!(delay_error(ButcherTableu::vern6()) < 1e-11);
+    assert!(delay_error(ButcherTableu::vern7()) < 1e-12);
+    assert!(delay_error(ButcherTableu::vern8()) < 1e-13);
+    assert!(delay_error(ButcherTableu::vern9()) < 1e-13);
+}
+
+#[test]
+fn verner_dense_output() {
+    // the dense output has the order of the method, and its local error one more; with smaller
+    // stepsizes the local error of vern9 reaches the roundoff of its coefficients
+    for stepsize in [0.7, 0.6] {
+        assert!((dense_order(ButcherTableu::vern6(), stepsize) - 7.).abs() < 0.5);
+        assert!((dense_order(ButcherTableu::vern7(), stepsize) - 8.).abs() < 0.5);
+        assert!((dense_order(ButcherTableu::vern8(), stepsize) - 9.).abs() < 0.5);
+        assert!((dense_order(ButcherTableu::vern9(), stepsize) - 10.).abs() < 0.5);
+    }
+}
+
+#[test]
+fn dense_output_at_end_of_step() {
+    fn assert_step<const S: usize, const I: usize>(rk: ButcherTableu<f64, S, I>) {
+        let state = Solver::new::<f64, Vector1<f64>>()
+            .initial(vector![1.])
+            .interval(0. ..1.)
+            .stepsize(0.3)
+            .rk(rk)
+            .equation(|s| *s.p * s.t.cos())
+            .run();
+        assert_eq!(state.eval::<0>(state.t_curr), state.p_curr);
+    }
+
+    assert_step(ButcherTableu::dopri5());
+    assert_step(ButcherTableu::dop853());
+    assert_step(ButcherTableu::vern6());
+    assert_step(ButcherTableu::vern7());
+    assert_step(ButcherTableu::vern8());
+    assert_step(ButcherTableu::vern9());
+}
+
+#[test]
+fn lazy_dense_stages() {
+    // the stages used only by the dense output are evaluated only for the accepted steps, and
+    // the first stage of the step is evaluated again after a rejected step
+    let rk = ButcherTableu::vern7();
+    let state = Solver::new::<f64, Vector1<f64>>()
+        .initial(vector![1.])
+        .interval(0. ..20.)
+        .stepsize(AutomaticStepsize {
+            stepsize: 1.,
+            stepsize_range: 1e-10..10.,
+            atol: vector![1e-10],
+            rtol: vector![1e-10],
+            order: 7,
+            fac: 0.9,
+            fac_range: 0.2..5.,
+            initial_stepsize: None,
+        })
+        .rk(rk)
+        .equation(|s| *s.p * s.t.cos())
+        .run();
+    let stats = &state.stats;
+    assert!(stats.rejected_steps > 0);
+    // one of the extra stages is the derivative at the end of the step, which is not evaluated
+    // again
+    assert_eq!(
+        stats.rhs_evals,
+        1 + stats.accepted_steps * (16 - 1) + stats.rejected_steps * (rk.main_stages() + 1)
+    );
+}
+
+#[test]
+fn without_main_stages() {
+    // the position does not change, and the derivative is evaluated at the end of the step
+    let rk = ButcherTableu {
+        b: [0.; 4],
+        b2: [0.; 4],
+        ..ButcherTableu::rk4()
+    };
+    assert_eq!(rk.main_stages(), 0);
+    let state = Solver::new::<f64, f64>()
+        .initial(1.)
+        .interval(0. ..1.)
+        .stepsize(0.25)
+        .rk(rk)
+        .equation(|s| s.t)
+        .run();
+    assert_eq!(state.p_curr, 1.);
+    assert_eq!(state.d_curr, 1.);
+}
+
+#[test]
+fn tsitouras_bogacki_shampine() {
+    assert_consistent(ButcherTableu::tsit5());
+    assert_consistent(ButcherTableu::bs3());
+
+    // the leading error terms of Tsit5 are small by construction, so its error at the end of
+    // the interval decreases faster for these stepsizes
+    let [step, dense] = orders(ButcherTableu::tsit5(), 0.2);
+    assert!(step > 4.5);
+    assert!((dense - 5.).abs() < 0.5);
+    let [step, dense] = orders(ButcherTableu::bs3(), 0.2);
+    assert!((step - 3.).abs() < 0.5);
+    assert!((dense - 3.).abs() < 0.5);
+
+    assert!(delay_error(ButcherTableu::tsit5()) < 1e-6);
+    assert!(delay_error(ButcherTableu::bs3()) < 1e-3);
+}
+
+/// Returns the times of the events and the error at `t = 3` of `x' = -x`, x(0) = 1, which is
+/// reset to 1 whenever it decreases to 1/2.
+fn reset_errors<const S: usize, const I: usize>(rk: ButcherTableu<f64, S, I>) -> [f64; 2] {
+    let mut times = vec![];
+    let state = Solver::new::<f64, f64>()
+        .initial(1.)
+        .interval(0. ..3.)
+        .stepsize(0.05)
+        .rk(rk)
+        .equation(|s| -s.p)
+        .on_mut(Locator::<f64, f64>::zero(|s| s.p - 0.5), |s| {
+            times.push(*s.t);
+            *s.p = 1.;
+        })
+        .run();
+    assert_eq!(times.len(), 4);
+    let times_error = times
+        .into_iter()
+        .enumerate()
+        .map(|(i, t)| (t - (i + 1) as f64 * 2f64.ln()).abs())
+        .fold(0., f64::max);
+    [
+        times_error,
+        (state.p_curr - (4. * 2f64.ln() - 3.).exp()).abs(),
+    ]
+}
+
+#[test]
+fn restart_after_events() {
+    // the derivative at the end of the step is evaluated again, when the event changes the state
+    for [times_error, error] in [
+        reset_errors(ButcherTableu::dopri5()),
+        reset_errors(ButcherTableu::tsit5()),
+    ] {
+        assert!(times_error < 1e-8);
+        assert!(error < 1e-8);
+    }
+    let [times_error, error] = reset_errors(ButcherTableu::bs3());
+    assert!(times_error < 1e-4);
+    assert!(error < 1e-4);
+}
@@ -0,0 +1,92 @@
+use diffurch::*;
+
+#[test]
+fn steps_land_on_tstops() {
+    let mut ts = vec![];
+
+    let state = Solver::new::<f64, f64>()
+        .rk(RK::euler())
+        .initial(0.)
+        .equation(|_| 0.)
+        .interval(0. ..3.)
+        .stepsize(0.75)
+        .tstops([2.2, 0.1, 1.])
+        .tstops([0.1, 5.])
+        .on_step(|s| ts.push(s.t))
+        .run();
+
+    assert_eq!(ts, vec![0.0, 0.1, 0.85, 1.0, 1.75, 2.2, 2.95, 3.0]);
+    assert_eq!(state.t_curr, 3.);
+}
+
+#[test]
+fn tstops_backward() {
+    let mut ts = vec![];
+
+    Solver::new::<f64, f64>()
+        .rk(RK::euler())
+        .initial(0.)
+        .equation(|_| 0.)
+        .interval(0. ..-2.)
+        .stepsize(0.75)
+        .tstops([-1., 0.5, -0.5])
+        .on_step(|s| ts.push(s.t))
+        .run();
+
+    assert_eq!(ts, vec![0.0, -0.5, -1., -1.75, -2.]);
+}
+
+#[test]
+fn piecewise_forcing() {
+    // the forcing switches on at t = 1, which is not a multiple of the stepsize, and the method
+    // has no stages at the end of a step
+    let solver = || {
+        Solver::new::<f64, f64>()
+            .rk(RK::midpoint())
+            .initial(0.)
+            .equation(|s| if s.t < 1. { 0. } else { 1. })
+            .interval(0. ..2.)
+            .stepsize(0.3)
+    };
+
+    let state = solver().tstops([1.]).run();
+    assert!((state.p_curr - 1.).abs() < 1e-12);
+
+    let state = solver().run();
+    assert!((state.p_curr - 1.).abs() > 1e-2);
+}
+
+#[test]
+fn tstops_propagated_through_delay() {
+    let mut ts = vec![];
+
+    Solver::new::<f64, f64>()
+        .rk(RK::euler())
+        .initial(0.)
+        .equation(|_| 0.)
+        .interval(0. ..3.)
+        .stepsize(0.75)
+        .tstops([0.1])
+        .tstops_disco()
+        .with_const_delay(1., 0)
+        .on_step(|s| ts.push(s.t))
+        .run();
+
+    assert_eq!(ts, vec![0.0, 0.1, 0.85, 1.1, 1.85, 2.1, 2.85, 3.0]);
+}
+
+#[test]
+fn non_finite_tstop() {
+    let result = Solver::new::<f64, f64>()
+        .initial(0.)
+        .equation(|_| 1.)
+        .interval(0. ..2.)
+        .tstops([1., f64::NAN])
+        .try_run();
+
+    let Err(SolverError::NonFiniteTstop { tstop, t, step }) = result else {
+        panic!("expected non-finite stop time error")
+    };
+    assert!(tstop.is_nan());
+    assert_eq!((t, step), (0., 0));
+}
@@ -0,0 +1,108 @@
+use std::cell::Cell;
+
+use diffurch::*;
+use nalgebra::{Vector2, vector};
+
+type Loc = Locator<f64, Vector2<f64>>;
+
+// Ball, dropped from the height 1 with unit gravity, and restitution coefficient 0.5, comes to
+// rest at t = 3 sqrt(2) after infinitely many bounces.
+const REST_TIME: f64 = 4.242640687119286;
+
+#[test]
+fn error() {
+    let mut bounces = 0;
+    let result = Solver::new::<f64, Vector2<f64>>()
+        .initial(vector![1., 0.])
+        .interval(0. ..10.)
+        .stepsize(0.01)
+        .equation(|s| vector![s.p[1], -1.])
+        .on(Periodic::new(0.25), |_| {})
+        .on_mut_zeno(
+            Loc::below_zero(|s| s.p[0]),
+            |s| {
+                s.p[1] *= -0.5;
+                bounces += 1;
+            },
+            Zeno::new(5, 1.),
+            ZenoResponse::Error,
+        )
+        .try_run();
+
+    let Err(SolverError::Zeno { t, event, .. }) = result else {
+        panic!("expected Zeno error")
+    };
+    assert_eq!(event, Some(1));
+    assert!(t < REST_TIME);
+    assert!(bounces >= 4);
+}
+
+#[test]
+fn stop() {
+    let state = Solver::new::<f64, Vector2<f64>>()
+        .initial(vector![1., 0.])
+        .interval(0. ..10.)
+        .stepsize(0.01)
+        .equation(|s| vector![s.p[1], -1.])
+        .on_mut_zeno(
+            Loc::below_zero(|s| s.p[0]),
+            |s| s.p[1] *= -0.5,
+            Zeno::new(5, 1.),
+            ZenoResponse::Stop,
+        )
+        .run();
+
+    assert!(state.t_curr < REST_TIME);
+    assert!(state.p_curr[0].abs() < 1e-9);
+    assert_eq!(
+        state.termination,
+        Some(Termination::Stopped {
+            event: Some(0),
+            reason: "Zeno behavior".into()
+        })
+    );
+}
+
+#[test]
+fn switch_to_rest() {
+    let resting = Cell::new(false);
+    let state = Solver::new::<f64, Vector2<f64>>()
+        .initial(vector![1., 0.])
+        .interval(0. ..10.)
+        .stepsize(0.01)
+        .equation(|s| vector![s.p[1], if resting.get() { 0. } else { -1. }])
+        .on_mut_zeno_with(
+            Loc::below_zero(|s| s.p[0]),
+            |s| s.p[1] *= -0.5,
+            Zeno::new(5, 1.),
+            |s| {
+                *s.p = vector![0., 0.];
+                resting.set(true);
+            },
+        )
+        .try_run()
+        .unwrap();
+
+    assert!(resting.get());
+    assert_eq!(state.t_curr, 10.);
+    assert_eq!(state.p_curr, vector![0., 0.]);
+}
+
+#[test]
+fn regular_firings_are_not_zeno() {
+    let state = Solver::new::<f64, f64>()
+        .initial(0.)
+        .interval(0. ..20.)
+        .stepsize(0.3)
+        .equation(|_| 1.)
+        .on_mut_zeno(
+            Periodic::new(0.1),
+            |_| {},
+            Zeno::new(5, 1.),
+            ZenoResponse::Error,
+        )
+        .try_run()
+        .unwrap();
+
+    assert_eq!(state.t_curr, 20.);
+}